@@ -2,12 +2,31 @@ use eframe::egui;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use chrono::{NaiveDateTime, Datelike, Timelike};
+use crate::app::core_sweep::{CoreSweepResult, rank_results, ui_ranked_table};
+use crate::app::network_stress::{NetworkPhase, NetworkPhaseSummary, ui_summary_table};
+use crate::app::metadata_stress::{MetadataOp, MetadataOpSummary};
+use crate::app::storage_sync::{SyncMode, SyncSummary, ui_sync_table};
+use crate::app::system_stress::SystemSample;
+use crate::app::journal::{self, CrashedRun};
+use crate::app::inventory;
+use crate::app::seed;
+use crate::app::bench::{self, BenchStats};
+use crate::app::selectable_stress::crypto_stress::CryptoAlgorithm;
+use crate::app::selectable_stress::micro_stress::MicroKernel;
+use crate::app::throttle::{self, TimelineSample};
+use crate::app::scoring::{self, ScoreMode, ScoreSummary, ScoreWorkload, SCORE_VERSION};
 
 #[derive(PartialEq, Eq)]
 pub enum AnalyzerTab {
     StorageStress,
     CpuStress,
     SelectableStress,
+    CoreSweep,
+    NetworkStress,
+    MetadataStress,
+    SystemStress,
+    Scores,
+    CrashedRuns,
     // Add more analyzer types here
 }
 
@@ -16,6 +35,8 @@ pub struct Analyzer {
     pub selected_log_index: Option<usize>,
     pub dev_mode: bool,
     pub marked_for_delete: Option<(usize, std::time::Instant)>,
+    // Journaled runs that never reached a clean end, found at launch
+    pub crashed_runs: Vec<CrashedRun>,
 }
 
 impl Default for Analyzer {
@@ -25,22 +46,21 @@ impl Default for Analyzer {
             selected_log_index: None,
             dev_mode: false,
             marked_for_delete: None,
+            crashed_runs: Vec::new(),
         }
     }
 }
 
 impl Analyzer {
     pub fn log_dir(&self) -> std::path::PathBuf {
-        if self.dev_mode {
-            std::path::PathBuf::from("log")
-        } else {
-            std::env::current_exe().ok().and_then(|p| p.parent().map(|d| d.to_path_buf())).unwrap_or_else(|| std::path::PathBuf::from("."))
-                .join("log")
-        }
+        crate::app::log_dir(self.dev_mode)
     }
 }
 
-fn parse_storage_stress_filename(name: &str) -> Option<(String, String, String, Option<u32>, Option<u32>)> {
+// (formatted date, raw date, hash or type, first numeric param, duration)
+type ParsedLogName = (String, String, String, Option<u32>, Option<u32>);
+
+fn parse_storage_stress_filename(name: &str) -> Option<ParsedLogName> {
     // Format: storage_stress_<hash>_<YYYYMMDD_HHMMSS>.csv
     // Optionally: storage_stress_<hash>_<YYYYMMDD_HHMMSS>_buf<buf>_dur<dur>.csv
     let base = name.strip_prefix("storage_stress_")?.strip_suffix(".csv")?;
@@ -75,7 +95,7 @@ fn parse_storage_stress_filename(name: &str) -> Option<(String, String, String,
     Some((formatted, date_str, hash.to_string(), buffer_size, duration))
 }
 
-fn analyze_storage_stress_csv(path: &str) -> Option<(Vec<f64>, Vec<f64>, f64, f64, f64, f64, f64, f64, f64, f64)> {
+pub fn analyze_storage_stress_csv(path: &str) -> Option<(Vec<f64>, Vec<f64>, f64, f64, f64, f64, f64, f64, f64, f64)> {
     let file = File::open(path).ok()?;
     let reader = BufReader::new(file);
     let mut write_speeds = vec![];
@@ -112,8 +132,67 @@ fn analyze_storage_stress_csv(path: &str) -> Option<(Vec<f64>, Vec<f64>, f64, f6
     Some((write_speeds, read_speeds, avg_write, avg_read, max_write, max_read, min_write, min_read, std_write, std_read))
 }
 
-fn parse_cpu_stress_filename(name: &str) -> Option<(String, String, String, Option<u32>, Option<u32>)> {
-    // Format: cpu_stress_<hash>_<YYYYMMDD_HHMMSS>_int<intensity>_dur<duration>.csv
+// Durability logs: one "<mode>_total" row per sync mode plus "<mode>_mode"/"<mode>_write_bytes"/"<mode>_error" notes,
+// sample rows are only used for the live view
+fn analyze_storage_sync_csv(path: &str) -> Option<Vec<SyncSummary>> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut totals: Vec<SyncSummary> = vec![];
+    let mut effective = std::collections::HashMap::new();
+    let mut write_bytes = std::collections::HashMap::new();
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        if let [_, operation, note] = parts.as_slice() {
+            if let Some(mode) = operation.strip_suffix("_mode").and_then(SyncMode::from_name) {
+                effective.insert(mode, note.to_string());
+            } else if let Some(mode) = operation.strip_suffix("_write_bytes").and_then(SyncMode::from_name) {
+                if let Ok(bytes) = note.parse::<usize>() {
+                    write_bytes.insert(mode, bytes);
+                }
+            } else if let Some(mode) = operation.strip_suffix("_error").and_then(SyncMode::from_name) {
+                // A mode that failed to open has no total row, so the error stands in for it
+                match totals.iter_mut().find(|t| t.mode == mode) {
+                    Some(t) => t.error = Some(note.to_string()),
+                    None => totals.push(SyncSummary {
+                        mode,
+                        mbps: 0.0,
+                        ops_per_sec: 0.0,
+                        p50_us: None,
+                        p95_us: None,
+                        p99_us: None,
+                        max_us: None,
+                        error: Some(note.to_string()),
+                        effective: effective.get(&mode).cloned().unwrap_or_else(|| mode.name().to_string()),
+                        write_bytes: write_bytes.get(&mode).copied(),
+                    }),
+                }
+            }
+            continue;
+        }
+        if parts.len() != 8 {
+            continue;
+        }
+        let Some(mode) = parts[1].strip_suffix("_total").and_then(SyncMode::from_name) else {
+            continue;
+        };
+        totals.push(SyncSummary {
+            mode,
+            mbps: parts[2].parse().unwrap_or(0.0),
+            ops_per_sec: parts[3].parse().unwrap_or(0.0),
+            p50_us: parts[4].parse().ok(),
+            p95_us: parts[5].parse().ok(),
+            p99_us: parts[6].parse().ok(),
+            max_us: parts[7].parse().ok(),
+            error: None,
+            effective: effective.get(&mode).cloned().unwrap_or_else(|| mode.name().to_string()),
+            write_bytes: write_bytes.get(&mode).copied(),
+        });
+    }
+    Some(totals)
+}
+
+fn parse_cpu_stress_filename(name: &str) -> Option<ParsedLogName> {
+    // Format: cpu_stress_<hash>_<YYYYMMDD_HHMMSS>_int<intensity>_dur<duration>[_prof<profile>][_util<pct>|_utilcore<pct>].csv
     let base = name.strip_prefix("cpu_stress_")?.strip_suffix(".csv")?;
     let parts: Vec<&str> = base.split('_').collect();
     if parts.len() < 3 {
@@ -146,7 +225,7 @@ fn parse_cpu_stress_filename(name: &str) -> Option<(String, String, String, Opti
     Some((formatted, date_str, hash.to_string(), intensity, duration))
 }
 
-fn analyze_cpu_stress_csv(path: &str) -> Option<(Vec<Vec<f64>>, Vec<f64>, f64, f64, f64, f64)> {
+pub fn analyze_cpu_stress_csv(path: &str) -> Option<(Vec<Vec<f64>>, Vec<f64>, f64, f64, f64, f64)> {
     let file = File::open(path).ok()?;
     let reader = BufReader::new(file);
     let mut thread_rates: Vec<Vec<f64>> = vec![];
@@ -154,7 +233,7 @@ fn analyze_cpu_stress_csv(path: &str) -> Option<(Vec<Vec<f64>>, Vec<f64>, f64, f
     for line in reader.lines().skip(1) { // skip header
         if let Ok(l) = line {
             let parts: Vec<&str> = l.split(',').collect();
-            if parts.len() == 3 {
+            if parts.len() >= 3 {
                 if let (Ok(tid), Ok(rate)) = (parts[1].parse::<usize>(), parts[2].parse::<f64>()) {
                     if tid >= thread_rates.len() {
                         thread_rates.resize(tid + 1, vec![]);
@@ -180,7 +259,39 @@ fn analyze_cpu_stress_csv(path: &str) -> Option<(Vec<Vec<f64>>, Vec<f64>, f64, f
     Some((thread_rates, all_rates, avg, max, min, stddev))
 }
 
-fn parse_selectable_stress_filename(name: &str) -> Option<(String, String, String, Option<u32>, Option<u32>)> {
+// Total throughput per sampling tick; a tick starts at each thread 0 row. Logs from before the
+// cpu_mhz/cpu_temp_c columns still give a timeline, just without sensor readings.
+pub fn analyze_cpu_timeline_csv(path: &str) -> Option<Vec<TimelineSample>> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut samples: Vec<TimelineSample> = vec![];
+    let mut start = None;
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        let (Ok(time), Ok(tid), Ok(rate)) = (chrono::DateTime::parse_from_rfc3339(parts[0]), parts[1].parse::<usize>(), parts[2].parse::<f64>()) else {
+            continue;
+        };
+        let start = *start.get_or_insert(time);
+        if tid == 0 || samples.is_empty() {
+            samples.push(TimelineSample {
+                elapsed_secs: (time - start).num_milliseconds() as f64 / 1000.0,
+                rate: 0.0,
+                cpu_mhz: parts.get(4).and_then(|m| m.parse().ok()),
+                cpu_temp_c: parts.get(5).and_then(|t| t.parse().ok()),
+                target_load: parts.get(3).and_then(|l| l.parse().ok()),
+            });
+        }
+        if let Some(tick) = samples.last_mut() {
+            tick.rate += rate;
+        }
+    }
+    Some(samples)
+}
+
+fn parse_selectable_stress_filename(name: &str) -> Option<ParsedLogName> {
     // Format: selectable_<type>_<YYYYMMDD_HHMMSS>_params.csv
     let base = name.strip_prefix("selectable_")?.strip_suffix(".csv")?;
     let parts: Vec<&str> = base.split('_').collect();
@@ -213,11 +324,13 @@ fn analyze_selectable_stress_csv(path: &str) -> Option<(String, String, u64, Vec
         let l = line.ok()?;
         if i == 0 { continue; } // skip header
         let parts: Vec<&str> = l.split(',').collect();
-        if parts.len() >= 6 {
+        // Params hold commas themselves, so the counts are read from the end of the row
+        let n = parts.len();
+        if n >= 6 {
             workload = parts[1].to_string();
-            params = parts[2].to_string();
-            let tid = parts[4].parse().unwrap_or(0);
-            let ops = parts[5].parse().unwrap_or(0);
+            params = parts[2..n - 3].join(",");
+            let tid = parts[n - 2].parse().unwrap_or(0);
+            let ops = parts[n - 1].parse().unwrap_or(0);
             thread_ops.push((tid, ops));
         }
     }
@@ -225,6 +338,410 @@ fn analyze_selectable_stress_csv(path: &str) -> Option<(String, String, u64, Vec
     Some((workload, params, total_ops, thread_ops))
 }
 
+// Benchmark-mode rows: warm-up seconds, then ops/sec per repetition with its outlier flag
+type SelectableBench = (u32, Vec<(f64, bool)>, BenchStats);
+
+fn analyze_selectable_bench_csv(path: &str) -> Option<SelectableBench> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut warmup = None;
+    let mut stable_cv_percent = 0.0;
+    let mut repetitions = vec![];
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        match parts.as_slice() {
+            ["bench", warm, _, cv] => {
+                warmup = warm.parse().ok();
+                stable_cv_percent = cv.parse().unwrap_or(0.0);
+            }
+            ["repetition", _, rate, outlier] => {
+                if let Ok(rate) = rate.parse() {
+                    repetitions.push((rate, *outlier == "1"));
+                }
+            }
+            _ => {}
+        }
+    }
+    let rates: Vec<f64> = repetitions.iter().map(|&(r, _)| r).collect();
+    let stats = BenchStats::from_samples(&rates, stable_cv_percent)?;
+    Some((warmup?, repetitions, stats))
+}
+
+// Matrix rows: GFLOPS (or GOPS for integers) per thread, with the unit
+fn analyze_selectable_matrix_csv(path: &str) -> Option<(String, Vec<(usize, f64)>)> {
+    let file = File::open(path).ok()?;
+    let mut unit = None;
+    let mut rates = vec![];
+    for line in BufReader::new(file).lines().skip(1).map_while(Result::ok) {
+        if let ["matrix", tid, rate, u] = line.split(',').collect::<Vec<_>>().as_slice()
+            && let (Ok(tid), Ok(rate)) = (tid.parse(), rate.parse()) {
+            rates.push((tid, rate));
+            unit = Some(u.to_string());
+        }
+    }
+    Some((unit?, rates))
+}
+
+// Crypto rows: acceleration flags, then self-test result and MB/s per algorithm (decrypt empty for hashes)
+type SelectableCrypto = (Vec<(String, bool)>, Vec<(String, bool, f64, Option<f64>, u64)>);
+
+fn analyze_selectable_crypto_csv(path: &str) -> Option<SelectableCrypto> {
+    let file = File::open(path).ok()?;
+    let mut accel = vec![];
+    let mut self_tests = std::collections::HashMap::new();
+    let mut algorithms = vec![];
+    for line in BufReader::new(file).lines().skip(1).map_while(Result::ok) {
+        match line.split(',').collect::<Vec<_>>().as_slice() {
+            ["accel", feature, on] => accel.push((feature.to_string(), *on == "1")),
+            ["selftest", name, passed] => {
+                self_tests.insert(name.to_string(), *passed == "1");
+            }
+            ["crypto", name, forward, decrypt, mismatches] => {
+                let label = CryptoAlgorithm::from_name(name).map(|a| a.label().to_string()).unwrap_or_else(|| name.to_string());
+                let passed = self_tests.get(*name).copied().unwrap_or(false);
+                algorithms.push((label, passed, forward.parse().unwrap_or(0.0), decrypt.parse().ok(), mismatches.parse().unwrap_or(0)));
+            }
+            _ => {}
+        }
+    }
+    if algorithms.is_empty() {
+        return None;
+    }
+    Some((accel, algorithms))
+}
+
+// Micro-workload rows: ops/sec and mismatched results per kernel
+fn analyze_selectable_micro_csv(path: &str) -> Option<Vec<(String, f64, u64)>> {
+    let file = File::open(path).ok()?;
+    let mut kernels = vec![];
+    for line in BufReader::new(file).lines().skip(1).map_while(Result::ok) {
+        if let ["micro", name, rate, mismatches] = line.split(',').collect::<Vec<_>>().as_slice() {
+            let label = MicroKernel::from_name(name).map(|k| k.label().to_string()).unwrap_or_else(|| name.to_string());
+            kernels.push((label, rate.parse().unwrap_or(0.0), mismatches.parse().unwrap_or(0)));
+        }
+    }
+    if kernels.is_empty() {
+        return None;
+    }
+    Some(kernels)
+}
+
+// Compression summary row: compress MB/s, decompress MB/s, ratio and mismatched round trips
+fn analyze_selectable_compression_csv(path: &str) -> Option<(f64, f64, f64, u64)> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file).lines().skip(1).map_while(Result::ok).find_map(|line| {
+        match line.split(',').collect::<Vec<_>>().as_slice() {
+            ["compression", compress, decompress, ratio, mismatches] => {
+                Some((compress.parse().ok()?, decompress.parse().ok()?, ratio.parse().ok()?, mismatches.parse().ok()?))
+            }
+            _ => None,
+        }
+    })
+}
+
+fn parse_core_sweep_filename(name: &str) -> Option<ParsedLogName> {
+    // Format: core_sweep_<hash>_<YYYYMMDD_HHMMSS>_dur<secs_per_core>.csv
+    let base = name.strip_prefix("core_sweep_")?.strip_suffix(".csv")?;
+    let parts: Vec<&str> = base.split('_').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let hash = parts[0];
+    let date_str = parts[1..3].join("_");
+    let dt = chrono::NaiveDateTime::parse_from_str(&date_str, "%Y%m%d_%H%M%S").ok()?;
+    let formatted = format!(
+        "{} {}, {}: {:02}:{:02}:{:02}",
+        dt.format("%B"),
+        dt.day(),
+        dt.year(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    );
+    let duration = parts[3..].iter().find_map(|p| p.strip_prefix("dur")).and_then(|d| d.parse::<u32>().ok());
+    Some((formatted, date_str, hash.to_string(), None, duration))
+}
+
+fn analyze_core_sweep_csv(path: &str) -> Option<Vec<CoreSweepResult>> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut results = vec![];
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        // Logs from before the status column have eight fields
+        if parts.len() == 8 || parts.len() == 9 {
+            results.push(CoreSweepResult {
+                cpu: parts[1].parse().ok()?,
+                core: parts[2].parse().ok()?,
+                iterations_per_sec: parts[3].parse().ok()?,
+                errors: parts[4].parse().ok()?,
+                first_error_secs: parts[5].parse().ok(),
+                max_mhz: parts[6].parse().unwrap_or(0),
+                max_temp_c: parts[7].parse().ok(),
+                pin_failed: parts.get(8) == Some(&"pin_failed"),
+            });
+        }
+    }
+    rank_results(&mut results);
+    Some(results)
+}
+
+fn parse_network_stress_filename(name: &str) -> Option<ParsedLogName> {
+    // Format: network_stress_<hash>_<YYYYMMDD_HHMMSS>_str<streams>_dur<duration>.csv
+    let base = name.strip_prefix("network_stress_")?.strip_suffix(".csv")?;
+    let parts: Vec<&str> = base.split('_').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let hash = parts[0];
+    let date_str = parts[1..3].join("_");
+    let dt = chrono::NaiveDateTime::parse_from_str(&date_str, "%Y%m%d_%H%M%S").ok()?;
+    let formatted = format!(
+        "{} {}, {}: {:02}:{:02}:{:02}",
+        dt.format("%B"),
+        dt.day(),
+        dt.year(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    );
+    let streams = parts[3..].iter().find_map(|p| p.strip_prefix("str")).and_then(|s| s.parse::<u32>().ok());
+    let duration = parts[3..].iter().find_map(|p| p.strip_prefix("dur")).and_then(|d| d.parse::<u32>().ok());
+    Some((formatted, date_str, hash.to_string(), streams, duration))
+}
+
+// Per phase: the "total" row summary plus min/max/stddev of the 200ms samples
+fn analyze_network_stress_csv(path: &str) -> Option<Vec<(NetworkPhaseSummary, f64, f64, f64)>> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut samples: Vec<(NetworkPhase, Vec<f64>)> = vec![];
+    let mut totals: Vec<NetworkPhaseSummary> = vec![];
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() != 8 {
+            continue;
+        }
+        let (Some(phase), Ok(rate)) = (NetworkPhase::from_name(parts[1]), parts[3].parse::<f64>()) else {
+            continue;
+        };
+        if parts[2] == "total" {
+            totals.push(NetworkPhaseSummary {
+                phase,
+                rate,
+                p50_us: parts[4].parse().ok(),
+                p95_us: parts[5].parse().ok(),
+                p99_us: parts[6].parse().ok(),
+                errors: parts[7].parse().unwrap_or(0),
+            });
+        } else {
+            match samples.iter_mut().find(|(p, _)| *p == phase) {
+                Some((_, rates)) => rates.push(rate),
+                None => samples.push((phase, vec![rate])),
+            }
+        }
+    }
+    Some(totals.into_iter().map(|total| {
+        let rates = samples.iter().find(|(p, _)| *p == total.phase).map(|(_, r)| r.as_slice()).unwrap_or(&[]);
+        let min = rates.iter().cloned().fold(f64::NAN, f64::min);
+        let max = rates.iter().cloned().fold(f64::NAN, f64::max);
+        let stddev = if !rates.is_empty() {
+            let mean = rates.iter().sum::<f64>() / rates.len() as f64;
+            (rates.iter().map(|v| (v-mean).powi(2)).sum::<f64>() / rates.len() as f64).sqrt()
+        } else { 0.0 };
+        (total, min, max, stddev)
+    }).collect())
+}
+
+fn parse_metadata_stress_filename(name: &str) -> Option<ParsedLogName> {
+    // Format: metadata_stress_<hash>_<YYYYMMDD_HHMMSS>_thr<threads>_files<files_per_dir>_dur<duration>.csv
+    let base = name.strip_prefix("metadata_stress_")?.strip_suffix(".csv")?;
+    let parts: Vec<&str> = base.split('_').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let hash = parts[0];
+    let date_str = parts[1..3].join("_");
+    let dt = chrono::NaiveDateTime::parse_from_str(&date_str, "%Y%m%d_%H%M%S").ok()?;
+    let formatted = format!(
+        "{} {}, {}: {:02}:{:02}:{:02}",
+        dt.format("%B"),
+        dt.day(),
+        dt.year(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    );
+    let threads = parts[3..].iter().find_map(|p| p.strip_prefix("thr")).and_then(|t| t.parse::<u32>().ok());
+    let duration = parts[3..].iter().find_map(|p| p.strip_prefix("dur")).and_then(|d| d.parse::<u32>().ok());
+    Some((formatted, date_str, hash.to_string(), threads, duration))
+}
+
+// Per operation: the "total" row summary plus min/max of the sampled ops/sec
+fn analyze_metadata_stress_csv(path: &str) -> Option<Vec<(MetadataOpSummary, f64, f64)>> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut samples: Vec<(MetadataOp, Vec<f64>)> = vec![];
+    let mut totals: Vec<MetadataOpSummary> = vec![];
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() != 8 {
+            continue;
+        }
+        let (Some(op), Ok(rate)) = (MetadataOp::from_name(parts[2]), parts[3].parse::<f64>()) else {
+            continue;
+        };
+        if parts[1] == "total" {
+            totals.push(MetadataOpSummary {
+                op,
+                ops_per_sec: rate,
+                p50_us: parts[4].parse().ok(),
+                p95_us: parts[5].parse().ok(),
+                p99_us: parts[6].parse().ok(),
+                max_us: parts[7].parse().ok(),
+            });
+        } else {
+            match samples.iter_mut().find(|(o, _)| *o == op) {
+                Some((_, rates)) => rates.push(rate),
+                None => samples.push((op, vec![rate])),
+            }
+        }
+    }
+    Some(totals.into_iter().map(|total| {
+        let rates = samples.iter().find(|(o, _)| *o == total.op).map(|(_, r)| r.as_slice()).unwrap_or(&[]);
+        let min = rates.iter().cloned().fold(f64::NAN, f64::min);
+        let max = rates.iter().cloned().fold(f64::NAN, f64::max);
+        (total, min, max)
+    }).collect())
+}
+
+fn parse_score_filename(name: &str) -> Option<ParsedLogName> {
+    // Format: score_<hash>_<YYYYMMDD_HHMMSS>_v<score_version>_runs<repetitions>.csv
+    let base = name.strip_prefix("score_")?.strip_suffix(".csv")?;
+    let parts: Vec<&str> = base.split('_').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let hash = parts[0];
+    let date_str = parts[1..3].join("_");
+    let dt = chrono::NaiveDateTime::parse_from_str(&date_str, "%Y%m%d_%H%M%S").ok()?;
+    let formatted = format!(
+        "{} {}, {}: {:02}:{:02}:{:02}",
+        dt.format("%B"),
+        dt.day(),
+        dt.year(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    );
+    let version = parts[3..].iter().find_map(|p| p.strip_prefix('v')).and_then(|v| v.parse::<u32>().ok());
+    let runs = parts[3..].iter().find_map(|p| p.strip_prefix("runs")).and_then(|r| r.parse::<u32>().ok());
+    Some((formatted, date_str, hash.to_string(), version, runs))
+}
+
+// Summary rows only; per-run rows are kept in the log for reference
+pub fn analyze_score_csv(path: &str) -> Option<Vec<ScoreSummary>> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut summaries = vec![];
+    // Repetitions per mode and workload, from the per-run rows that precede each mode's summary
+    let mut run_counts: std::collections::HashMap<(String, String), usize> = std::collections::HashMap::new();
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() >= 3 && parts[0] == "run" {
+            *run_counts.entry((parts[1].to_string(), parts[2].to_string())).or_insert(0) += 1;
+        }
+        // 8 columns before the benchmark harness added median/stddev/cv_percent/rejected/stable
+        if (parts.len() == 8 || parts.len() == 13) && parts[0] == "summary" {
+            // A run only counts towards the composite once its last workload finished
+            let last_workload = ScoreWorkload::ALL[ScoreWorkload::ALL.len() - 1].name();
+            let counted = if parts[2] == "composite" { last_workload } else { parts[2] };
+            let samples = run_counts.get(&(parts[1].to_string(), counted.to_string())).copied().unwrap_or(0);
+            let stats = (parts.len() == 13).then(|| BenchStats {
+                samples,
+                rejected: parts[11].parse().unwrap_or(0),
+                mean: parts[5].parse().unwrap_or(0.0),
+                median: parts[8].parse().unwrap_or(0.0),
+                stddev: parts[9].parse().unwrap_or(0.0),
+                cv_percent: parts[10].parse().unwrap_or(0.0),
+                stable: parts[12] == "true",
+            });
+            summaries.push(ScoreSummary {
+                mode: ScoreMode::from_name(parts[1])?,
+                workload: ScoreWorkload::ALL.into_iter().find(|w| w.name() == parts[2]),
+                ops_per_sec: parts[4].parse().ok(),
+                score: parts[5].parse().ok()?,
+                ci_low: parts[6].parse().ok()?,
+                ci_high: parts[7].parse().ok()?,
+                stats,
+            });
+        }
+    }
+    Some(summaries)
+}
+
+fn parse_system_stress_filename(name: &str) -> Option<ParsedLogName> {
+    // Format: system_stress_<hash>_<YYYYMMDD_HHMMSS>_dur<duration>.csv
+    let base = name.strip_prefix("system_stress_")?.strip_suffix(".csv")?;
+    let parts: Vec<&str> = base.split('_').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let hash = parts[0];
+    let date_str = parts[1..3].join("_");
+    let dt = chrono::NaiveDateTime::parse_from_str(&date_str, "%Y%m%d_%H%M%S").ok()?;
+    let formatted = format!(
+        "{} {}, {}: {:02}:{:02}:{:02}",
+        dt.format("%B"),
+        dt.day(),
+        dt.year(),
+        dt.hour(),
+        dt.minute(),
+        dt.second()
+    );
+    let duration = parts[3..].iter().find_map(|p| p.strip_prefix("dur")).and_then(|d| d.parse::<u32>().ok());
+    Some((formatted, date_str, hash.to_string(), None, duration))
+}
+
+// (timeline samples, avg record, peak record); avg/peak are missing if the run was cut short
+pub type SystemStressLog = (Vec<SystemSample>, Option<SystemSample>, Option<SystemSample>);
+
+pub fn analyze_system_stress_csv(path: &str) -> Option<SystemStressLog> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut samples = vec![];
+    let mut avg = None;
+    let mut peak = None;
+    for line in reader.lines().skip(1).map_while(Result::ok) { // skip header
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() != 9 {
+            continue;
+        }
+        let sample = SystemSample {
+            elapsed_secs: parts[2].parse().unwrap_or(0.0),
+            cpu_iters_per_sec: parts[3].parse().unwrap_or(0.0),
+            ram_ops_per_sec: parts[4].parse().unwrap_or(0.0),
+            storage_write_mbps: parts[5].parse().unwrap_or(0.0),
+            storage_read_mbps: parts[6].parse().unwrap_or(0.0),
+            cpu_temp_c: parts[7].parse().ok(),
+            cpu_mhz: parts[8].parse().unwrap_or(0),
+        };
+        match parts[1] {
+            "avg" => avg = Some(sample),
+            "peak" => peak = Some(sample),
+            _ => samples.push(sample),
+        }
+    }
+    Some((samples, avg, peak))
+}
+
+// Older system logs recorded the CPU stress's running average, which smooths step drops away
+fn system_log_has_interval_rate(path: &str) -> bool {
+    File::open(path).ok()
+        .and_then(|f| BufReader::new(f).lines().next())
+        .and_then(Result::ok)
+        .is_some_and(|header| header.split(',').any(|c| c == "cpu_interval_iters_per_sec"))
+}
+
 // fn draw_speed_graph(ui: &mut egui::Ui, data: &[f64], label: &str, color: egui::Color32) {
 //     if data.is_empty() { return; }
 //     let points: PlotPoints = data.iter().enumerate().map(|(i, v)| [i as f64, *v]).collect();
@@ -259,8 +776,42 @@ impl Analyzer {
                 self.selected_log_index = None;
                 self.marked_for_delete = None;
             }
+            if ui.selectable_label(self.analyzer_tab == AnalyzerTab::CoreSweep, "Core Sweep").clicked() {
+                self.analyzer_tab = AnalyzerTab::CoreSweep;
+                self.selected_log_index = None;
+                self.marked_for_delete = None;
+            }
+            if ui.selectable_label(self.analyzer_tab == AnalyzerTab::NetworkStress, "Network Stress").clicked() {
+                self.analyzer_tab = AnalyzerTab::NetworkStress;
+                self.selected_log_index = None;
+                self.marked_for_delete = None;
+            }
+            if ui.selectable_label(self.analyzer_tab == AnalyzerTab::MetadataStress, "Metadata Stress").clicked() {
+                self.analyzer_tab = AnalyzerTab::MetadataStress;
+                self.selected_log_index = None;
+                self.marked_for_delete = None;
+            }
+            if ui.selectable_label(self.analyzer_tab == AnalyzerTab::SystemStress, "System Stress").clicked() {
+                self.analyzer_tab = AnalyzerTab::SystemStress;
+                self.selected_log_index = None;
+                self.marked_for_delete = None;
+            }
+            if ui.selectable_label(self.analyzer_tab == AnalyzerTab::Scores, "Scores").clicked() {
+                self.analyzer_tab = AnalyzerTab::Scores;
+                self.selected_log_index = None;
+                self.marked_for_delete = None;
+            }
+            if ui.selectable_label(self.analyzer_tab == AnalyzerTab::CrashedRuns, format!("Crashed Runs ({})", self.crashed_runs.len())).clicked() {
+                self.analyzer_tab = AnalyzerTab::CrashedRuns;
+                self.selected_log_index = None;
+                self.marked_for_delete = None;
+            }
         });
         ui.separator();
+        if self.analyzer_tab == AnalyzerTab::CrashedRuns {
+            self.ui_crashed_runs(ui);
+            return;
+        }
         // List available logs for the selected test type
         let log_dir = self.log_dir();
         let mut log_files = vec![];
@@ -316,6 +867,87 @@ impl Analyzer {
                     bdt.cmp(&adt)
                 });
             },
+            AnalyzerTab::CoreSweep => {
+                if let Ok(entries) = std::fs::read_dir(&log_dir) {
+                    for entry in entries.flatten() {
+                        let path = entry.path();
+                        if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                            && name.starts_with("core_sweep_") && name.ends_with(".csv") {
+                            log_files.push(name.to_string());
+                        }
+                    }
+                }
+                log_files.sort_by(|a, b| {
+                    let adt = parse_core_sweep_filename(a).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    let bdt = parse_core_sweep_filename(b).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    bdt.cmp(&adt)
+                });
+            },
+            AnalyzerTab::NetworkStress => {
+                if let Ok(entries) = std::fs::read_dir(&log_dir) {
+                    for entry in entries.flatten() {
+                        let path = entry.path();
+                        if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                            && name.starts_with("network_stress_") && name.ends_with(".csv") {
+                            log_files.push(name.to_string());
+                        }
+                    }
+                }
+                log_files.sort_by(|a, b| {
+                    let adt = parse_network_stress_filename(a).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    let bdt = parse_network_stress_filename(b).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    bdt.cmp(&adt)
+                });
+            },
+            AnalyzerTab::MetadataStress => {
+                if let Ok(entries) = std::fs::read_dir(&log_dir) {
+                    for entry in entries.flatten() {
+                        let path = entry.path();
+                        if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                            && name.starts_with("metadata_stress_") && name.ends_with(".csv") {
+                            log_files.push(name.to_string());
+                        }
+                    }
+                }
+                log_files.sort_by(|a, b| {
+                    let adt = parse_metadata_stress_filename(a).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    let bdt = parse_metadata_stress_filename(b).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    bdt.cmp(&adt)
+                });
+            },
+            AnalyzerTab::SystemStress => {
+                if let Ok(entries) = std::fs::read_dir(&log_dir) {
+                    for entry in entries.flatten() {
+                        let path = entry.path();
+                        if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                            && name.starts_with("system_stress_") && name.ends_with(".csv") {
+                            log_files.push(name.to_string());
+                        }
+                    }
+                }
+                log_files.sort_by(|a, b| {
+                    let adt = parse_system_stress_filename(a).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    let bdt = parse_system_stress_filename(b).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    bdt.cmp(&adt)
+                });
+            },
+            AnalyzerTab::Scores => {
+                if let Ok(entries) = std::fs::read_dir(&log_dir) {
+                    for entry in entries.flatten() {
+                        let path = entry.path();
+                        if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                            && name.starts_with("score_") && name.ends_with(".csv") {
+                            log_files.push(name.to_string());
+                        }
+                    }
+                }
+                log_files.sort_by(|a, b| {
+                    let adt = parse_score_filename(a).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    let bdt = parse_score_filename(b).and_then(|(_, date, _, _, _)| chrono::NaiveDateTime::parse_from_str(&date, "%Y%m%d_%H%M%S").ok());
+                    bdt.cmp(&adt)
+                });
+            },
+            AnalyzerTab::CrashedRuns => {}
         }
         ui.label("Select a test to analyze:");
         egui::Grid::new("log_table").striped(true).show(ui, |ui| {
@@ -337,6 +969,12 @@ impl Analyzer {
                     AnalyzerTab::StorageStress => parse_storage_stress_filename(log),
                     AnalyzerTab::CpuStress => parse_cpu_stress_filename(log),
                     AnalyzerTab::SelectableStress => parse_selectable_stress_filename(log),
+                    AnalyzerTab::CoreSweep => parse_core_sweep_filename(log),
+                    AnalyzerTab::NetworkStress => parse_network_stress_filename(log),
+                    AnalyzerTab::MetadataStress => parse_metadata_stress_filename(log),
+                    AnalyzerTab::SystemStress => parse_system_stress_filename(log),
+                    AnalyzerTab::Scores => parse_score_filename(log),
+                    AnalyzerTab::CrashedRuns => None,
                 };
                 if let Some((formatted, _date_str, typ, _, _)) = parsed {
                     let _selected = self.selected_log_index == Some(i);
@@ -389,6 +1027,9 @@ impl Analyzer {
                             self.marked_for_delete = Some((i, std::time::Instant::now()));
                         }
                         ui.label(format!("({})", typ));
+                        if let Some(crash) = self.crashed_runs.iter().find(|c| c.matches_log(log)) {
+                            ui.colored_label(egui::Color32::RED, format!("⚠ crashed at T+{:.0} s", crash.crashed_at_secs));
+                        }
                         ui.end_row();
                     }
                 }
@@ -403,7 +1044,44 @@ impl Analyzer {
         ui.separator();
         if let Some(idx) = self.selected_log_index {
             if let Some(log) = log_files.get(idx) {
+                if let Some(crash) = self.crashed_runs.iter().find(|c| c.matches_log(log)) {
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {}; {}. Results below stop there.", crash.label(), crash.readings()));
+                }
+                let stored_inventory = inventory::read_from_csv(&log_dir.join(log));
+                if !stored_inventory.is_empty() {
+                    egui::CollapsingHeader::new("Hardware Inventory").id_salt("log_inventory").show(ui, |ui| {
+                        inventory::ui_entries(ui, "log_inventory_table", &stored_inventory);
+                    });
+                }
+                if let Some(run_seed) = seed::from_entries(&stored_inventory) {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Seed: {}", run_seed));
+                        if run_seed == seed::current() {
+                            ui.label("(current seed, the next run replays this one's inputs)");
+                        } else if ui.button("Replay with this seed").on_hover_text("Use this seed for the next runs").clicked() {
+                            seed::set(run_seed);
+                        }
+                    });
+                }
                 match self.analyzer_tab {
+                    AnalyzerTab::StorageStress if log.contains("_sync") => {
+                        if let Some((formatted, _date_str, hash, _, dur)) = parse_storage_stress_filename(log) {
+                            ui.label(format!("Analyzing: {} ({})", formatted, hash));
+                            let append = log.strip_suffix(".csv").unwrap_or(log).split('_').find_map(|p| p.strip_prefix("sync"));
+                            ui.label(format!(
+                                "Durable Write Latency | Append Size: {} bytes | Duration: {} s",
+                                append.unwrap_or("Unknown"),
+                                dur.map(|d| d.to_string()).unwrap_or_else(|| "Unknown".to_string())
+                            ));
+                        }
+                        let path = format!("{}/{}", log_dir.to_string_lossy(), log);
+                        if let Some(results) = analyze_storage_sync_csv(&path) {
+                            ui.label("Storage Durability Analysis:");
+                            ui_sync_table(ui, "storage_sync_analysis_table", &results);
+                        } else {
+                            ui.label("Failed to analyze log file.");
+                        }
+                    },
                     AnalyzerTab::StorageStress => {
                         if let Some((formatted, _date_str, hash, buf, dur)) = parse_storage_stress_filename(log) {
                             ui.label(format!("Analyzing: {} ({})", formatted, hash));
@@ -445,6 +1123,15 @@ impl Analyzer {
                                 dur.map(|d| d.to_string()).unwrap_or_else(|| "Unknown".to_string())
                             ));
                         }
+                        for part in log.strip_suffix(".csv").unwrap_or(log).split('_') {
+                            if let Some(profile) = part.strip_prefix("prof") {
+                                ui.label(format!("Load Profile: {}", profile));
+                            } else if let Some(t) = part.strip_prefix("utilcore") {
+                                ui.label(format!("Utilization Target: {}% per core", t));
+                            } else if let Some(t) = part.strip_prefix("util") {
+                                ui.label(format!("Utilization Target: {}% total", t));
+                            }
+                        }
                         let path = format!("{}/{}", log_dir.to_string_lossy(), log);
                         if let Some((thread_rates, _all_rates, avg, max, min, stddev)) = analyze_cpu_stress_csv(&path) {
                             ui.label("CPU Stress Test Analysis:");
@@ -475,6 +1162,16 @@ impl Analyzer {
                                     ui.end_row();
                                 }
                             });
+                            ui.separator();
+                            ui.label("Throttling Analysis:");
+                            let timeline = analyze_cpu_timeline_csv(&path).unwrap_or_default();
+                            if throttle::load_shaped(&timeline) {
+                                ui.label("Skipped: this run used a load profile or utilization target, so its throughput drops on purpose.");
+                            } else if let Some(report) = throttle::analyze(&timeline) {
+                                throttle::ui_report(ui, "cpu_throttle_table", &report, &timeline);
+                            } else {
+                                ui.label("Run too short for throttling analysis.");
+                            }
                         } else {
                             ui.label("Failed to analyze log file.");
                         }
@@ -484,6 +1181,69 @@ impl Analyzer {
                             ui.label(format!("Workload: {}", workload));
                             ui.label(format!("Params: {}", params));
                             ui.label(format!("Total Operations: {}", total_ops));
+                            if let Some((unit, rates)) = analyze_selectable_matrix_csv(&format!("{}/{}", log_dir.to_string_lossy(), log)) {
+                                ui.label(format!("Total: {:.2} {}", rates.iter().map(|&(_, r)| r).sum::<f64>(), unit));
+                                egui::Grid::new("selectable_matrix_rate_table").striped(true).show(ui, |ui| {
+                                    ui.label("Thread"); ui.label(&unit); ui.end_row();
+                                    for (tid, rate) in &rates {
+                                        ui.label(format!("{}", tid));
+                                        ui.label(format!("{:.2}", rate));
+                                        ui.end_row();
+                                    }
+                                });
+                            }
+                            if let Some((accel, algorithms)) = analyze_selectable_crypto_csv(&format!("{}/{}", log_dir.to_string_lossy(), log)) {
+                                if !accel.is_empty() {
+                                    let present: Vec<&str> = accel.iter().filter(|(_, on)| *on).map(|(f, _)| f.as_str()).collect();
+                                    ui.label(format!("Hardware acceleration: {}", if present.is_empty() { "none".to_string() } else { present.join(", ") }));
+                                }
+                                egui::Grid::new("selectable_crypto_analysis_table").striped(true).show(ui, |ui| {
+                                    ui.label("Algorithm"); ui.label("Self-test"); ui.label("Hash/Encrypt"); ui.label("Decrypt"); ui.end_row();
+                                    for (label, passed, forward, decrypt, _) in &algorithms {
+                                        ui.label(label);
+                                        if *passed {
+                                            ui.colored_label(egui::Color32::GREEN, "✔ pass");
+                                        } else {
+                                            ui.colored_label(egui::Color32::RED, "✖ FAIL");
+                                        }
+                                        ui.label(format!("{:.2} MB/s", forward));
+                                        ui.label(decrypt.map(|d| format!("{:.2} MB/s", d)).unwrap_or_else(|| "-".to_string()));
+                                        ui.end_row();
+                                    }
+                                });
+                                let mismatches: u64 = algorithms.iter().map(|a| a.4).sum();
+                                if mismatches > 0 {
+                                    ui.colored_label(egui::Color32::RED, format!("⚠ {} decryption(s) failed or did not reproduce the plaintext", mismatches));
+                                }
+                            }
+                            if let Some(kernels) = analyze_selectable_micro_csv(&format!("{}/{}", log_dir.to_string_lossy(), log)) {
+                                egui::Grid::new("selectable_micro_analysis_table").striped(true).show(ui, |ui| {
+                                    ui.label("Kernel"); ui.label("Mops/s"); ui.label("Mismatches"); ui.end_row();
+                                    for (label, rate, mismatches) in &kernels {
+                                        ui.label(label);
+                                        ui.label(format!("{:.2}", rate / 1e6));
+                                        if *mismatches > 0 {
+                                            ui.colored_label(egui::Color32::RED, format!("✖ {}", mismatches));
+                                        } else {
+                                            ui.colored_label(egui::Color32::GREEN, "✔ 0");
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                                if kernels.iter().any(|k| k.2 > 0) {
+                                    ui.colored_label(egui::Color32::RED, "⚠ Repeated passes gave different results; the kernels with mismatches point at the unstable execution unit");
+                                }
+                            }
+                            if let Some((compress, decompress, ratio, mismatches)) = analyze_selectable_compression_csv(&format!("{}/{}", log_dir.to_string_lossy(), log)) {
+                                egui::Grid::new("selectable_compression_table").striped(true).show(ui, |ui| {
+                                    ui.label("Compress"); ui.label(format!("{:.2} MB/s", compress)); ui.end_row();
+                                    ui.label("Decompress"); ui.label(format!("{:.2} MB/s", decompress)); ui.end_row();
+                                    ui.label("Ratio"); ui.label(format!("{:.2}:1", ratio)); ui.end_row();
+                                });
+                                if mismatches > 0 {
+                                    ui.colored_label(egui::Color32::RED, format!("⚠ {} round trip(s) did not reproduce the input", mismatches));
+                                }
+                            }
                             egui::Grid::new("selectable_thread_table").striped(true).show(ui, |ui| {
                                 ui.label("Thread"); ui.label("Ops"); ui.end_row();
                                 for (tid, ops) in thread_ops {
@@ -492,12 +1252,241 @@ impl Analyzer {
                                     ui.end_row();
                                 }
                             });
+                            if let Some((warmup, repetitions, stats)) = analyze_selectable_bench_csv(&format!("{}/{}", log_dir.to_string_lossy(), log)) {
+                                ui.separator();
+                                ui.label(format!("Benchmark: {} s warm-up (not measured), {} repetitions", warmup, repetitions.len()));
+                                bench::ui_stats_table(ui, "selectable_bench_analysis_table", &stats, "ops/s");
+                                egui::Grid::new("selectable_bench_reps_table").striped(true).show(ui, |ui| {
+                                    ui.label("Repetition"); ui.label("Ops/s"); ui.label(""); ui.end_row();
+                                    for (i, (rate, outlier)) in repetitions.iter().enumerate() {
+                                        ui.label(format!("{}", i + 1));
+                                        ui.label(format!("{:.2}", rate));
+                                        if *outlier {
+                                            ui.colored_label(egui::Color32::YELLOW, "outlier, excluded");
+                                        } else {
+                                            ui.label("");
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            }
+                        } else {
+                            ui.label("Failed to analyze log file.");
+                        }
+                    },
+                    AnalyzerTab::CoreSweep => {
+                        if let Some((formatted, _date_str, hash, _, dur)) = parse_core_sweep_filename(log) {
+                            ui.label(format!("Analyzing: {} ({})", formatted, hash));
+                            ui.label(format!(
+                                "Seconds per Core: {}",
+                                dur.map(|d| d.to_string()).unwrap_or_else(|| "Unknown".to_string())
+                            ));
+                        }
+                        let path = format!("{}/{}", log_dir.to_string_lossy(), log);
+                        if let Some(ranked) = analyze_core_sweep_csv(&path) {
+                            let failed = ranked.iter().filter(|r| r.errors > 0).count();
+                            let skipped = ranked.iter().filter(|r| r.pin_failed).count();
+                            ui.label(format!("Per-Core Stability: {} of {} cores failed verification", failed, ranked.len() - skipped));
+                            if skipped > 0 {
+                                ui.colored_label(egui::Color32::YELLOW, format!("{} core(s) skipped: the worker could not be pinned to them", skipped));
+                            }
+                            if let Some(weakest) = ranked.first().filter(|r| !r.pin_failed) {
+                                ui.label(format!("Weakest core: CPU {} (core {})", weakest.cpu, weakest.core));
+                            }
+                            ui.separator();
+                            ui_ranked_table(ui, "core_sweep_analysis_table", &ranked);
+                        } else {
+                            ui.label("Failed to analyze log file.");
+                        }
+                    },
+                    AnalyzerTab::NetworkStress => {
+                        if let Some((formatted, _date_str, hash, streams, dur)) = parse_network_stress_filename(log) {
+                            ui.label(format!("Analyzing: {} ({})", formatted, hash));
+                            ui.label(format!(
+                                "Parallel Streams: {} | Duration: {} s",
+                                streams.map(|s| s.to_string()).unwrap_or_else(|| "Unknown".to_string()),
+                                dur.map(|d| d.to_string()).unwrap_or_else(|| "Unknown".to_string())
+                            ));
+                        }
+                        let path = format!("{}/{}", log_dir.to_string_lossy(), log);
+                        if let Some(phases) = analyze_network_stress_csv(&path) {
+                            ui.label("Network Stress Test Analysis:");
+                            let summaries: Vec<NetworkPhaseSummary> = phases.iter().map(|(s, _, _, _)| s.clone()).collect();
+                            ui_summary_table(ui, "network_analysis_table", &summaries);
+                            ui.separator();
+                            ui.label("Sample variation:");
+                            egui::Grid::new("network_sample_table").striped(true).show(ui, |ui| {
+                                ui.label("Phase"); ui.label("Min"); ui.label("Max"); ui.label("StdDev"); ui.end_row();
+                                for (s, min, max, stddev) in &phases {
+                                    ui.label(s.phase.label());
+                                    ui.label(format!("{:.1} {}", min, s.phase.unit()));
+                                    ui.label(format!("{:.1} {}", max, s.phase.unit()));
+                                    ui.label(format!("{:.1}", stddev));
+                                    ui.end_row();
+                                }
+                            });
+                        } else {
+                            ui.label("Failed to analyze log file.");
+                        }
+                    },
+                    AnalyzerTab::MetadataStress => {
+                        if let Some((formatted, _date_str, hash, threads, dur)) = parse_metadata_stress_filename(log) {
+                            ui.label(format!("Analyzing: {} ({})", formatted, hash));
+                            ui.label(format!(
+                                "Threads: {} | Duration: {} s",
+                                threads.map(|t| t.to_string()).unwrap_or_else(|| "Unknown".to_string()),
+                                dur.map(|d| d.to_string()).unwrap_or_else(|| "Unknown".to_string())
+                            ));
+                        }
+                        let path = format!("{}/{}", log_dir.to_string_lossy(), log);
+                        if let Some(ops) = analyze_metadata_stress_csv(&path) {
+                            ui.label("Metadata Stress Test Analysis:");
+                            let summaries: Vec<MetadataOpSummary> = ops.iter().map(|(s, _, _)| s.clone()).collect();
+                            crate::app::metadata_stress::ui_summary_table(ui, "metadata_analysis_table", &summaries);
+                            ui.separator();
+                            ui.label("Sample variation:");
+                            egui::Grid::new("metadata_sample_table").striped(true).show(ui, |ui| {
+                                ui.label("Operation"); ui.label("Min Ops/s"); ui.label("Max Ops/s"); ui.end_row();
+                                for (s, min, max) in &ops {
+                                    ui.label(s.op.name());
+                                    ui.label(format!("{:.0}", min));
+                                    ui.label(format!("{:.0}", max));
+                                    ui.end_row();
+                                }
+                            });
+                        } else {
+                            ui.label("Failed to analyze log file.");
+                        }
+                    },
+                    AnalyzerTab::SystemStress => {
+                        if let Some((formatted, _date_str, hash, _, dur)) = parse_system_stress_filename(log) {
+                            ui.label(format!("Analyzing: {} ({})", formatted, hash));
+                            ui.label(format!(
+                                "Duration: {} s",
+                                dur.map(|d| d.to_string()).unwrap_or_else(|| "Unknown".to_string())
+                            ));
+                        }
+                        let path = format!("{}/{}", log_dir.to_string_lossy(), log);
+                        if let Some((samples, avg, peak)) = analyze_system_stress_csv(&path) {
+                            ui.label("System Stress Analysis:");
+                            // Runs stopped early have no summary rows, so fall back to recomputing from the samples
+                            let summary = match (avg, peak) {
+                                (Some(avg), Some(peak)) => Some((avg, peak)),
+                                _ => crate::app::system_stress::summarize(&samples),
+                            };
+                            if let Some((avg, peak)) = summary {
+                                crate::app::system_stress::ui_summary_table(ui, "system_stress_analysis_table", &avg, &peak);
+                            }
+                            ui.separator();
+                            crate::app::system_stress::ui_timeline(ui, &samples);
+                            ui.separator();
+                            ui.label("CPU Throttling Analysis:");
+                            let timeline: Vec<TimelineSample> = samples.iter().map(|s| TimelineSample {
+                                elapsed_secs: s.elapsed_secs,
+                                rate: s.cpu_iters_per_sec,
+                                cpu_mhz: Some(s.cpu_mhz),
+                                cpu_temp_c: s.cpu_temp_c,
+                                // The preset always runs the CPU at full load
+                                target_load: None,
+                            }).collect();
+                            if !system_log_has_interval_rate(&path) {
+                                ui.label("Skipped: this log predates per-interval CPU rates, so drops can't be told apart.");
+                            } else if let Some(report) = throttle::analyze(&timeline) {
+                                throttle::ui_report(ui, "system_throttle_table", &report, &timeline);
+                            } else {
+                                ui.label("Run too short for throttling analysis.");
+                            }
                         } else {
                             ui.label("Failed to analyze log file.");
                         }
                     },
+                    AnalyzerTab::Scores => {
+                        let mut version = None;
+                        if let Some((formatted, _date_str, hash, ver, runs)) = parse_score_filename(log) {
+                            ui.label(format!("Analyzing: {} ({})", formatted, hash));
+                            ui.label(format!(
+                                "Score Version: {} | Repetitions: {}",
+                                ver.map(|v| format!("v{}", v)).unwrap_or_else(|| "Unknown".to_string()),
+                                runs.map(|r| r.to_string()).unwrap_or_else(|| "Unknown".to_string())
+                            ));
+                            version = ver;
+                        }
+                        if version != Some(SCORE_VERSION) {
+                            ui.colored_label(egui::Color32::YELLOW, format!("⚠ Scored with a different version than this build (v{}); not comparable with current scores.", SCORE_VERSION));
+                        }
+                        let path = format!("{}/{}", log_dir.to_string_lossy(), log);
+                        if let Some(summaries) = analyze_score_csv(&path) {
+                            ui.label(format!("Benchmark Scores ({:.0} = reference machine):", scoring::REFERENCE_SCORE));
+                            scoring::ui_summary_table(ui, "score_analysis_table", &summaries);
+                        } else {
+                            ui.label("Failed to analyze log file.");
+                        }
+                    },
+                    AnalyzerTab::CrashedRuns => {}
                 }
             }
         }
     }
+
+    fn ui_crashed_runs(&mut self, ui: &mut egui::Ui) {
+        ui.label("Runs that started but never wrote a clean end marker, usually because the machine crashed, hung or lost power.");
+        if self.crashed_runs.is_empty() {
+            ui.label("No crashed runs found.");
+            return;
+        }
+        let mut forget: Option<usize> = None;
+        egui::Grid::new("crashed_runs_table").striped(true).show(ui, |ui| {
+            ui.heading("Started");
+            ui.heading("Test");
+            ui.heading("Last seen");
+            ui.heading("CPU Temp");
+            ui.heading("CPU MHz");
+            ui.heading("CPU Load");
+            ui.heading("Log");
+            ui.end_row();
+            for (i, run) in self.crashed_runs.iter().enumerate() {
+                ui.label(journal::format_time(&run.started));
+                ui.label(&run.kind);
+                ui.colored_label(egui::Color32::RED, format!("T+{:.0} s", run.crashed_at_secs));
+                ui.label(run.cpu_temp_c.map(|t| format!("{:.1} °C", t)).unwrap_or_else(|| "N/A".to_string()));
+                ui.label(run.cpu_mhz.map(|m| m.to_string()).unwrap_or_else(|| "N/A".to_string()));
+                ui.label(run.cpu_usage_percent.map(|u| format!("{:.0}%", u)).unwrap_or_else(|| "N/A".to_string()));
+                ui.label(run.csv.as_ref().and_then(|p| p.file_name()).map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "none".to_string()));
+                // Only removes the journal; the test's own log stays in the log folder
+                if ui.small_button("Forget").clicked() {
+                    forget = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = forget {
+            let run = self.crashed_runs.remove(i);
+            if self.dev_mode {
+                println!("[DEV] Removing journal {}", run.journal.display());
+            }
+            let _ = std::fs::remove_file(&run.journal);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_timeline_sums_threads_per_tick_and_keeps_the_target_load() {
+        let path = std::env::temp_dir().join(format!("stressor_cpu_timeline_test_{}.csv", std::process::id()));
+        std::fs::write(&path, "timestamp,thread,iterations_per_sec,target_load,cpu_mhz,cpu_temp_c\n\
+            2026-01-01T00:00:00+00:00,0,100,100,4000,60\n\
+            2026-01-01T00:00:00+00:00,1,150,100,4000,60\n\
+            2026-01-01T00:00:01+00:00,0,0,0,3900,\n\
+            2026-01-01T00:00:01+00:00,1,0,0,3900,\n").unwrap();
+        let timeline = analyze_cpu_timeline_csv(&path.to_string_lossy()).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(timeline.len(), 2);
+        assert_eq!((timeline[0].rate, timeline[0].target_load, timeline[0].cpu_temp_c), (250.0, Some(100.0), Some(60.0)));
+        assert_eq!((timeline[1].elapsed_secs, timeline[1].rate, timeline[1].target_load, timeline[1].cpu_mhz), (1.0, 0.0, Some(0.0), Some(3900)));
+        // The duty cycle's off phase marks the whole run as shaped, so it isn't judged as throttled
+        assert!(throttle::load_shaped(&timeline));
+    }
 }
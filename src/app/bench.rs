@@ -0,0 +1,166 @@
+use eframe::egui;
+use crate::app::config::Config;
+
+// Tukey fences: repetitions further than this many IQRs outside the middle half are dropped
+const OUTLIER_IQR_FACTOR: f64 = 1.5;
+// Below this many repetitions quartiles are meaningless, so nothing is rejected
+const MIN_SAMPLES_FOR_REJECTION: usize = 4;
+// A CV from fewer kept repetitions than this isn't trusted to call a result stable
+const MIN_SAMPLES_FOR_STABLE: usize = 3;
+
+// How a workload is repeated when run as a benchmark instead of a single timed run
+#[derive(Clone, Debug)]
+pub struct BenchSettings {
+    pub warmup_secs: u32,
+    pub repetitions: u32,
+    pub stable_cv_percent: f64,
+}
+
+impl BenchSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            warmup_secs: config.bench_warmup_secs,
+            repetitions: config.bench_repetitions.max(1),
+            stable_cv_percent: config.bench_stable_cv_percent,
+        }
+    }
+
+    // Warm-up and repetition counts next to each other, shared by every benchmark's settings row
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Warm-up (s):");
+            ui.add(egui::DragValue::new(&mut self.warmup_secs).range(0..=120));
+            ui.label("Repetitions:");
+            ui.add(egui::DragValue::new(&mut self.repetitions).range(1..=50));
+            ui.label("Stable below CV (%):");
+            ui.add(egui::DragValue::new(&mut self.stable_cv_percent).range(0.1..=50.0).speed(0.1));
+        });
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchStats {
+    pub samples: usize,
+    pub rejected: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub cv_percent: f64,
+    pub stable: bool,
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let pos = p * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+// Flags each sample outside the Tukey fences; the flags follow the input order
+pub fn outliers(samples: &[f64]) -> Vec<bool> {
+    if samples.len() < MIN_SAMPLES_FOR_REJECTION {
+        return vec![false; samples.len()];
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let (q1, q3) = (percentile(&sorted, 0.25), percentile(&sorted, 0.75));
+    let iqr = q3 - q1;
+    let (low, high) = (q1 - OUTLIER_IQR_FACTOR * iqr, q3 + OUTLIER_IQR_FACTOR * iqr);
+    samples.iter().map(|&v| v < low || v > high).collect()
+}
+
+pub fn reject_outliers(samples: &[f64]) -> Vec<f64> {
+    samples.iter().zip(outliers(samples)).filter(|&(_, out)| !out).map(|(&v, _)| v).collect()
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &[f64], stable_cv_percent: f64) -> Option<Self> {
+        let mut kept = reject_outliers(samples);
+        if kept.is_empty() {
+            return None;
+        }
+        kept.sort_by(f64::total_cmp);
+        let n = kept.len();
+        let mean = kept.iter().sum::<f64>() / n as f64;
+        // Sample stddev, since the repetitions stand in for every run this machine could do
+        let stddev = if n > 1 {
+            (kept.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let cv_percent = if mean.abs() > 0.0 { stddev / mean.abs() * 100.0 } else { 0.0 };
+        Some(Self {
+            samples: samples.len(),
+            rejected: samples.len() - n,
+            mean,
+            median: percentile(&kept, 0.5),
+            stddev,
+            cv_percent,
+            stable: n >= MIN_SAMPLES_FOR_STABLE && cv_percent <= stable_cv_percent,
+        })
+    }
+
+    pub fn stability_label(&self) -> &'static str {
+        if self.stable { "✔ stable" } else { "✖ unstable" }
+    }
+}
+
+pub fn ui_stable_label(ui: &mut egui::Ui, stats: &BenchStats) {
+    let color = if stats.stable { egui::Color32::GREEN } else { egui::Color32::YELLOW };
+    ui.colored_label(color, stats.stability_label());
+}
+
+pub fn ui_stats_table(ui: &mut egui::Ui, id: &str, stats: &BenchStats, unit: &str) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Mean"); ui.label(format!("{:.2} {}", stats.mean, unit)); ui.end_row();
+        ui.label("Median"); ui.label(format!("{:.2} {}", stats.median, unit)); ui.end_row();
+        ui.label("StdDev"); ui.label(format!("{:.2} {}", stats.stddev, unit)); ui.end_row();
+        ui.label("CV"); ui.label(format!("{:.2}%", stats.cv_percent)); ui.end_row();
+        ui.label("Repetitions"); ui.label(format!("{} ({} rejected as outliers)", stats.samples, stats.rejected)); ui.end_row();
+        ui.label("Result"); ui_stable_label(ui, stats); ui.end_row();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn outliers_flags_samples_outside_the_fences_in_input_order() {
+        assert_eq!(outliers(&[12.0, 100.0, 10.0, 13.0, 11.0]), vec![false, true, false, false, false]);
+        assert_eq!(outliers(&[0.1, 10.0, 11.0, 12.0, 13.0]), vec![true, false, false, false, false]);
+        assert_eq!(outliers(&[5.0, 5.0, 5.0, 5.0]), vec![false; 4]);
+        assert_eq!(reject_outliers(&[12.0, 100.0, 10.0, 13.0, 11.0]), vec![12.0, 10.0, 13.0, 11.0]);
+    }
+
+    #[test]
+    fn outliers_keeps_everything_below_the_minimum_sample_count() {
+        assert_eq!(outliers(&[1.0, 2.0, 1000.0]), vec![false; 3]);
+        assert!(outliers(&[]).is_empty());
+    }
+
+    #[test]
+    fn from_samples_summarizes_the_kept_repetitions() {
+        let stats = BenchStats::from_samples(&[12.0, 100.0, 10.0, 13.0, 11.0], 20.0).unwrap();
+        assert_eq!((stats.samples, stats.rejected), (5, 1));
+        assert!(close(stats.mean, 11.5));
+        assert!(close(stats.median, 11.5));
+        assert!(close(stats.stddev, (5.0f64 / 3.0).sqrt()));
+        assert!(close(stats.cv_percent, (5.0f64 / 3.0).sqrt() / 11.5 * 100.0));
+        assert!(stats.stable);
+        assert!(!BenchStats::from_samples(&[12.0, 100.0, 10.0, 13.0, 11.0], 10.0).unwrap().stable);
+    }
+
+    #[test]
+    fn from_samples_needs_enough_repetitions_to_call_a_result_stable() {
+        let single = BenchStats::from_samples(&[42.0], 5.0).unwrap();
+        assert_eq!(single.stddev, 0.0);
+        assert_eq!(single.median, 42.0);
+        assert!(!single.stable);
+        assert!(BenchStats::from_samples(&[42.0, 42.0, 42.0], 5.0).unwrap().stable);
+        assert!(BenchStats::from_samples(&[], 5.0).is_none());
+    }
+}
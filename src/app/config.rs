@@ -13,16 +13,59 @@ pub struct Config {
     pub matrix_size: usize,
     pub matrix_duration_secs: u32,
     pub matrix_threads: usize,
+    pub matrix_kernel: String,
+    pub matrix_element: String,
     pub ram_buffer_size: usize,
     pub ram_duration_secs: u32,
     pub ram_threads: usize,
     pub compression_block_size: usize,
     pub compression_duration_secs: u32,
     pub compression_threads: usize,
+    pub compression_codec: String,
+    pub compression_level: u32,
+    pub compression_data: String,
+    pub crypto_buffer_size: usize,
+    pub crypto_duration_secs: u32,
+    pub crypto_threads: usize,
+    pub crypto_algorithm: String,
+    pub micro_duration_secs: u32,
+    pub micro_threads: usize,
+    pub micro_kernel: String,
+    pub micro_chase_bytes: usize,
     pub tightloop_duration_secs: u32,
     pub tightloop_threads: usize,
     pub storage_duration_secs: u32,
     pub storage_buffer_mb: u32,
+    pub storage_sync_write_bytes: u32,
+    pub core_sweep_secs_per_core: u32,
+    pub process_competition_threshold: f64,
+    pub network_host: String,
+    pub network_port: u16,
+    pub network_duration_secs: u32,
+    pub network_streams: u32,
+    pub network_packet_bytes: u32,
+    pub network_peer_bind: String,
+    pub network_peer_max_connections: usize,
+    pub metadata_dir: String,
+    pub metadata_duration_secs: u32,
+    pub metadata_threads: usize,
+    pub metadata_files_per_dir: u32,
+    pub system_stress_duration_secs: u32,
+    pub telemetry: String,
+    pub metrics_port: u16,
+    pub control_bind: String,
+    pub control_port: u16,
+    pub control_token: String,
+    pub watchdog_ui_timeout_secs: u32,
+    pub watchdog_engine_timeout_secs: u32,
+    pub watchdog_grace_secs: u32,
+    pub watchdog_max_cpu_temp_c: f64,
+    pub score_runs: u32,
+    pub score_run_secs: u32,
+    pub bench_warmup_secs: u32,
+    pub bench_repetitions: u32,
+    pub bench_stable_cv_percent: f64,
+    pub seed: u64,
 }
 
 impl Config {
@@ -59,6 +102,9 @@ impl Config {
                 .unwrap_or_else(|_| panic!("Missing or invalid int for key: {}", key))
                 .unwrap_or_else(|| panic!("Missing int value for key: {}", key)) as u32
         };
+        let get_string = |ini: &Ini, key: &str| {
+            ini.get("default", &key.to_lowercase()).unwrap_or_else(|| panic!("Missing value for key: {}", key))
+        };
         let get_threads = |ini: &Ini, key: &str| {
             let v = ini.get("default", &key.to_lowercase()).unwrap_or_else(|| panic!("Missing value for key: {}", key));
             if v == "auto" { num_cpus::get() } else { v.parse::<usize>().unwrap_or_else(|_| panic!("Invalid thread count for key: {}", key)) }
@@ -73,16 +119,59 @@ impl Config {
             matrix_size: get_usize(&stressors, "MATRIX_SIZE"),
             matrix_duration_secs: get_u32(&stressors, "MATRIX_DURATION_SECS"),
             matrix_threads: get_threads(&stressors, "MATRIX_THREADS"),
+            matrix_kernel: get_string(&stressors, "MATRIX_KERNEL"),
+            matrix_element: get_string(&stressors, "MATRIX_ELEMENT"),
             ram_buffer_size: get_usize(&stressors, "RAM_BUFFER_SIZE"),
             ram_duration_secs: get_u32(&stressors, "RAM_DURATION_SECS"),
             ram_threads: get_threads(&stressors, "RAM_THREADS"),
             compression_block_size: get_usize(&stressors, "COMPRESSION_BLOCK_SIZE"),
             compression_duration_secs: get_u32(&stressors, "COMPRESSION_DURATION_SECS"),
             compression_threads: get_threads(&stressors, "COMPRESSION_THREADS"),
+            compression_codec: get_string(&stressors, "COMPRESSION_CODEC"),
+            compression_level: get_u32(&stressors, "COMPRESSION_LEVEL"),
+            compression_data: get_string(&stressors, "COMPRESSION_DATA"),
+            crypto_buffer_size: get_usize(&stressors, "CRYPTO_BUFFER_SIZE"),
+            crypto_duration_secs: get_u32(&stressors, "CRYPTO_DURATION_SECS"),
+            crypto_threads: get_threads(&stressors, "CRYPTO_THREADS"),
+            crypto_algorithm: get_string(&stressors, "CRYPTO_ALGORITHM"),
+            micro_duration_secs: get_u32(&stressors, "MICRO_DURATION_SECS"),
+            micro_threads: get_threads(&stressors, "MICRO_THREADS"),
+            micro_kernel: get_string(&stressors, "MICRO_KERNEL"),
+            micro_chase_bytes: get_usize(&stressors, "MICRO_CHASE_BYTES"),
             tightloop_duration_secs: get_u32(&stressors, "TIGHTLOOP_DURATION_SECS"),
             tightloop_threads: get_threads(&stressors, "TIGHTLOOP_THREADS"),
             storage_duration_secs: get_u32(&stressors, "STORAGE_DURATION_SECS"),
             storage_buffer_mb: get_u32(&stressors, "STORAGE_BUFFER_MB"),
+            storage_sync_write_bytes: get_u32(&stressors, "STORAGE_SYNC_WRITE_BYTES"),
+            core_sweep_secs_per_core: get_u32(&stressors, "CORE_SWEEP_SECS_PER_CORE"),
+            process_competition_threshold: get_f64(&stressors, "PROCESS_COMPETITION_THRESHOLD"),
+            network_host: get_string(&stressors, "NETWORK_HOST"),
+            network_port: get_u32(&stressors, "NETWORK_PORT") as u16,
+            network_duration_secs: get_u32(&stressors, "NETWORK_DURATION_SECS"),
+            network_streams: get_u32(&stressors, "NETWORK_STREAMS"),
+            network_packet_bytes: get_u32(&stressors, "NETWORK_PACKET_BYTES"),
+            network_peer_bind: get_string(&stressors, "NETWORK_PEER_BIND"),
+            network_peer_max_connections: get_usize(&stressors, "NETWORK_PEER_MAX_CONNECTIONS"),
+            metadata_dir: get_string(&stressors, "METADATA_DIR"),
+            metadata_duration_secs: get_u32(&stressors, "METADATA_DURATION_SECS"),
+            metadata_threads: get_threads(&stressors, "METADATA_THREADS"),
+            metadata_files_per_dir: get_u32(&stressors, "METADATA_FILES_PER_DIR"),
+            system_stress_duration_secs: get_u32(&stressors, "SYSTEM_STRESS_DURATION_SECS"),
+            telemetry: get_string(&stressors, "TELEMETRY"),
+            metrics_port: get_u32(&stressors, "METRICS_PORT") as u16,
+            control_bind: get_string(&stressors, "CONTROL_BIND"),
+            control_port: get_u32(&stressors, "CONTROL_PORT") as u16,
+            control_token: get_string(&stressors, "CONTROL_TOKEN"),
+            watchdog_ui_timeout_secs: get_u32(&stressors, "WATCHDOG_UI_TIMEOUT_SECS"),
+            watchdog_engine_timeout_secs: get_u32(&stressors, "WATCHDOG_ENGINE_TIMEOUT_SECS"),
+            watchdog_grace_secs: get_u32(&stressors, "WATCHDOG_GRACE_SECS"),
+            watchdog_max_cpu_temp_c: get_f64(&stressors, "WATCHDOG_MAX_CPU_TEMP_C"),
+            score_runs: get_u32(&stressors, "SCORE_RUNS"),
+            score_run_secs: get_u32(&stressors, "SCORE_RUN_SECS"),
+            bench_warmup_secs: get_u32(&stressors, "BENCH_WARMUP_SECS"),
+            bench_repetitions: get_u32(&stressors, "BENCH_REPETITIONS"),
+            bench_stable_cv_percent: get_f64(&stressors, "BENCH_STABLE_CV_PERCENT"),
+            seed: get_usize(&stressors, "SEED") as u64,
         }
     }
 } 
\ No newline at end of file
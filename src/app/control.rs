@@ -0,0 +1,373 @@
+use eframe::egui;
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use once_cell::sync::{Lazy, OnceCell};
+use crate::app::scheduler::ScheduleTarget;
+use crate::app::stress_test::StressTest;
+use crate::app::seed;
+
+// How long a request waits for the GUI/headless loop to pick it up before giving up
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_DURATION_SECS: u32 = 7 * 24 * 3600;
+
+enum ControlRequest {
+    Start { target: ScheduleTarget, duration_secs: Option<u32>, seed: Option<u64> },
+    Stop,
+    Status,
+    Result(ScheduleTarget),
+}
+
+enum ControlReply {
+    Json(&'static str, String),
+    File(PathBuf),
+}
+
+struct Pending {
+    request: ControlRequest,
+    reply: mpsc::Sender<ControlReply>,
+}
+
+// Requests are applied on the thread that owns the StressTest, see process_pending()
+static QUEUE: OnceCell<Mutex<mpsc::Receiver<Pending>>> = OnceCell::new();
+static SENDER: OnceCell<Mutex<mpsc::Sender<Pending>>> = OnceCell::new();
+static REPAINT: OnceCell<egui::Context> = OnceCell::new();
+// Start time of runs launched through the API, for progress reporting
+static API_RUNS: Lazy<Mutex<HashMap<&'static str, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn is_enabled() -> bool {
+    QUEUE.get().is_some()
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn error_json(message: &str) -> String {
+    format!("{{\"error\":{}}}", json_string(message))
+}
+
+// Compares without stopping at the first mismatch so response timing doesn't leak the token
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn stressors_json() -> String {
+    let items: Vec<String> = ScheduleTarget::ALL
+        .iter()
+        .map(|t| format!("{{\"name\":{},\"label\":{},\"params\":[\"duration_secs\",\"seed\"]}}", json_string(t.name()), json_string(t.label())))
+        .collect();
+    format!("{{\"stressors\":[{}]}}", items.join(","))
+}
+
+fn status_json(stress_test: &StressTest) -> String {
+    let api_runs = API_RUNS.lock().unwrap();
+    let tests: Vec<String> = ScheduleTarget::ALL
+        .iter()
+        .map(|&t| {
+            let running = stress_test.target_running(t);
+            let duration = stress_test.target_duration_secs(t);
+            // Only known for runs started here; GUI and scheduled runs report null
+            let elapsed = api_runs.get(t.name()).filter(|_| running).map(|s| s.elapsed().as_secs_f64());
+            let progress = elapsed.map(|e| (e / duration.max(1) as f64).min(1.0));
+            let fmt = |v: Option<f64>| v.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "null".to_string());
+            let log = stress_test.target_log_path(t).map(|p| json_string(&p.to_string_lossy())).unwrap_or_else(|| "null".to_string());
+            format!(
+                "{{\"name\":{},\"running\":{},\"duration_secs\":{},\"elapsed_secs\":{},\"progress\":{},\"log\":{}}}",
+                json_string(t.name()),
+                running,
+                duration,
+                fmt(elapsed),
+                fmt(progress),
+                log
+            )
+        })
+        .collect();
+    format!(
+        "{{\"running\":{},\"tests\":[{}],\"scheduled_runs\":{}}}",
+        stress_test.is_running(),
+        tests.join(","),
+        stress_test.scheduler.len()
+    )
+}
+
+fn apply(request: ControlRequest, stress_test: &mut StressTest, ctx: &egui::Context, dev_mode: bool) -> ControlReply {
+    match request {
+        ControlRequest::Start { target, duration_secs, seed: run_seed } => {
+            if stress_test.is_running() {
+                return ControlReply::Json("409 Conflict", error_json("a test is already running"));
+            }
+            if let Some(secs) = duration_secs {
+                stress_test.set_target_duration_secs(target, secs);
+            }
+            if let Some(run_seed) = run_seed {
+                seed::set(run_seed);
+            }
+            if dev_mode {
+                println!("[DEV] Control API starting {}", target.name());
+            }
+            stress_test.start_target(target, ctx, dev_mode);
+            API_RUNS.lock().unwrap().insert(target.name(), Instant::now());
+            ControlReply::Json("202 Accepted", format!("{{\"started\":{},\"duration_secs\":{},\"seed\":{}}}", json_string(target.name()), stress_test.target_duration_secs(target), seed::current()))
+        }
+        ControlRequest::Stop => {
+            let was_running = stress_test.is_running();
+            stress_test.stop_all();
+            ControlReply::Json("200 OK", format!("{{\"stopped\":{}}}", was_running))
+        }
+        ControlRequest::Status => ControlReply::Json("200 OK", status_json(stress_test)),
+        ControlRequest::Result(target) => match stress_test.target_log_path(target) {
+            Some(path) if !stress_test.target_running(target) => ControlReply::File(path),
+            Some(_) => ControlReply::Json("409 Conflict", error_json("test is still running")),
+            None => ControlReply::Json("404 Not Found", error_json("no result for this test yet")),
+        },
+    }
+}
+
+// Drains queued API requests; call regularly from the thread that owns the StressTest. A non-zero
+// `wait` blocks up to that long for the first request, which lets the headless loop react at once.
+pub fn process_pending(stress_test: &mut StressTest, ctx: &egui::Context, dev_mode: bool, wait: Duration) {
+    let Some(queue) = QUEUE.get() else {
+        thread::sleep(wait);
+        return;
+    };
+    REPAINT.get_or_init(|| ctx.clone());
+    let queue = queue.lock().unwrap();
+    let mut next = queue.recv_timeout(wait).ok();
+    while let Some(pending) = next {
+        let reply = apply(pending.request, stress_test, ctx, dev_mode);
+        pending.reply.send(reply).ok();
+        next = queue.try_recv().ok();
+    }
+}
+
+fn dispatch(request: ControlRequest) -> ControlReply {
+    let (tx, rx) = mpsc::channel();
+    SENDER.get().unwrap().lock().unwrap().send(Pending { request, reply: tx }).ok();
+    // Wake the GUI so the request doesn't wait for the next scheduled repaint
+    if let Some(ctx) = REPAINT.get() {
+        ctx.request_repaint();
+    }
+    rx.recv_timeout(REPLY_TIMEOUT)
+        .unwrap_or_else(|_| ControlReply::Json("503 Service Unavailable", error_json("stressor did not respond in time")))
+}
+
+fn route(method: &str, path: &str, query: &str) -> ControlReply {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let target = |name: &str| ScheduleTarget::from_name(name).ok_or_else(|| ControlReply::Json("404 Not Found", error_json(&format!("unknown stressor '{}'", name))));
+    match (method, segments.as_slice()) {
+        ("GET", ["stressors"]) => ControlReply::Json("200 OK", stressors_json()),
+        ("GET", ["status"]) => dispatch(ControlRequest::Status),
+        ("POST", ["stop"]) => dispatch(ControlRequest::Stop),
+        ("POST", ["stressors", name, "start"]) => {
+            let target = match target(name) {
+                Ok(t) => t,
+                Err(reply) => return reply,
+            };
+            let mut duration_secs = None;
+            let mut run_seed = None;
+            for (key, value) in query.split('&').filter_map(|kv| kv.split_once('=')) {
+                match (key, value.parse::<u32>()) {
+                    ("duration_secs", Ok(secs)) if (1..=MAX_DURATION_SECS).contains(&secs) => duration_secs = Some(secs),
+                    ("duration_secs", _) => return ControlReply::Json("400 Bad Request", error_json(&format!("duration_secs must be 1-{}", MAX_DURATION_SECS))),
+                    ("seed", _) => match value.parse::<u64>() {
+                        Ok(s) if s != 0 => run_seed = Some(s),
+                        _ => return ControlReply::Json("400 Bad Request", error_json("seed must be a positive integer")),
+                    },
+                    _ => return ControlReply::Json("400 Bad Request", error_json(&format!("unknown parameter '{}'", key))),
+                }
+            }
+            dispatch(ControlRequest::Start { target, duration_secs, seed: run_seed })
+        }
+        ("GET", ["results", name]) => match target(name) {
+            Ok(t) => dispatch(ControlRequest::Result(t)),
+            Err(reply) => reply,
+        },
+        _ => ControlReply::Json("404 Not Found", error_json("unknown endpoint")),
+    }
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, extra_headers: &str, body: &[u8]) {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len(),
+        extra_headers
+    );
+    stream.write_all(head.as_bytes()).and_then(|_| stream.write_all(body)).ok();
+}
+
+fn handle_client(mut stream: TcpStream, token: &str) {
+    stream.set_read_timeout(Some(Duration::from_secs(2))).ok();
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut lines = request.lines();
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let (method, target) = (request_line.next().unwrap_or(""), request_line.next().unwrap_or(""));
+    let authorized = lines
+        .filter_map(|l| l.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .and_then(|(_, value)| value.trim().strip_prefix("Bearer "))
+        .is_some_and(|given| token_matches(given.trim(), token));
+    if !authorized {
+        respond(&mut stream, "401 Unauthorized", "application/json", "WWW-Authenticate: Bearer\r\n", error_json("missing or invalid token").as_bytes());
+        return;
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    match route(method, path, query) {
+        ControlReply::Json(status, body) => respond(&mut stream, status, "application/json", "", body.as_bytes()),
+        ControlReply::File(path) => match std::fs::read(&path) {
+            Ok(data) => {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let disposition = format!("Content-Disposition: attachment; filename=\"{}\"\r\n", name);
+                respond(&mut stream, "200 OK", "text/csv", &disposition, &data);
+            }
+            Err(e) => respond(&mut stream, "500 Internal Server Error", "application/json", "", error_json(&format!("failed to read {}: {}", path.display(), e)).as_bytes()),
+        },
+    }
+}
+
+// Listens on <bind>:<port>. Port 0 leaves the API off; a token is required otherwise.
+pub fn start_server(bind: &str, port: u16, token: &str) -> Result<(), String> {
+    if port == 0 || is_enabled() {
+        return Ok(());
+    }
+    if token.trim().is_empty() {
+        return Err("CONTROL_TOKEN must be set to enable the control API".to_string());
+    }
+    let listener = TcpListener::bind((bind, port)).map_err(|e| format!("Failed to start control API on {}:{}: {}", bind, port, e))?;
+    let (tx, rx) = mpsc::channel();
+    SENDER.set(Mutex::new(tx)).ok();
+    QUEUE.set(Mutex::new(rx)).ok();
+    let token = token.trim().to_string();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let token = token.clone();
+            thread::spawn(move || handle_client(stream, &token));
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Once;
+
+    const TOKEN: &str = "s3cret";
+
+    // Stands in for the GUI/headless loop: answers queued requests without a StressTest
+    fn fake_loop(result_file: PathBuf) {
+        static STARTED: Once = Once::new();
+        STARTED.call_once(|| {
+            let (tx, rx) = mpsc::channel::<Pending>();
+            SENDER.set(Mutex::new(tx)).ok();
+            thread::spawn(move || {
+                for pending in rx {
+                    let reply = match pending.request {
+                        ControlRequest::Stop => ControlReply::Json("200 OK", "{\"stopped\":false}".to_string()),
+                        ControlRequest::Result(_) => ControlReply::File(result_file.clone()),
+                        ControlRequest::Start { target, duration_secs, seed } => ControlReply::Json("202 Accepted", format!("{{\"started\":{},\"duration_secs\":{:?},\"seed\":{:?}}}", json_string(target.name()), duration_secs, seed)),
+                        ControlRequest::Status => ControlReply::Json("200 OK", "{\"running\":false}".to_string()),
+                    };
+                    pending.reply.send(reply).ok();
+                }
+            });
+        });
+    }
+
+    // Sends one request to handle_client over loopback and returns the whole response
+    fn exchange(method: &str, target: &str, auth: Option<&str>) -> String {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || handle_client(listener.accept().unwrap().0, TOKEN));
+        let mut client = TcpStream::connect(addr).unwrap();
+        let auth = auth.map(|a| format!("Authorization: {}\r\n", a)).unwrap_or_default();
+        write!(client, "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: 0\r\n\r\n", method, target, auth).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+        response
+    }
+
+    fn authorized(method: &str, target: &str) -> String {
+        exchange(method, target, Some(&format!("Bearer {}", TOKEN)))
+    }
+
+    #[test]
+    fn requests_without_the_right_token_are_rejected() {
+        for auth in [None, Some("Bearer wrong!"), Some("Bearer s3cre"), Some(TOKEN)] {
+            let response = exchange("GET", "/stressors", auth);
+            assert!(response.starts_with("HTTP/1.1 401 Unauthorized\r\n"), "{:?} was let in", auth);
+            assert!(response.contains("WWW-Authenticate: Bearer\r\n"));
+        }
+        assert!(exchange("GET", "/stressors", Some("bearer s3cret")).starts_with("HTTP/1.1 401"));
+        assert!(token_matches(TOKEN, TOKEN) && !token_matches(TOKEN, "s3cres") && !token_matches("", TOKEN));
+    }
+
+    #[test]
+    fn stressors_lists_every_target() {
+        let response = authorized("GET", "/stressors");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        for target in ScheduleTarget::ALL {
+            assert!(response.contains(&format!("{{\"name\":\"{}\"", target.name())));
+        }
+    }
+
+    #[test]
+    fn start_parameters_are_validated_before_queueing() {
+        assert!(authorized("POST", "/stressors/gpu/start").starts_with("HTTP/1.1 404"));
+        assert!(authorized("POST", "/stressors/cpu/start?duration_secs=0").starts_with("HTTP/1.1 400"));
+        assert!(authorized("POST", &format!("/stressors/cpu/start?duration_secs={}", MAX_DURATION_SECS + 1)).starts_with("HTTP/1.1 400"));
+        assert!(authorized("POST", "/stressors/cpu/start?seed=0").starts_with("HTTP/1.1 400"));
+        assert!(authorized("POST", "/stressors/cpu/start?speed=11").starts_with("HTTP/1.1 400"));
+        assert!(authorized("GET", "/stressors/cpu/start").starts_with("HTTP/1.1 404"));
+        assert!(authorized("DELETE", "/stop").starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn queued_requests_are_answered_by_the_owning_loop() {
+        let result_file = std::env::temp_dir().join(format!("stressor_control_test_{}.csv", std::process::id()));
+        std::fs::write(&result_file, "time,ops\n1,2\n").unwrap();
+        fake_loop(result_file.clone());
+        let started = authorized("POST", "/stressors/system/start?duration_secs=600&seed=42");
+        assert!(started.starts_with("HTTP/1.1 202 Accepted\r\n"));
+        assert!(started.ends_with("{\"started\":\"system\",\"duration_secs\":Some(600),\"seed\":Some(42)}"));
+        assert!(authorized("POST", "/stop").ends_with("{\"stopped\":false}"));
+        let result = authorized("GET", "/results/cpu");
+        std::fs::remove_file(&result_file).ok();
+        assert!(result.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(result.contains(&format!("Content-Disposition: attachment; filename=\"{}\"\r\n", result_file.file_name().unwrap().to_string_lossy())));
+        assert!(result.ends_with("\r\n\r\ntime,ops\n1,2\n"));
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\u000a\"");
+        assert_eq!(error_json("nope"), "{\"error\":\"nope\"}");
+    }
+}
@@ -0,0 +1,401 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use crate::app::analyzer::{analyze_cpu_stress_csv, analyze_score_csv, analyze_storage_stress_csv, analyze_system_stress_csv};
+use crate::app::scheduler::ScheduleTarget;
+use crate::app::scoring::{ScoreMode, SCORE_VERSION};
+use crate::app::inventory;
+use crate::app::seed;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub struct Agent {
+    pub addr: String,
+    pub token: String,
+}
+
+impl Agent {
+    // Used for file names and table rows
+    pub fn label(&self) -> String {
+        self.addr.replace([':', '/', '\\'], "_")
+    }
+}
+
+// The file name from an agent's Content-Disposition header, reduced to its last component so a
+// name with separators or ".." can't write outside the campaign directory
+fn result_file_name(headers: &str) -> Option<String> {
+    let raw = headers.lines().find_map(|l| l.split_once("filename=\"").map(|(_, rest)| rest.trim_end_matches('"')))?;
+    // Agents may run on Windows, so treat backslashes as separators too
+    let name = Path::new(raw.rsplit('\\').next()?).file_name()?.to_str()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+// One agent per line: "<host:port> [token]". Blank lines and lines starting with # are skipped.
+pub fn parse_agents(text: &str, default_token: Option<&str>) -> Result<Vec<Agent>, String> {
+    let mut agents = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let addr = parts.next().unwrap_or_default().to_string();
+        let token = parts.next().or(default_token).ok_or_else(|| format!("agents line {}: no token and no --control-token given", n + 1))?;
+        agents.push(Agent { addr, token: token.to_string() });
+    }
+    if agents.is_empty() {
+        return Err("agents file lists no agents".to_string());
+    }
+    Ok(agents)
+}
+
+// Parses "<test>" or "<test>:<duration_secs>"
+pub fn parse_plan(spec: &str) -> Result<(ScheduleTarget, Option<u32>), String> {
+    let (name, secs) = match spec.split_once(':') {
+        Some((name, secs)) => (name, Some(secs.parse::<u32>().map_err(|_| format!("invalid plan duration '{}'", secs))?)),
+        None => (spec, None),
+    };
+    let target = ScheduleTarget::from_name(name).ok_or_else(|| format!("unknown plan test '{}'", name))?;
+    Ok((target, secs))
+}
+
+struct HttpResponse {
+    status: u16,
+    headers: String,
+    body: Vec<u8>,
+}
+
+fn connect(agent: &Agent) -> Result<TcpStream, String> {
+    let addr = agent.addr.to_socket_addrs().ok().and_then(|mut a| a.next()).ok_or_else(|| format!("{}: cannot resolve address", agent.addr))?;
+    let stream = TcpStream::connect_timeout(&addr, HTTP_TIMEOUT).map_err(|e| format!("{}: {}", agent.addr, e))?;
+    stream.set_read_timeout(Some(HTTP_TIMEOUT)).ok();
+    stream.set_nodelay(true).ok();
+    Ok(stream)
+}
+
+// Sends on an already open connection so callers can connect ahead of time
+fn send(mut stream: TcpStream, agent: &Agent, method: &str, path: &str) -> Result<HttpResponse, String> {
+    let request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        method, path, agent.addr, agent.token
+    );
+    stream.write_all(request.as_bytes()).map_err(|e| format!("{}: {}", agent.addr, e))?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(|e| format!("{}: {}", agent.addr, e))?;
+    let split = raw.windows(4).position(|w| w == b"\r\n\r\n").ok_or_else(|| format!("{}: malformed response", agent.addr))?;
+    let headers = String::from_utf8_lossy(&raw[..split]).to_string();
+    let status = headers.split_whitespace().nth(1).and_then(|s| s.parse().ok()).ok_or_else(|| format!("{}: malformed status line", agent.addr))?;
+    Ok(HttpResponse { status, headers, body: raw[split + 4..].to_vec() })
+}
+
+fn request(agent: &Agent, method: &str, path: &str) -> Result<HttpResponse, String> {
+    send(connect(agent)?, agent, method, path)
+}
+
+// The control API's JSON is flat enough to pick values out by key without a parser
+fn json_value<'a>(json: &'a str, key: &str) -> Option<&'a str> {
+    let start = json.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let rest = &json[start..];
+    let end = rest.find([',', '}']).unwrap_or(rest.len());
+    Some(rest[..end].trim().trim_matches('"'))
+}
+
+// The status entry for one test: {"name":"cpu",...}
+fn test_status(status: &str, target: ScheduleTarget) -> Option<&str> {
+    let start = status.find(&format!("{{\"name\":\"{}\"", target.name()))?;
+    let end = status[start..].find('}')? + start;
+    Some(&status[start..=end])
+}
+
+fn check_ok(agent: &Agent, response: &HttpResponse) -> Result<(), String> {
+    if response.status / 100 == 2 {
+        return Ok(());
+    }
+    let body = String::from_utf8_lossy(&response.body);
+    let message = json_value(&body, "error").unwrap_or(&body);
+    Err(format!("{}: HTTP {} {}", agent.addr, response.status, message))
+}
+
+// Opens every connection first and releases all start requests together so agents begin within
+// a few milliseconds of each other
+fn start_in_sync(agents: &[Agent], path: &str) -> Vec<Result<Duration, String>> {
+    let barrier = Arc::new(Barrier::new(agents.len()));
+    thread::scope(|scope| {
+        let handles: Vec<_> = agents
+            .iter()
+            .map(|agent| {
+                let barrier = barrier.clone();
+                scope.spawn(move || {
+                    // A failed connect still has to reach the barrier or the others would wait forever
+                    let stream = connect(agent);
+                    barrier.wait();
+                    let sent_at = Instant::now();
+                    let response = send(stream?, agent, "POST", path)?;
+                    check_ok(agent, &response)?;
+                    Ok(sent_at.elapsed())
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap_or_else(|_| Err("start thread panicked".to_string()))).collect()
+    })
+}
+
+// Headline score used for ranking, plus the formatted comparison columns
+type ResultSummary = (f64, Vec<String>);
+
+fn summarize_result(target: ScheduleTarget, path: &Path) -> Option<ResultSummary> {
+    let path = path.to_str()?;
+    match target {
+        ScheduleTarget::Cpu => {
+            let (thread_rates, _, avg, max, min, stddev) = analyze_cpu_stress_csv(path)?;
+            let threads = thread_rates.len();
+            let total = avg * threads as f64;
+            Some((total, vec![format!("{:.0}", total), format!("{:.0}", avg), format!("{:.0}", min), format!("{:.0}", max), format!("{:.0}", stddev), threads.to_string()]))
+        }
+        ScheduleTarget::Storage => {
+            let (_, _, avg_write, avg_read, _, _, min_write, min_read, _, _) = analyze_storage_stress_csv(path)?;
+            Some((avg_write + avg_read, vec![format!("{:.2}", avg_write), format!("{:.2}", avg_read), format!("{:.2}", min_write), format!("{:.2}", min_read)]))
+        }
+        ScheduleTarget::SystemPreset => {
+            let (samples, avg, peak) = analyze_system_stress_csv(path)?;
+            let (avg, peak) = match (avg, peak) {
+                (Some(avg), Some(peak)) => (avg, peak),
+                _ => crate::app::system_stress::summarize(&samples)?,
+            };
+            Some((avg.cpu_iters_per_sec, vec![
+                format!("{:.0}", avg.cpu_iters_per_sec),
+                format!("{:.0}", avg.ram_ops_per_sec),
+                format!("{:.2}", avg.storage_write_mbps),
+                format!("{:.2}", avg.storage_read_mbps),
+                peak.cpu_temp_c.map(|t| format!("{:.1}", t)).unwrap_or_else(|| "N/A".to_string()),
+                avg.cpu_mhz.to_string(),
+            ]))
+        }
+        ScheduleTarget::Score => {
+            let summaries = analyze_score_csv(path)?;
+            let composite = |mode| summaries.iter().find(|s| s.mode == mode && s.workload.is_none());
+            let (st, mt) = (composite(ScoreMode::SingleThread)?, composite(ScoreMode::MultiThread)?);
+            Some((mt.score, vec![
+                format!("{:.0}", st.score),
+                format!("{:.0}", st.ci_high - st.score),
+                format!("{:.0}", mt.score),
+                format!("{:.0}", mt.ci_high - mt.score),
+                SCORE_VERSION.to_string(),
+            ]))
+        }
+    }
+}
+
+fn comparison_header(target: ScheduleTarget) -> &'static [&'static str] {
+    match target {
+        ScheduleTarget::Cpu => &["total_iters_per_sec", "avg_thread_iters_per_sec", "min", "max", "stddev", "threads"],
+        ScheduleTarget::Storage => &["avg_write_mbps", "avg_read_mbps", "min_write_mbps", "min_read_mbps"],
+        ScheduleTarget::SystemPreset => &["avg_cpu_iters_per_sec", "avg_ram_ops_per_sec", "avg_write_mbps", "avg_read_mbps", "peak_cpu_temp_c", "avg_cpu_mhz"],
+        ScheduleTarget::Score => &["st_score", "st_ci95", "mt_score", "mt_ci95", "score_version"],
+    }
+}
+
+// Writes comparison.csv and prints the same table, with each agent's headline score relative to the best
+fn write_comparison(target: ScheduleTarget, campaign_dir: &Path, results: &[(String, Option<PathBuf>)]) {
+    let header = comparison_header(target);
+    let mut rows: Vec<(String, Option<ResultSummary>, Option<u64>)> = results
+        .iter()
+        .map(|(label, path)| (
+            label.clone(),
+            path.as_deref().and_then(|p| summarize_result(target, p)),
+            path.as_deref().and_then(|p| seed::from_entries(&inventory::read_from_csv(p))),
+        ))
+        .collect();
+    rows.sort_by(|a, b| {
+        let score = |r: &Option<ResultSummary>| r.as_ref().map(|(s, _)| *s).unwrap_or(f64::MIN);
+        score(&b.1).total_cmp(&score(&a.1))
+    });
+    let best = rows.iter().filter_map(|(_, r, _)| r.as_ref().map(|(s, _)| *s)).fold(f64::NAN, f64::max);
+    let mut table = vec![std::iter::once("agent").chain(header.iter().copied()).chain(["vs_best_percent", "seed"]).map(String::from).collect::<Vec<_>>()];
+    for (label, row, run_seed) in &rows {
+        let mut cells = vec![label.clone()];
+        match row {
+            Some((score, values)) => {
+                cells.extend(values.iter().cloned());
+                cells.push(if best > 0.0 { format!("{:.1}", score / best * 100.0) } else { "N/A".to_string() });
+            }
+            None => cells.extend(std::iter::repeat_n("N/A".to_string(), header.len() + 1)),
+        }
+        cells.push(run_seed.map(|s| s.to_string()).unwrap_or_else(|| "N/A".to_string()));
+        table.push(cells);
+    }
+    let csv: String = table.iter().map(|r| r.join(",") + "\n").collect();
+    if let Err(e) = fs::write(campaign_dir.join("comparison.csv"), csv) {
+        eprintln!("Failed to write comparison.csv: {}", e);
+    }
+    let widths: Vec<usize> = (0..table[0].len()).map(|c| table.iter().map(|r| r[c].len()).max().unwrap_or(0)).collect();
+    for row in &table {
+        let line: Vec<String> = row.iter().zip(&widths).map(|(cell, w)| format!("{:>w$}", cell, w = w)).collect();
+        println!("{}", line.join("  "));
+    }
+}
+
+pub fn run_campaign(agents: &[Agent], target: ScheduleTarget, duration_secs: Option<u32>, dev_mode: bool) -> Result<(), String> {
+    // Preflight: every agent must be reachable, accept the token and be idle
+    for agent in agents {
+        let response = request(agent, "GET", "/status")?;
+        check_ok(agent, &response)?;
+        if json_value(&String::from_utf8_lossy(&response.body), "running") == Some("true") {
+            return Err(format!("{}: a test is already running", agent.addr));
+        }
+    }
+    // Every agent gets the campaign's seed so they all work on the same input data
+    let campaign_seed = seed::current();
+    let path = match duration_secs {
+        Some(secs) => format!("/stressors/{}/start?duration_secs={}&seed={}", target.name(), secs, campaign_seed),
+        None => format!("/stressors/{}/start?seed={}", target.name(), campaign_seed),
+    };
+    println!("Starting {} on {} agents (seed {})", target.label(), agents.len(), campaign_seed);
+    let started = start_in_sync(agents, &path);
+    if started.iter().any(|r| r.is_err()) {
+        for (agent, result) in agents.iter().zip(&started) {
+            match result {
+                Ok(_) => {
+                    request(agent, "POST", "/stop").ok();
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        return Err("not every agent started, campaign aborted".to_string());
+    }
+    for (agent, result) in agents.iter().zip(&started) {
+        if let Ok(latency) = result {
+            println!("  {} acknowledged after {} ms", agent.addr, latency.as_millis());
+        }
+    }
+    let campaign_start = Instant::now();
+    // Aggregate progress until every agent reports the test finished
+    let mut offline = vec![false; agents.len()];
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut cells = Vec::new();
+        let mut progress_sum = 0.0;
+        let mut any_running = false;
+        for (i, agent) in agents.iter().enumerate() {
+            let status = request(agent, "GET", "/status").ok().filter(|r| r.status == 200).map(|r| String::from_utf8_lossy(&r.body).to_string());
+            offline[i] = status.is_none();
+            let test = status.as_deref().and_then(|s| test_status(s, target));
+            let running = test.and_then(|t| json_value(t, "running")) == Some("true");
+            let progress = test.and_then(|t| json_value(t, "progress")).and_then(|p| p.parse::<f64>().ok()).unwrap_or(if running { 0.0 } else { 1.0 });
+            any_running |= running;
+            progress_sum += progress;
+            cells.push(match (status.is_some(), running) {
+                (false, _) => format!("{} offline", agent.addr),
+                (true, true) => format!("{} {:.0}%", agent.addr, progress * 100.0),
+                (true, false) => format!("{} done", agent.addr),
+            });
+        }
+        println!(
+            "[{:>5}s] {:.0}% overall | {}",
+            campaign_start.elapsed().as_secs(),
+            progress_sum / agents.len() as f64 * 100.0,
+            cells.join(" | ")
+        );
+        if !any_running {
+            break;
+        }
+    }
+    let log_dir = crate::app::log_dir(dev_mode);
+    let campaign_dir = log_dir.join("campaigns").join(format!("campaign_{}_{}", Local::now().format("%Y%m%d_%H%M%S"), target.name()));
+    fs::create_dir_all(&campaign_dir).map_err(|e| format!("Failed to create {}: {}", campaign_dir.display(), e))?;
+    let mut results = Vec::new();
+    for (agent, &was_offline) in agents.iter().zip(&offline) {
+        let saved = if was_offline {
+            eprintln!("{}: offline, no result collected", agent.addr);
+            None
+        } else {
+            match request(agent, "GET", &format!("/results/{}", target.name())).and_then(|r| check_ok(agent, &r).map(|_| r)) {
+                Ok(response) => {
+                    let remote_name = result_file_name(&response.headers).unwrap_or_else(|| format!("{}.csv", target.name()));
+                    let local = campaign_dir.join(format!("{}__{}", agent.label(), remote_name));
+                    match fs::write(&local, &response.body) {
+                        Ok(()) => Some(local),
+                        Err(e) => {
+                            eprintln!("Failed to save {}: {}", local.display(), e);
+                            None
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    None
+                }
+            }
+        };
+        results.push((agent.label(), saved));
+    }
+    println!("\nResults saved to {}\n", campaign_dir.display());
+    write_comparison(target, &campaign_dir, &results);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_value_reads_strings_numbers_and_the_last_key() {
+        let json = r#"{"name":"cpu","running":true,"progress":0.5,"error":"busy"}"#;
+        assert_eq!(json_value(json, "name"), Some("cpu"));
+        assert_eq!(json_value(json, "running"), Some("true"));
+        assert_eq!(json_value(json, "progress"), Some("0.5"));
+        assert_eq!(json_value(json, "error"), Some("busy"));
+        assert_eq!(json_value(json, "seed"), None);
+    }
+
+    #[test]
+    fn test_status_picks_the_entry_for_one_test() {
+        let status = r#"{"tests":[{"name":"system","running":false},{"name":"cpu","running":true,"progress":0.25}]}"#;
+        let cpu = test_status(status, ScheduleTarget::Cpu).unwrap();
+        assert_eq!(json_value(cpu, "running"), Some("true"));
+        assert_eq!(json_value(cpu, "progress"), Some("0.25"));
+        assert!(test_status(status, ScheduleTarget::Score).is_none());
+    }
+
+    #[test]
+    fn parse_agents_uses_the_default_token_and_skips_comments() {
+        let agents = parse_agents("# bench rack\nhost-a:8720 secret-a\n\n  host-b:8720\n", Some("shared")).unwrap();
+        assert_eq!(agents.len(), 2);
+        assert_eq!((agents[0].addr.as_str(), agents[0].token.as_str()), ("host-a:8720", "secret-a"));
+        assert_eq!((agents[1].addr.as_str(), agents[1].token.as_str()), ("host-b:8720", "shared"));
+        assert_eq!(agents[1].label(), "host-b_8720");
+    }
+
+    #[test]
+    fn parse_agents_needs_a_token_and_at_least_one_agent() {
+        assert!(matches!(parse_agents("host-a:8720\n", None), Err(e) if e.contains("line 1")));
+        assert!(parse_agents("# nothing here\n", Some("shared")).is_err());
+    }
+
+    #[test]
+    fn parse_plan_accepts_an_optional_duration() {
+        assert_eq!(parse_plan("system:600"), Ok((ScheduleTarget::SystemPreset, Some(600))));
+        assert_eq!(parse_plan("cpu"), Ok((ScheduleTarget::Cpu, None)));
+        assert!(parse_plan("cpu:ten").is_err());
+        assert!(parse_plan("gpu:60").is_err());
+    }
+
+    #[test]
+    fn result_file_name_keeps_only_the_last_component() {
+        let headers = |name: &str| format!("HTTP/1.1 200 OK\r\nContent-Disposition: attachment; filename=\"{}\"", name);
+        assert_eq!(result_file_name(&headers("cpu_stress.csv")).as_deref(), Some("cpu_stress.csv"));
+        assert_eq!(result_file_name(&headers("../../etc/cron.d/x.csv")).as_deref(), Some("x.csv"));
+        assert_eq!(result_file_name(&headers("C:\\log\\cpu.csv")).as_deref(), Some("cpu.csv"));
+        assert_eq!(result_file_name(&headers("..")), None);
+        assert_eq!(result_file_name(&headers("")), None);
+        assert_eq!(result_file_name("HTTP/1.1 200 OK\r\nContent-Type: text/csv"), None);
+    }
+}
@@ -0,0 +1,319 @@
+use eframe::egui;
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use sysinfo::{System, SystemExt, CpuExt};
+use crate::app::cpu::sample_cpu_temperature;
+use crate::app::topology;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::seed;
+
+// Number of distinct inputs the verification kernel cycles through
+const SWEEP_SEEDS: u64 = 16;
+
+#[derive(Clone, Debug)]
+pub struct CoreSweepResult {
+    pub cpu: usize,
+    pub core: usize,
+    pub iterations_per_sec: f64,
+    pub errors: u64,
+    pub first_error_secs: Option<f64>,
+    pub max_mhz: u64,
+    pub max_temp_c: Option<f32>,
+    // The worker couldn't be pinned to this CPU, so nothing was measured on it
+    pub pin_failed: bool,
+}
+
+impl CoreSweepResult {
+    pub fn passed(&self) -> bool {
+        self.errors == 0 && !self.pin_failed
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.pin_failed {
+            "pin_failed"
+        } else if self.errors > 0 {
+            "fail"
+        } else {
+            "pass"
+        }
+    }
+}
+
+// Weakest first: any errors outrank clean cores, earlier first error is worse, then slower is worse.
+// Skipped cores go last since they weren't tested.
+pub fn rank_results(results: &mut [CoreSweepResult]) {
+    results.sort_by(|a, b| {
+        a.pin_failed.cmp(&b.pin_failed)
+            .then_with(|| b.errors.min(1).cmp(&a.errors.min(1)))
+            .then_with(|| {
+                let a_first = a.first_error_secs.unwrap_or(f64::INFINITY);
+                let b_first = b.first_error_secs.unwrap_or(f64::INFINITY);
+                a_first.total_cmp(&b_first)
+            })
+            .then_with(|| b.errors.cmp(&a.errors))
+            .then_with(|| a.iterations_per_sec.total_cmp(&b.iterations_per_sec))
+    });
+}
+
+// Mixed integer/FP kernel with a deterministic result, so any deviation means the core computed wrong
+fn sweep_kernel(seed: u64) -> u64 {
+    let mut acc = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    let mut f = seed as f64 + 1.5;
+    for i in 1..4096u64 {
+        acc ^= acc << 13;
+        acc ^= acc >> 7;
+        acc ^= acc << 17;
+        acc = acc.wrapping_mul(i | 1).wrapping_add(i);
+        f = (f * 1.000_001 + (i as f64).sqrt()).mul_add(0.999_999, 0.5);
+    }
+    acc ^ f.to_bits()
+}
+
+pub struct CoreSweep {
+    running: Arc<AtomicBool>,
+    secs_per_core: Arc<Mutex<u32>>,
+    current_cpu: Arc<AtomicUsize>,
+    results: Arc<Mutex<Vec<CoreSweepResult>>>,
+    log_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl CoreSweep {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            secs_per_core: Arc::new(Mutex::new(config.core_sweep_secs_per_core)),
+            current_cpu: Arc::new(AtomicUsize::new(0)),
+            results: Arc::new(Mutex::new(Vec::new())),
+            log_path: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl CoreSweep {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
+        ui.heading("Per-Core Stability Sweep");
+        ui.add_space(10.0);
+        ui.label("Runs a verified single-threaded workload pinned to each logical CPU in turn, then ranks cores from weakest to strongest. Useful for tuning per-core overclocks and curve offsets.");
+        ui.add_space(10.0);
+        let mut secs = *self.secs_per_core.lock().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Seconds per Core:");
+            if ui.add(egui::DragValue::new(&mut secs).range(1..=600)).changed() {
+                *self.secs_per_core.lock().unwrap() = secs;
+            }
+        });
+        let cpus: Vec<(usize, usize)> = {
+            let mut cpus: Vec<(usize, usize)> = topology::get().cpus.iter().map(|c| (c.id, c.core)).collect();
+            cpus.sort();
+            cpus
+        };
+        ui.label(format!("Estimated duration: {} s across {} logical CPUs", secs as usize * cpus.len(), cpus.len()));
+        ui.add_space(10.0);
+        if ui.button(if self.running.load(Ordering::SeqCst) { "Stop Core Sweep" } else { "Start Core Sweep" }).clicked() {
+            let running = self.running.clone();
+            if running.load(Ordering::SeqCst) {
+                running.store(false, Ordering::SeqCst);
+            } else {
+                running.store(true, Ordering::SeqCst);
+                self.results.lock().unwrap().clear();
+                let results = self.results.clone();
+                let current_cpu = self.current_cpu.clone();
+                let log_path = self.log_path.clone();
+                let secs_per_core = secs;
+                let ctx = ctx.clone();
+                let cpus = cpus.clone();
+                if dev_mode {
+                    println!("[DEV] Starting core sweep: secs_per_core={}, cpus={}", secs_per_core, cpus.len());
+                }
+                thread::spawn(move || {
+                    // The result log is only written once the sweep is done, see set_csv below
+                    let journal = RunJournal::begin(dev_mode, "core_sweep", None);
+                    let watch = watchdog::watch("core_sweep", secs_per_core as u64 * cpus.len() as u64, {
+                        let running = running.clone();
+                        move || running.store(false, Ordering::SeqCst)
+                    });
+                    let expected: Arc<Vec<u64>> = Arc::new((0..SWEEP_SEEDS).map(sweep_kernel).collect());
+                    let mut sys = System::new_all();
+                    for &(cpu, core) in &cpus {
+                        if !running.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        current_cpu.store(cpu, Ordering::SeqCst);
+                        ctx.request_repaint();
+                        let iters = Arc::new(AtomicU64::new(0));
+                        let errors = Arc::new(AtomicU64::new(0));
+                        let first_error_ns = Arc::new(AtomicU64::new(u64::MAX));
+                        let start = Instant::now();
+                        let end = start + Duration::from_secs(secs_per_core as u64);
+                        let (pinned_tx, pinned_rx) = std::sync::mpsc::channel();
+                        let worker = {
+                            let running = running.clone();
+                            let iters = iters.clone();
+                            let errors = errors.clone();
+                            let first_error_ns = first_error_ns.clone();
+                            let expected = expected.clone();
+                            thread::spawn(move || {
+                                let pinned = topology::pin_current_thread(cpu);
+                                let _ = pinned_tx.send(pinned);
+                                // Unpinned, the OS would run this on any CPU and the result would be misattributed
+                                if !pinned {
+                                    return;
+                                }
+                                let mut n = 0u64;
+                                while running.load(Ordering::SeqCst) && Instant::now() < end {
+                                    let seed = n % SWEEP_SEEDS;
+                                    if std::hint::black_box(sweep_kernel(seed)) != expected[seed as usize] {
+                                        errors.fetch_add(1, Ordering::SeqCst);
+                                        first_error_ns.fetch_min(start.elapsed().as_nanos() as u64, Ordering::SeqCst);
+                                    }
+                                    n += 1;
+                                    if n.is_multiple_of(64) {
+                                        iters.store(n, Ordering::SeqCst);
+                                    }
+                                }
+                                iters.store(n, Ordering::SeqCst);
+                            })
+                        };
+                        if !pinned_rx.recv().unwrap_or(false) {
+                            let _ = worker.join();
+                            if dev_mode {
+                                println!("[DEV] Core sweep: could not pin to CPU {}, skipping it", cpu);
+                            }
+                            results.lock().unwrap().push(CoreSweepResult {
+                                cpu,
+                                core,
+                                iterations_per_sec: 0.0,
+                                errors: 0,
+                                first_error_secs: None,
+                                max_mhz: 0,
+                                max_temp_c: None,
+                                pin_failed: true,
+                            });
+                            ctx.request_repaint();
+                            continue;
+                        }
+                        let mut max_mhz = 0u64;
+                        let mut max_temp: Option<f32> = None;
+                        while running.load(Ordering::SeqCst) && Instant::now() < end {
+                            thread::sleep(Duration::from_millis(200));
+                            watch.heartbeat();
+                            sys.refresh_cpu();
+                            if let Some(c) = sys.cpus().get(cpu) {
+                                max_mhz = max_mhz.max(c.frequency());
+                            }
+                            if let Some(t) = sample_cpu_temperature(&mut sys) {
+                                max_temp = Some(max_temp.map_or(t, |m| m.max(t)));
+                            }
+                        }
+                        let _ = worker.join();
+                        let elapsed = start.elapsed().as_secs_f64().max(1e-6);
+                        let first_error = first_error_ns.load(Ordering::SeqCst);
+                        results.lock().unwrap().push(CoreSweepResult {
+                            cpu,
+                            core,
+                            iterations_per_sec: iters.load(Ordering::SeqCst) as f64 / elapsed,
+                            errors: errors.load(Ordering::SeqCst),
+                            first_error_secs: (first_error != u64::MAX).then(|| first_error as f64 / 1e9),
+                            max_mhz,
+                            max_temp_c: max_temp,
+                            pin_failed: false,
+                        });
+                        ctx.request_repaint();
+                    }
+                    let mut ranked = results.lock().unwrap().clone();
+                    rank_results(&mut ranked);
+                    let hash = seed::file_hash();
+                    let date = Local::now().format("%Y%m%d_%H%M%S");
+                    let log_dir = crate::app::log_dir(dev_mode);
+                    let _ = std::fs::create_dir_all(&log_dir);
+                    let log_file_name = log_dir.join(format!("core_sweep_{}_{}_dur{}.csv", hash, date, secs_per_core));
+                    if let Ok(mut log_file) = OpenOptions::new().create(true).append(true).open(&log_file_name) {
+                        writeln!(log_file, "rank,cpu,core,iterations_per_sec,errors,first_error_secs,max_mhz,max_temp_c,status").ok();
+                        inventory::get().write_csv_comments(&mut log_file);
+                        for (rank, r) in ranked.iter().enumerate() {
+                            writeln!(
+                                log_file,
+                                "{},{},{},{},{},{},{},{},{}",
+                                rank + 1,
+                                r.cpu,
+                                r.core,
+                                r.iterations_per_sec,
+                                r.errors,
+                                r.first_error_secs.map(|s| s.to_string()).unwrap_or_default(),
+                                r.max_mhz,
+                                r.max_temp_c.map(|t| t.to_string()).unwrap_or_default(),
+                                r.status()
+                            ).ok();
+                        }
+                        *log_path.lock().unwrap() = Some(log_file_name.clone());
+                        if let Some(journal) = &journal {
+                            journal.set_csv(&log_file_name);
+                        }
+                        if dev_mode {
+                            println!("[DEV] Created log file: {}", log_file_name.display());
+                        }
+                    }
+                    if let Some(journal) = journal {
+                        journal.finish();
+                    }
+                    running.store(false, Ordering::SeqCst);
+                    ctx.request_repaint();
+                });
+            }
+        }
+        let mut ranked = self.results.lock().unwrap().clone();
+        if self.running.load(Ordering::SeqCst) {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Testing CPU {} ({} of {})...", self.current_cpu.load(Ordering::SeqCst), ranked.len() + 1, cpus.len()),
+            );
+        } else if let Some(log_path) = &*self.log_path.lock().unwrap() {
+            ui.label(format!("Log saved to: {}", log_path.display()));
+        }
+        if !ranked.is_empty() {
+            rank_results(&mut ranked);
+            ui.add_space(10.0);
+            ui_ranked_table(ui, "core_sweep_table", &ranked);
+        }
+    }
+}
+
+pub fn ui_ranked_table(ui: &mut egui::Ui, id: &str, ranked: &[CoreSweepResult]) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Rank"); ui.label("CPU"); ui.label("Core"); ui.label("Iter/s"); ui.label("Errors"); ui.label("Max MHz"); ui.label("Max °C"); ui.label("Status"); ui.end_row();
+        for (rank, r) in ranked.iter().enumerate() {
+            ui.label(format!("{}", rank + 1));
+            ui.label(format!("{}", r.cpu));
+            ui.label(format!("{}", r.core));
+            ui.label(format!("{:.2}", r.iterations_per_sec));
+            ui.label(format!("{}", r.errors));
+            ui.label(if r.max_mhz > 0 { format!("{}", r.max_mhz) } else { "N/A".to_string() });
+            ui.label(r.max_temp_c.map(|t| format!("{:.1}", t)).unwrap_or_else(|| "N/A".to_string()));
+            if r.pin_failed {
+                ui.colored_label(egui::Color32::GRAY, "SKIPPED (pin failed)");
+            } else if r.passed() {
+                ui.colored_label(egui::Color32::GREEN, "PASS");
+            } else {
+                ui.colored_label(egui::Color32::RED, format!("FAIL @ {:.1}s", r.first_error_secs.unwrap_or(0.0)));
+            }
+            ui.end_row();
+        }
+    });
+}
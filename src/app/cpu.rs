@@ -1,6 +1,7 @@
 use eframe::egui::{self, Color32, Stroke};
-use sysinfo::{SystemExt, CpuExt};
+use sysinfo::{System, SystemExt, CpuExt};
 use crate::app::SystemMonitorApp;
+use crate::app::telemetry;
 use std::collections::VecDeque;
 use log::{error, info};
 
@@ -81,6 +82,31 @@ fn try_read_cpu_temperature() -> Option<u32> {
     }
 }
 
+// Package temperature for samplers that own their own `System`; Windows goes through the driver
+pub fn sample_cpu_temperature(sys: &mut System) -> Option<f32> {
+    #[cfg(windows)]
+    {
+        let _ = sys;
+        try_read_cpu_temperature().map(|t| t as f32)
+    }
+    #[cfg(not(windows))]
+    {
+        use sysinfo::ComponentExt;
+        if sys.components().is_empty() {
+            sys.refresh_components_list();
+        }
+        sys.refresh_components();
+        sys.components()
+            .iter()
+            .filter(|c| {
+                let label = c.label().to_lowercase();
+                label.contains("package") || label.contains("tctl") || label.contains("cpu") || label.contains("core")
+            })
+            .map(|c| c.temperature())
+            .fold(None, |acc: Option<f32>, t| Some(acc.map_or(t, |a| a.max(t))))
+    }
+}
+
 pub fn update_cpu_data(app: &mut SystemMonitorApp) {
     let now = std::time::Instant::now();
     if now.duration_since(app.last_update) >= std::time::Duration::from_millis(500) {
@@ -91,6 +117,7 @@ pub fn update_cpu_data(app: &mut SystemMonitorApp) {
             .map(|cpu| cpu.cpu_usage() as f64)
             .sum::<f64>() / app.sys.cpus().len() as f64;
         app.cpu_history.push((app.time_counter, avg_cpu_usage));
+        telemetry::emit("system", "cpu_usage_percent", avg_cpu_usage, None);
         // No need to pop_front, handled by History struct
         // Update CPU frequency (platform-specific)
         #[cfg(windows)]
@@ -135,6 +162,10 @@ pub fn update_cpu_data(app: &mut SystemMonitorApp) {
                 .unwrap_or(0);
             app.cpu_temperature_celsius = None;
         }
+        telemetry::emit("system", "cpu_mhz", app.current_cpu_freq as f64, None);
+        if let Some(temp) = app.cpu_temperature_celsius {
+            telemetry::emit("system", "cpu_temp_c", temp as f64, None);
+        }
         app.last_update = now;
     }
 }
@@ -155,6 +186,13 @@ pub fn ui_cpu_info(app: &mut SystemMonitorApp, ui: &mut egui::Ui) {
                         app.sys.physical_core_count().unwrap_or(0),
                         app.sys.cpus().len()
                     )).color(egui::Color32::LIGHT_BLUE));
+                    let topology = crate::app::topology::get();
+                    ui.label(format!(
+                        "Topology: {} package(s), {} NUMA node(s), SMT {}",
+                        topology.packages(),
+                        topology.numa_nodes().len(),
+                        if topology.has_smt() { "on" } else { "off" }
+                    ));
                 }
                 ui.add_space(10.0);
                 // Always show frequency info as text
@@ -9,10 +9,17 @@ use std::{
     time::{Duration, Instant},
 };
 use atomic_float::AtomicF64;
-use rand::{thread_rng, Rng};
 use chrono::Local;
 use num_cpus;
 use sysinfo::{System, SystemExt, CpuExt};
+use crate::app::topology::{self, PlacementPolicy};
+use crate::app::load_profile::{self, LoadGate, LoadProfile, UtilizationController, UtilizationTarget};
+use crate::app::telemetry;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::cpu::sample_cpu_temperature;
+use crate::app::seed;
 
 #[cfg(windows)]
 fn set_thread_priority_for_mode(max_stress: bool) {
@@ -37,9 +44,16 @@ pub struct CpuStress {
     intensity: Arc<Mutex<u32>>,
     last_score: Arc<AtomicF64>,
     live_rate: Arc<AtomicF64>,
+    // Iterations/sec over the last report interval, for timelines that need to see drops
+    interval_rate: Arc<AtomicF64>,
     log_path: Arc<Mutex<Option<PathBuf>>>,
     cpu_usage_history: Arc<Mutex<VecDeque<f64>>>,
     responsiveness_mode: Arc<Mutex<bool>>, // true = safe, false = max
+    placement: Arc<Mutex<PlacementPolicy>>,
+    load_profile: Arc<Mutex<LoadProfile>>,
+    load_history: Arc<Mutex<VecDeque<f64>>>,
+    utilization_target: Arc<Mutex<UtilizationTarget>>,
+    controller_output: Arc<AtomicF64>,
 }
 
 impl CpuStress {
@@ -50,14 +64,269 @@ impl CpuStress {
             intensity: Arc::new(Mutex::new(10000)),
             last_score: Arc::new(AtomicF64::new(0.0)),
             live_rate: Arc::new(AtomicF64::new(0.0)),
+            interval_rate: Arc::new(AtomicF64::new(0.0)),
             log_path: Arc::new(Mutex::new(None)),
             cpu_usage_history: Arc::new(Mutex::new(VecDeque::with_capacity(config.cpu_usage_history_len))),
             responsiveness_mode: Arc::new(Mutex::new(true)), // default to safe (checked)
+            placement: Arc::new(Mutex::new(PlacementPolicy::OsDefault)),
+            load_profile: Arc::new(Mutex::new(LoadProfile::Constant)),
+            load_history: Arc::new(Mutex::new(VecDeque::with_capacity(config.cpu_usage_history_len))),
+            utilization_target: Arc::new(Mutex::new(UtilizationTarget::Off)),
+            controller_output: Arc::new(AtomicF64::new(1.0)),
         }
     }
 }
 
 impl CpuStress {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn start(&self, ctx: &egui::Context, dev_mode: bool) {
+        let running = self.running.clone();
+        let cycle_secs = *self.cycle_secs.lock().unwrap();
+        let intensity = *self.intensity.lock().unwrap();
+        let last_score = self.last_score.clone();
+        let live_rate = self.live_rate.clone();
+        let interval_rate = self.interval_rate.clone();
+        let log_path = self.log_path.clone();
+        let cpu_usage_history = self.cpu_usage_history.clone();
+        let cpu_usage_history_len = self.cpu_usage_history.lock().unwrap().capacity();
+        let responsiveness_mode = *self.responsiveness_mode.lock().unwrap();
+        let pinned_cpus = Arc::new(self.placement.lock().unwrap().resolve(topology::get()));
+        let profile = *self.load_profile.lock().unwrap();
+        let load_history = self.load_history.clone();
+        let target = *self.utilization_target.lock().unwrap();
+        let controller_output = self.controller_output.clone();
+        let ctx = ctx.clone();
+        running.store(true, Ordering::SeqCst);
+        last_score.store(0.0, Ordering::SeqCst);
+        live_rate.store(0.0, Ordering::SeqCst);
+        interval_rate.store(0.0, Ordering::SeqCst);
+        {
+            let mut hist = cpu_usage_history.lock().unwrap();
+            hist.clear();
+        }
+        load_history.lock().unwrap().clear();
+        let num_threads = if pinned_cpus.is_empty() { num_cpus::get() } else { pinned_cpus.len() };
+        let hash = seed::file_hash();
+        let date = Local::now().format("%Y%m%d_%H%M%S");
+        let log_dir = crate::app::log_dir(dev_mode);
+        let _ = std::fs::create_dir_all(&log_dir);
+        let mut suffix = String::new();
+        if profile != LoadProfile::Constant {
+            suffix.push_str(&format!("_prof{}", profile.tag()));
+        }
+        if let Some(tag) = target.tag() {
+            suffix.push_str(&format!("_{}", tag));
+        }
+        let log_file_name = log_dir.join(format!("cpu_stress_{}_{}_int{}_dur{}{}.csv", hash, date, intensity, cycle_secs, suffix));
+        let log_path_val = PathBuf::from(&log_file_name);
+        {
+            let mut log_path_guard = log_path.lock().unwrap();
+            *log_path_guard = Some(log_path_val.clone());
+        }
+        if dev_mode {
+            println!("[DEV] Starting CPU stress test: intensity={}, cycle_secs={}, pinned_cpus={:?}", intensity, cycle_secs, pinned_cpus);
+        }
+        thread::spawn(move || {
+            let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).unwrap();
+            writeln!(log_file, "timestamp,thread,iterations_per_sec,target_load,cpu_mhz,cpu_temp_c").unwrap();
+            inventory::get().write_csv_comments(&mut log_file);
+            let journal = RunJournal::begin(dev_mode, "cpu_stress", Some(&log_file_name));
+            let watch = watchdog::watch("cpu_stress", cycle_secs as u64, {
+                let running = running.clone();
+                move || running.store(false, Ordering::SeqCst)
+            });
+            let pin_failures_before = topology::pin_failures();
+            let start = Instant::now();
+            let end = start + Duration::from_secs(cycle_secs as u64);
+            let mut controllers: Vec<UtilizationController> = match target {
+                UtilizationTarget::Off => Vec::new(),
+                UtilizationTarget::Total(t) => vec![UtilizationController::new(t)],
+                UtilizationTarget::PerCore(t) => (0..num_threads).map(|_| UtilizationController::new(t)).collect(),
+            };
+            let gates: Arc<Vec<LoadGate>> = Arc::new((0..num_threads).map(|_| LoadGate::starting_at(profile, start)).collect());
+            for (tid, gate) in gates.iter().enumerate() {
+                if let Some(c) = controllers.get(tid).or(controllers.first()) {
+                    gate.set_scale(c.output());
+                }
+            }
+            controller_output.store(1.0, Ordering::SeqCst);
+            let mut handles = Vec::new();
+            let thread_iters: Arc<Vec<AtomicU64>> = Arc::new((0..num_threads).map(|_| AtomicU64::new(0)).collect());
+            let thread_running = running.clone();
+            for tid in 0..num_threads {
+                let thread_iters = thread_iters.clone();
+                let thread_running = thread_running.clone();
+                let thread_intensity = intensity;
+                let thread_safe_stress = responsiveness_mode;
+                let pinned_cpus = pinned_cpus.clone();
+                let gates = gates.clone();
+                handles.push(thread::spawn(move || {
+                    topology::pin_worker(&pinned_cpus, tid);
+                    set_thread_priority_for_mode(!thread_safe_stress); // false = max, true = safe
+                    let mut _local_iters = 0u64;
+                    let mut update_counter = 0u64;
+                    while thread_running.load(Ordering::SeqCst) && Instant::now() < end {
+                        for _ in 0..thread_intensity {
+                            gates[tid].pace(|| thread_running.load(Ordering::SeqCst) && Instant::now() < end);
+                            let mut acc = 1u64;
+                            for i in 1..1000 {
+                                acc = acc.wrapping_mul(i ^ tid as u64);
+                            }
+                            std::hint::black_box(acc);
+                            _local_iters += 1;
+                            update_counter += 1;
+                            if update_counter >= 100_000 {
+                                thread_iters[tid].fetch_add(update_counter, Ordering::SeqCst);
+                                update_counter = 0;
+                                if thread_safe_stress {
+                                    std::thread::yield_now();
+                                }
+                            }
+                        }
+                    }
+                    if update_counter > 0 {
+                        thread_iters[tid].fetch_add(update_counter, Ordering::SeqCst);
+                    }
+                }));
+            }
+            let mut last_report = Instant::now();
+            let mut last_iters = vec![0u64; num_threads];
+            let mut sys = System::new_all();
+            while Instant::now() < end && running.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(200));
+                watch.heartbeat();
+                let elapsed = start.elapsed().as_secs_f64();
+                let target_load = gates.iter().map(|g| g.level()).sum::<f64>() / num_threads as f64 * 100.0;
+                // Clock and temperature go next to every sample so the analyzer can explain throughput drops
+                sys.refresh_cpu();
+                let cpu_mhz = sys.cpus().iter().map(|c| c.frequency()).max().unwrap_or(0);
+                let cpu_temp = sample_cpu_temperature(&mut sys);
+                let mut total_iters = 0u64;
+                let mut interval_iters = 0u64;
+                let interval = last_report.elapsed().as_secs_f64().max(1e-6);
+                for tid in 0..num_threads {
+                    let iters = thread_iters[tid].load(Ordering::SeqCst);
+                    let delta = iters - last_iters[tid];
+                    let rate = delta as f64 / interval;
+                    writeln!(
+                        log_file,
+                        "{},{},{},{},{},{}",
+                        Local::now().to_rfc3339(),
+                        tid,
+                        rate,
+                        target_load,
+                        cpu_mhz,
+                        cpu_temp.map(|t| t.to_string()).unwrap_or_default()
+                    ).ok();
+                    telemetry::emit("cpu_stress", "iters_per_sec", rate, Some(tid));
+                    last_iters[tid] = iters;
+                    total_iters += iters;
+                    interval_iters += delta;
+                }
+                interval_rate.store(interval_iters as f64 / interval, Ordering::SeqCst);
+                let rate = total_iters as f64 / elapsed;
+                live_rate.store(rate, Ordering::SeqCst);
+                telemetry::emit("cpu_stress", "avg_iters_per_sec", rate, None);
+                // Sample system CPU usage
+                let avg_cpu_usage = sys.cpus().iter().map(|cpu| cpu.cpu_usage() as f64).sum::<f64>() / sys.cpus().len() as f64;
+                {
+                    let mut hist = cpu_usage_history.lock().unwrap();
+                    if hist.len() >= cpu_usage_history_len {
+                        hist.pop_front();
+                    }
+                    hist.push_back(avg_cpu_usage);
+                }
+                // Closed-loop throttling towards the requested utilization
+                match target {
+                    UtilizationTarget::Off => {}
+                    UtilizationTarget::Total(_) => {
+                        let scale = controllers[0].update(avg_cpu_usage);
+                        for gate in gates.iter() {
+                            gate.set_scale(scale);
+                        }
+                    }
+                    UtilizationTarget::PerCore(_) => {
+                        for (tid, controller) in controllers.iter_mut().enumerate() {
+                            let cpu = pinned_cpus.get(tid).copied().unwrap_or(tid);
+                            if let Some(usage) = sys.cpus().get(cpu).map(|c| c.cpu_usage() as f64) {
+                                gates[tid].set_scale(controller.update(usage));
+                            }
+                        }
+                    }
+                }
+                if !controllers.is_empty() {
+                    let mean = controllers.iter().map(|c| c.output()).sum::<f64>() / controllers.len() as f64;
+                    controller_output.store(mean, Ordering::SeqCst);
+                }
+                {
+                    let mut hist = load_history.lock().unwrap();
+                    if hist.len() >= cpu_usage_history_len {
+                        hist.pop_front();
+                    }
+                    hist.push_back(target_load);
+                }
+                last_report = Instant::now();
+                ctx.request_repaint();
+            }
+            // Before clearing `running`, which is what the app waits on before it can exit
+            if let Some(journal) = journal {
+                journal.finish();
+            }
+            running.store(false, Ordering::SeqCst);
+            for handle in handles {
+                let _ = handle.join();
+            }
+            // Workers pin themselves as they start, so the count is only known now
+            if !pinned_cpus.is_empty() {
+                let unpinned = topology::pin_failures() - pin_failures_before;
+                writeln!(log_file, "# Unpinned workers: {}", unpinned).ok();
+                if dev_mode && unpinned > 0 {
+                    println!("[DEV] {} CPU stress worker(s) could not be pinned and ran where the OS put them", unpinned);
+                }
+            }
+            let elapsed = start.elapsed().as_secs_f64();
+            let total_iters: u64 = (0..num_threads).map(|tid| thread_iters[tid].load(Ordering::SeqCst)).sum();
+            // Each iteration is the same fixed kernel, so the rate doesn't depend on the intensity setting
+            let score = total_iters as f64 / elapsed;
+            last_score.store(score, Ordering::SeqCst);
+            live_rate.store(0.0, Ordering::SeqCst);
+            interval_rate.store(0.0, Ordering::SeqCst);
+            ctx.request_repaint();
+            if dev_mode {
+                println!("[DEV] CPU stress test thread finished");
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    // Used by presets that run this test with their own duration
+    pub fn set_duration_secs(&self, secs: u32) {
+        *self.cycle_secs.lock().unwrap() = secs;
+    }
+
+    pub fn duration_secs(&self) -> u32 {
+        *self.cycle_secs.lock().unwrap()
+    }
+
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.log_path.lock().unwrap().clone()
+    }
+
+    pub fn interval_rate_handle(&self) -> Arc<AtomicF64> {
+        self.interval_rate.clone()
+    }
+
+    // Clearing it stops the test, for presets whose watchdog has to stop it from another thread
+    pub fn running_handle(&self) -> Arc<AtomicBool> {
+        self.running.clone()
+    }
+
     pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
         ui.heading("CPU Stress Test");
         ui.add_space(10.0);
@@ -84,140 +353,34 @@ impl CpuStress {
                 *self.responsiveness_mode.lock().unwrap() = safe_stress;
             }
         });
+        topology::placement_ui(ui, "cpu_stress_placement", &mut self.placement.lock().unwrap());
+        load_profile::load_profile_ui(ui, "cpu_stress_load_profile", &mut self.load_profile.lock().unwrap());
+        load_profile::utilization_target_ui(ui, "cpu_stress_utilization", &mut self.utilization_target.lock().unwrap());
+        if matches!(*self.utilization_target.lock().unwrap(), UtilizationTarget::PerCore(_)) && *self.placement.lock().unwrap() == PlacementPolicy::OsDefault {
+            ui.colored_label(egui::Color32::YELLOW, "Per-core targets work best with pinned threads; unpinned thread N is matched to CPU N.");
+        }
         ui.add_space(10.0);
         if ui.button(if self.running.load(Ordering::SeqCst) { "Stop CPU Stress" } else { "Start CPU Stress" }).clicked() {
-            let running = self.running.clone();
-            let cycle_secs = *self.cycle_secs.lock().unwrap();
-            let intensity = *self.intensity.lock().unwrap();
-            let last_score = self.last_score.clone();
-            let live_rate = self.live_rate.clone();
-            let log_path = self.log_path.clone();
-            let cpu_usage_history = self.cpu_usage_history.clone();
-            let cpu_usage_history_len = self.cpu_usage_history.lock().unwrap().capacity();
-            let responsiveness_mode = *self.responsiveness_mode.lock().unwrap();
-            let ctx = ctx.clone();
-            let dev_mode = dev_mode;
-            if running.load(Ordering::SeqCst) {
-                running.store(false, Ordering::SeqCst);
+            if self.running.load(Ordering::SeqCst) {
+                self.stop();
             } else {
-                running.store(true, Ordering::SeqCst);
-                last_score.store(0.0, Ordering::SeqCst);
-                live_rate.store(0.0, Ordering::SeqCst);
-                {
-                    let mut hist = cpu_usage_history.lock().unwrap();
-                    hist.clear();
-                }
-                let num_threads = num_cpus::get();
-                let mut rng = thread_rng();
-                let hash: u16 = rng.gen_range(1000..9999);
-                let date = Local::now().format("%Y%m%d_%H%M%S");
-                let log_dir = if dev_mode {
-                    std::path::PathBuf::from("log")
-                } else {
-                    std::env::current_exe().ok().and_then(|p| p.parent().map(|d| d.to_path_buf())).unwrap_or_else(|| std::path::PathBuf::from("."))
-                        .join("log")
-                };
-                let _ = std::fs::create_dir_all(&log_dir);
-                let log_file_name = log_dir.join(format!("cpu_stress_{}_{}_int{}_dur{}.csv", hash, date, intensity, cycle_secs));
-                let log_path_val = PathBuf::from(&log_file_name);
-                {
-                    let mut log_path_guard = log_path.lock().unwrap();
-                    *log_path_guard = Some(log_path_val.clone());
-                }
-                if dev_mode {
-                    println!("[DEV] Starting CPU stress test: intensity={}, cycle_secs={}", intensity, cycle_secs);
-                }
-                thread::spawn(move || {
-                    let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).unwrap();
-                    writeln!(log_file, "timestamp,thread,iterations_per_sec").unwrap();
-                    let start = Instant::now();
-                    let end = start + Duration::from_secs(cycle_secs as u64);
-                    let mut handles = Vec::new();
-                    let thread_iters: Arc<Vec<AtomicU64>> = Arc::new((0..num_threads).map(|_| AtomicU64::new(0)).collect());
-                    let thread_running = running.clone();
-                    for tid in 0..num_threads {
-                        let thread_iters = thread_iters.clone();
-                        let thread_running = thread_running.clone();
-                        let thread_intensity = intensity;
-                        let thread_safe_stress = responsiveness_mode;
-                        handles.push(thread::spawn(move || {
-                            set_thread_priority_for_mode(!thread_safe_stress); // false = max, true = safe
-                            let mut _local_iters = 0u64;
-                            let mut update_counter = 0u64;
-                            while thread_running.load(Ordering::SeqCst) && Instant::now() < end {
-                                for _ in 0..thread_intensity {
-                                    let mut acc = 1u64;
-                                    for i in 1..1000 {
-                                        acc = acc.wrapping_mul(i ^ tid as u64);
-                                    }
-                                    std::hint::black_box(acc);
-                                    _local_iters += 1;
-                                    update_counter += 1;
-                                    if update_counter >= 100_000 {
-                                        thread_iters[tid].fetch_add(update_counter, Ordering::SeqCst);
-                                        update_counter = 0;
-                                        if thread_safe_stress {
-                                            std::thread::yield_now();
-                                        }
-                                    }
-                                }
-                            }
-                            if update_counter > 0 {
-                                thread_iters[tid].fetch_add(update_counter, Ordering::SeqCst);
-                            }
-                        }));
-                    }
-                    let mut last_report = Instant::now();
-                    let mut last_iters = vec![0u64; num_threads];
-                    let mut sys = System::new_all();
-                    while Instant::now() < end && running.load(Ordering::SeqCst) {
-                        thread::sleep(Duration::from_millis(200));
-                        let elapsed = start.elapsed().as_secs_f64();
-                        let mut total_iters = 0u64;
-                        for tid in 0..num_threads {
-                            let iters = thread_iters[tid].load(Ordering::SeqCst);
-                            let delta = iters - last_iters[tid];
-                            let rate = delta as f64 / (last_report.elapsed().as_secs_f64().max(1e-6));
-                            writeln!(log_file, "{},{},{}", Local::now().to_rfc3339(), tid, rate).ok();
-                            last_iters[tid] = iters;
-                            total_iters += iters;
-                        }
-                        let rate = total_iters as f64 / elapsed;
-                        live_rate.store(rate, Ordering::SeqCst);
-                        // Sample system CPU usage
-                        sys.refresh_cpu();
-                        let avg_cpu_usage = sys.cpus().iter().map(|cpu| cpu.cpu_usage() as f64).sum::<f64>() / sys.cpus().len() as f64;
-                        {
-                            let mut hist = cpu_usage_history.lock().unwrap();
-                            if hist.len() >= cpu_usage_history_len {
-                                hist.pop_front();
-                            }
-                            hist.push_back(avg_cpu_usage);
-                        }
-                        last_report = Instant::now();
-                        ctx.request_repaint();
-                    }
-                    running.store(false, Ordering::SeqCst);
-                    for handle in handles {
-                        let _ = handle.join();
-                    }
-                    let elapsed = start.elapsed().as_secs_f64();
-                    let total_iters: u64 = (0..num_threads).map(|tid| thread_iters[tid].load(Ordering::SeqCst)).sum();
-                    let score = total_iters as f64 / elapsed * intensity as f64;
-                    last_score.store(score, Ordering::SeqCst);
-                    live_rate.store(0.0, Ordering::SeqCst);
-                    ctx.request_repaint();
-                    if dev_mode {
-                        println!("[DEV] CPU stress test thread finished");
-                    }
-                });
+                self.start(ctx, dev_mode);
             }
         }
         let score = self.last_score.load(Ordering::SeqCst);
         let live = self.live_rate.load(Ordering::SeqCst);
         ui.add_space(10.0);
-        ui.label(format!("Score: {:.2} (iterations/sec * intensity)", score));
+        ui.label(format!("Throughput: {:.2} iterations/sec (independent of intensity; use Benchmark Score to compare machines)", score));
         ui.label(format!("Live Iteration Rate: {:.2} iters/sec", live));
+        if let UtilizationTarget::Total(t) | UtilizationTarget::PerCore(t) = *self.utilization_target.lock().unwrap() {
+            let measured = self.cpu_usage_history.lock().unwrap().back().copied().unwrap_or(0.0);
+            ui.label(format!(
+                "Utilization Controller: target {:.0}% | measured {:.1}% | throttle {:.0}%",
+                t,
+                measured,
+                self.controller_output.load(Ordering::SeqCst) * 100.0
+            ));
+        }
         // Draw CPU usage timeline graph
         {
             let hist = self.cpu_usage_history.lock().unwrap();
@@ -226,6 +389,13 @@ impl CpuStress {
                 draw_cpu_stress_graph(ui, &points, egui::Color32::RED, "CPU Usage Timeline", 100.0);
             }
         }
+        if *self.load_profile.lock().unwrap() != LoadProfile::Constant || *self.utilization_target.lock().unwrap() != UtilizationTarget::Off {
+            let hist = self.load_history.lock().unwrap();
+            if !hist.is_empty() {
+                let points: Vec<(f64, f64)> = hist.iter().enumerate().map(|(i, v)| (i as f64, *v)).collect();
+                draw_cpu_stress_graph(ui, &points, egui::Color32::LIGHT_BLUE, "Target Load Timeline", 100.0);
+            }
+        }
         if !*self.responsiveness_mode.lock().unwrap() {
             ui.colored_label(egui::Color32::RED, "WARNING: Max Stress may freeze your system!");
         }
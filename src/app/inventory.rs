@@ -0,0 +1,366 @@
+use eframe::egui;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::Mutex,
+    thread,
+};
+use chrono::Local;
+use once_cell::sync::Lazy;
+use sysinfo::{CpuExt, DiskExt, System, SystemExt};
+use crate::app::topology;
+use crate::app::seed;
+use crate::app::selectable_stress::crypto_stress;
+
+// Result CSVs carry the inventory as "# key: value" lines right after their header. Analyzers
+// skip the header and ignore rows without enough comma-separated fields, so values have their
+// commas replaced to keep these lines out of the data.
+const CSV_PREFIX: &str = "# ";
+
+#[derive(Clone, Debug, Default)]
+pub struct Inventory {
+    pub entries: Vec<(String, String)>,
+}
+
+impl Inventory {
+    fn push(&mut self, key: &str, value: impl Into<String>) {
+        self.entries.push((key.to_string(), value.into()));
+    }
+
+    pub fn to_text(&self) -> String {
+        self.entries.iter().map(|(k, v)| format!("{}: {}\n", k, v)).collect()
+    }
+
+    // The run seed goes last; it isn't hardware, but a log needs it to be replayed (see seed.rs)
+    pub fn write_csv_comments(&self, w: &mut impl Write) {
+        for (key, value) in &self.entries {
+            writeln!(w, "{}{}: {}", CSV_PREFIX, key, value.replace([',', '\n', '\r'], ";")).ok();
+        }
+        writeln!(w, "{}Seed: {}", CSV_PREFIX, seed::current()).ok();
+    }
+}
+
+static CACHE: Lazy<Mutex<Option<Inventory>>> = Lazy::new(|| Mutex::new(None));
+
+// Collected once per session; callers block on the first collection if it's still running
+pub fn get() -> Inventory {
+    let mut cache = CACHE.lock().unwrap();
+    cache.get_or_insert_with(collect).clone()
+}
+
+// Recollects in the background; the previous inventory stays in use until it's done
+pub fn refresh() {
+    thread::spawn(|| {
+        let fresh = collect();
+        *CACHE.lock().unwrap() = Some(fresh);
+    });
+}
+
+// Warms the cache at startup so the first test doesn't wait on WMI or sysfs
+pub fn prefetch() {
+    thread::spawn(|| {
+        get();
+    });
+}
+
+// Inventory stored in a result log, empty for logs written before it existed
+pub fn read_from_csv(path: &Path) -> Vec<(String, String)> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .skip(1)
+        .take_while(|l| l.starts_with(CSV_PREFIX))
+        .filter_map(|l| l[CSV_PREFIX.len()..].split_once(": ").map(|(k, v)| (k.to_string(), v.to_string())))
+        .collect()
+}
+
+fn format_kb(kb: u64) -> String {
+    if kb >= 1024 && kb.is_multiple_of(1024) {
+        format!("{} MB", kb / 1024)
+    } else {
+        format!("{} KB", kb)
+    }
+}
+
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1e9)
+}
+
+#[cfg(target_os = "linux")]
+fn microcode() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo.lines().find(|l| l.starts_with("microcode")).and_then(|l| l.split_once(':')).map(|(_, v)| v.trim().to_string())
+}
+
+#[cfg(windows)]
+fn microcode() -> Option<String> {
+    use winapi::um::winreg::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_BINARY};
+    let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+    let key = wide(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0");
+    let value = wide("Update Revision");
+    let mut data = [0u8; 8];
+    let mut len = data.len() as u32;
+    let status = unsafe {
+        RegGetValueW(HKEY_LOCAL_MACHINE, key.as_ptr(), value.as_ptr(), RRF_RT_REG_BINARY, std::ptr::null_mut(), data.as_mut_ptr() as *mut _, &mut len)
+    };
+    if status != 0 || len < 8 {
+        return None;
+    }
+    // The revision sits in the high dword
+    Some(format!("0x{:x}", u32::from_le_bytes([data[4], data[5], data[6], data[7]])))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn microcode() -> Option<String> {
+    None
+}
+
+// "L1d 48 KB, L1i 32 KB, L2 1280 KB, L3 30 MB", one instance of each cache as seen from CPU 0
+#[cfg(target_os = "linux")]
+fn caches() -> Option<String> {
+    let mut caches = Vec::new();
+    for index in 0.. {
+        let base = format!("/sys/devices/system/cpu/cpu0/cache/index{}", index);
+        let read = |name: &str| std::fs::read_to_string(format!("{}/{}", base, name)).ok().map(|s| s.trim().to_string());
+        let (Some(level), Some(kind), Some(size)) = (read("level"), read("type"), read("size")) else {
+            break;
+        };
+        let suffix = match kind.as_str() {
+            "Data" => "d",
+            "Instruction" => "i",
+            _ => "",
+        };
+        let kb = size.trim_end_matches('K').parse::<u64>().ok()?;
+        caches.push(format!("L{}{} {}", level, suffix, format_kb(kb)));
+    }
+    (!caches.is_empty()).then(|| caches.join(", "))
+}
+
+#[cfg(windows)]
+fn caches() -> Option<String> {
+    use winapi::um::sysinfoapi::GetLogicalProcessorInformation;
+    use winapi::um::winnt::{CacheData, CacheInstruction, RelationCache, SYSTEM_LOGICAL_PROCESSOR_INFORMATION};
+    let mut len: u32 = 0;
+    unsafe {
+        GetLogicalProcessorInformation(std::ptr::null_mut(), &mut len);
+        let count = len as usize / std::mem::size_of::<SYSTEM_LOGICAL_PROCESSOR_INFORMATION>();
+        if count == 0 {
+            return None;
+        }
+        let mut buf: Vec<SYSTEM_LOGICAL_PROCESSOR_INFORMATION> = Vec::with_capacity(count);
+        if GetLogicalProcessorInformation(buf.as_mut_ptr(), &mut len) == 0 {
+            return None;
+        }
+        buf.set_len(count);
+        let mut seen: Vec<(u8, &str, u64)> = Vec::new();
+        for info in buf.iter().filter(|i| i.Relationship == RelationCache) {
+            let cache = info.u.Cache();
+            let suffix = match cache.Type {
+                t if t == CacheData => "d",
+                t if t == CacheInstruction => "i",
+                _ => "",
+            };
+            if !seen.iter().any(|(l, s, _)| *l == cache.Level && *s == suffix) {
+                seen.push((cache.Level, suffix, cache.Size as u64 / 1024));
+            }
+        }
+        seen.sort();
+        (!seen.is_empty()).then(|| seen.iter().map(|(l, s, kb)| format!("L{}{} {}", l, s, format_kb(*kb))).collect::<Vec<_>>().join(", "))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn caches() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn disk_model(device: &str) -> Option<String> {
+    // Partitions resolve to their parent disk through ".."
+    let name = Path::new(device).file_name()?.to_str()?;
+    let block = Path::new("/sys/class/block").join(name);
+    std::fs::read_to_string(block.join("device/model"))
+        .or_else(|_| std::fs::read_to_string(block.join("../device/model")))
+        .ok()
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn disk_model(_device: &str) -> Option<String> {
+    None
+}
+
+// WMI needs COM set up on the calling thread, and the GUI thread already has it in another mode,
+// so these queries get a thread of their own
+#[cfg(windows)]
+fn wmi_entries() -> Vec<(String, String)> {
+    thread::spawn(|| {
+        use serde::Deserialize;
+        #[derive(Deserialize)]
+        #[serde(rename = "Win32_PhysicalMemory")]
+        struct PhysicalMemory {
+            #[serde(rename = "Capacity")]
+            capacity: Option<String>,
+            #[serde(rename = "ConfiguredClockSpeed")]
+            configured_clock_speed: Option<u32>,
+            #[serde(rename = "Speed")]
+            speed: Option<u32>,
+            #[serde(rename = "Manufacturer")]
+            manufacturer: Option<String>,
+            #[serde(rename = "PartNumber")]
+            part_number: Option<String>,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename = "Win32_DiskDrive")]
+        struct DiskDrive {
+            #[serde(rename = "Model")]
+            model: Option<String>,
+            #[serde(rename = "Size")]
+            size: Option<String>,
+            #[serde(rename = "InterfaceType")]
+            interface_type: Option<String>,
+        }
+        let mut entries = Vec::new();
+        let Some(con) = wmi::COMLibrary::new().ok().and_then(|com| wmi::WMIConnection::new(com).ok()) else {
+            return entries;
+        };
+        if let Ok(modules) = con.query::<PhysicalMemory>() {
+            for (i, m) in modules.iter().enumerate() {
+                let size = m.capacity.as_deref().and_then(|c| c.parse::<u64>().ok()).map(format_gb).unwrap_or_else(|| "N/A".to_string());
+                let speed = m.configured_clock_speed.or(m.speed).map(|s| format!("{} MT/s", s)).unwrap_or_else(|| "N/A".to_string());
+                let part = [m.manufacturer.as_deref(), m.part_number.as_deref()]
+                    .iter()
+                    .flatten()
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                entries.push((format!("RAM module {}", i + 1), format!("{} @ {} {}", size, speed, part).trim().to_string()));
+            }
+        }
+        if let Ok(drives) = con.query::<DiskDrive>() {
+            for (i, d) in drives.iter().enumerate() {
+                let size = d.size.as_deref().and_then(|s| s.parse::<u64>().ok()).map(format_gb).unwrap_or_else(|| "N/A".to_string());
+                entries.push((
+                    format!("Physical disk {}", i + 1),
+                    format!("{} ({}, {})", d.model.as_deref().unwrap_or("Unknown"), d.interface_type.as_deref().unwrap_or("N/A"), size),
+                ));
+            }
+        }
+        entries
+    })
+    .join()
+    .unwrap_or_default()
+}
+
+#[cfg(not(windows))]
+fn wmi_entries() -> Vec<(String, String)> {
+    Vec::new()
+}
+
+pub fn collect() -> Inventory {
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    sys.refresh_memory();
+    sys.refresh_disks_list();
+    let mut inv = Inventory::default();
+    inv.push("Collected", Local::now().to_rfc3339());
+    inv.push("Host", sys.host_name().unwrap_or_else(|| "Unknown".to_string()));
+    let cpu = sys.global_cpu_info();
+    let brand = sys.cpus().first().map(|c| c.brand().trim().to_string()).filter(|b| !b.is_empty()).unwrap_or_else(|| cpu.brand().to_string());
+    inv.push("CPU", brand);
+    inv.push("CPU vendor", sys.cpus().first().map(|c| c.vendor_id().to_string()).unwrap_or_default());
+    inv.push("CPU microcode", microcode().unwrap_or_else(|| "N/A".to_string()));
+    let topology = topology::get();
+    inv.push("Cores / threads", format!(
+        "{} physical / {} logical ({} package(s), {} NUMA node(s))",
+        sys.physical_core_count().unwrap_or(topology.physical_cores()),
+        sys.cpus().len(),
+        topology.packages(),
+        topology.numa_nodes().len()
+    ));
+    inv.push("CPU caches", caches().unwrap_or_else(|| "N/A".to_string()));
+    inv.push("Crypto extensions", crypto_stress::acceleration_summary());
+    inv.push("RAM", format_gb(sys.total_memory()));
+    let wmi = wmi_entries();
+    if !wmi.iter().any(|(k, _)| k.starts_with("RAM module")) {
+        inv.push("RAM speed", "N/A");
+    }
+    inv.entries.extend(wmi.iter().filter(|(k, _)| k.starts_with("RAM module")).cloned());
+    for (i, disk) in sys.disks().iter().enumerate() {
+        let name = disk.name().to_string_lossy().to_string();
+        let model = disk_model(&name).map(|m| format!(" {},", m)).unwrap_or_default();
+        inv.push(&format!("Volume {}", i + 1), format!(
+            "{} on {}:{} {:?}, {}, {}",
+            name,
+            disk.mount_point().display(),
+            model,
+            disk.kind(),
+            String::from_utf8_lossy(disk.file_system()),
+            format_gb(disk.total_space())
+        ));
+    }
+    inv.entries.extend(wmi.into_iter().filter(|(k, _)| k.starts_with("Physical disk")));
+    inv.push("OS", sys.long_os_version().unwrap_or_else(|| "Unknown".to_string()));
+    inv.push("Kernel", sys.kernel_version().unwrap_or_else(|| "Unknown".to_string()));
+    inv.push("Stressor", format!(
+        "{} ({} build, {}-{})",
+        env!("CARGO_PKG_VERSION"),
+        if cfg!(debug_assertions) { "debug" } else { "release" },
+        std::env::consts::ARCH,
+        std::env::consts::OS
+    ));
+    inv
+}
+
+// Lines for the System Info tab and the analyzer, which shows the inventory stored in a log
+pub fn ui_entries(ui: &mut egui::Ui, id: &str, entries: &[(String, String)]) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        for (key, value) in entries {
+            ui.label(egui::RichText::new(key).strong());
+            ui.label(value);
+            ui.end_row();
+        }
+    });
+}
+
+pub fn ui_inventory(ui: &mut egui::Ui, dev_mode: bool, status: &mut Option<String>) {
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        egui::CollapsingHeader::new(egui::RichText::new("🧾 Inventory").strong()).id_salt("inventory").show(ui, |ui| {
+            // Never wait on a collection from the GUI thread
+            let Some(inventory) = CACHE.try_lock().ok().and_then(|c| c.clone()) else {
+                ui.label("Collecting...");
+                return;
+            };
+            ui.horizontal(|ui| {
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(inventory.to_text());
+                    *status = Some("Copied to clipboard".to_string());
+                }
+                if ui.button("Export").clicked() {
+                    let log_dir = crate::app::log_dir(dev_mode);
+                    let _ = std::fs::create_dir_all(&log_dir);
+                    let hash = seed::file_hash();
+                    let path = log_dir.join(format!("inventory_{}_{}.txt", hash, Local::now().format("%Y%m%d_%H%M%S")));
+                    *status = Some(match std::fs::write(&path, inventory.to_text()) {
+                        Ok(_) => format!("Exported to {}", path.display()),
+                        Err(e) => format!("Export failed: {}", e),
+                    });
+                }
+                if ui.button("Refresh").clicked() {
+                    refresh();
+                    *status = None;
+                }
+                if let Some(status) = status {
+                    ui.label(status.as_str());
+                }
+            });
+            ui_entries(ui, "inventory_table", &inventory.entries);
+        });
+    });
+}
@@ -0,0 +1,255 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+use sysinfo::{CpuExt, System, SystemExt};
+use crate::app::cpu::sample_cpu_temperature;
+use crate::app::seed;
+
+// Each journal sits in log/journal/ and is a small CSV of records:
+//   start,<time>,<kind>
+//   csv,<path of the run's result log>
+//   checkpoint,<time>,<elapsed_secs>,<cpu_temp_c>,<cpu_mhz>,<cpu_usage_percent>
+//   end,<time>,<elapsed_secs>,<reason>
+//   ack,<time>                (crash notice dismissed by the user)
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(2);
+// A journal whose last record is newer than this may belong to a run still going in another instance
+const STALE_AFTER_SECS: i64 = 15;
+
+// Journals of runs still going in this process, so closing the app can end them cleanly
+static ACTIVE: Lazy<Mutex<HashMap<PathBuf, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn append_record(path: &Path, record: &str) {
+    if let Ok(mut f) = OpenOptions::new().append(true).open(path) {
+        writeln!(f, "{}", record).ok();
+        f.sync_all().ok();
+    }
+}
+
+fn end_record(elapsed_secs: f64, reason: &str) -> String {
+    format!("end,{},{:.1},{}", Local::now().to_rfc3339(), elapsed_secs, reason)
+}
+
+pub fn journal_dir(log_dir: &Path) -> PathBuf {
+    log_dir.join("journal")
+}
+
+pub struct RunJournal {
+    path: PathBuf,
+    start: Instant,
+    csv: Arc<Mutex<Option<PathBuf>>>,
+    stop: Arc<AtomicBool>,
+    checkpointer: Option<thread::JoinHandle<()>>,
+}
+
+impl RunJournal {
+    // Starts journaling a run; `csv` is the result log if it's already known. Checkpoints are written
+    // from a background thread so stressor loops don't need to call in.
+    pub fn begin(dev_mode: bool, kind: &str, csv: Option<&Path>) -> Option<Self> {
+        let log_dir = crate::app::log_dir(dev_mode);
+        let dir = journal_dir(&log_dir);
+        fs::create_dir_all(&dir).ok()?;
+        let hash = seed::file_hash();
+        let path = dir.join(format!("{}_{}_{}.journal", kind, hash, Local::now().format("%Y%m%d_%H%M%S")));
+        let mut file = File::create(&path).ok()?;
+        writeln!(file, "start,{},{}", Local::now().to_rfc3339(), kind).ok();
+        if let Some(csv) = csv {
+            writeln!(file, "csv,{}", csv.display()).ok();
+        }
+        file.sync_all().ok();
+        let start = Instant::now();
+        ACTIVE.lock().unwrap().insert(path.clone(), start);
+        let csv = Arc::new(Mutex::new(csv.map(|p| p.to_path_buf())));
+        let stop = Arc::new(AtomicBool::new(false));
+        let checkpointer = {
+            let (path, csv, stop) = (path.clone(), csv.clone(), stop.clone());
+            thread::spawn(move || {
+                let mut sys = System::new();
+                loop {
+                    let tick = Instant::now();
+                    while !stop.load(Ordering::SeqCst) && tick.elapsed() < CHECKPOINT_INTERVAL {
+                        thread::sleep(Duration::from_millis(100));
+                    }
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    sys.refresh_cpu();
+                    let usage = sys.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>() / sys.cpus().len().max(1) as f32;
+                    let mhz = sys.cpus().iter().map(|c| c.frequency()).max().unwrap_or(0);
+                    let temp = sample_cpu_temperature(&mut sys);
+                    // Push the result log to disk first so it's at least as complete as the journal
+                    if let Some(csv) = &*csv.lock().unwrap()
+                        && let Ok(f) = OpenOptions::new().append(true).open(csv) {
+                        f.sync_data().ok();
+                    }
+                    append_record(&path, &format!(
+                        "checkpoint,{},{:.1},{},{},{:.1}",
+                        Local::now().to_rfc3339(),
+                        start.elapsed().as_secs_f64(),
+                        temp.map(|t| format!("{:.1}", t)).unwrap_or_default(),
+                        mhz,
+                        usage
+                    ));
+                }
+            })
+        };
+        Some(Self { path, start, csv, stop, checkpointer: Some(checkpointer) })
+    }
+
+    // For tests that only write their result log when they finish
+    pub fn set_csv(&self, csv: &Path) {
+        append_record(&self.path, &format!("csv,{}", csv.display()));
+        *self.csv.lock().unwrap() = Some(csv.to_path_buf());
+    }
+
+    fn close(&mut self, reason: &str) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.checkpointer.take() {
+            let _ = handle.join();
+        }
+        if ACTIVE.lock().unwrap().remove(&self.path).is_some() {
+            append_record(&self.path, &end_record(self.start.elapsed().as_secs_f64(), reason));
+        }
+    }
+
+    pub fn finish(mut self) {
+        self.close("completed");
+    }
+}
+
+impl Drop for RunJournal {
+    // Reached when a test thread panics or returns early without calling finish()
+    fn drop(&mut self) {
+        self.close("aborted");
+    }
+}
+
+// Ends every journal of this process. Test threads are killed without unwinding when the app
+// exits, so without this a normal close would look like a crash on the next launch.
+pub fn close_active(reason: &str) {
+    let active: Vec<(PathBuf, Instant)> = ACTIVE.lock().unwrap().drain().collect();
+    for (path, start) in active {
+        append_record(&path, &end_record(start.elapsed().as_secs_f64(), reason));
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CrashedRun {
+    pub journal: PathBuf,
+    pub kind: String,
+    pub started: String,
+    pub csv: Option<PathBuf>,
+    // Time of the last checkpoint, i.e. the latest point the machine was known to be alive
+    pub crashed_at_secs: f64,
+    pub last_seen: Option<String>,
+    pub cpu_temp_c: Option<f32>,
+    pub cpu_mhz: Option<u64>,
+    pub cpu_usage_percent: Option<f32>,
+    pub acknowledged: bool,
+}
+
+impl CrashedRun {
+    pub fn label(&self) -> String {
+        format!("System crashed/hung at T+{:.0} s", self.crashed_at_secs)
+    }
+
+    pub fn readings(&self) -> String {
+        let temp = self.cpu_temp_c.map(|t| format!("{:.1} °C", t)).unwrap_or_else(|| "N/A".to_string());
+        let mhz = self.cpu_mhz.map(|m| format!("{} MHz", m)).unwrap_or_else(|| "N/A".to_string());
+        let usage = self.cpu_usage_percent.map(|u| format!("{:.0}%", u)).unwrap_or_else(|| "N/A".to_string());
+        format!("last readings: CPU {}, {}, {} load", temp, mhz, usage)
+    }
+
+    // Whether this crashed run produced `log` (a file name in the log folder)
+    pub fn matches_log(&self, log: &str) -> bool {
+        self.csv.as_ref().and_then(|p| p.file_name()).and_then(|n| n.to_str()) == Some(log)
+    }
+}
+
+fn read_journal(path: &Path) -> Option<CrashedRun> {
+    let reader = BufReader::new(File::open(path).ok()?);
+    let mut run = CrashedRun {
+        journal: path.to_path_buf(),
+        kind: String::new(),
+        started: String::new(),
+        csv: None,
+        crashed_at_secs: 0.0,
+        last_seen: None,
+        cpu_temp_c: None,
+        cpu_mhz: None,
+        cpu_usage_percent: None,
+        acknowledged: false,
+    };
+    let mut last_time = None;
+    for line in reader.lines().map_while(Result::ok) {
+        let parts: Vec<&str> = line.split(',').collect();
+        match parts.as_slice() {
+            ["start", time, kind] => {
+                run.started = time.to_string();
+                run.kind = kind.to_string();
+                last_time = Some(time.to_string());
+            }
+            // Paths may contain commas, so take the rest of the line
+            ["csv", ..] => run.csv = line.strip_prefix("csv,").map(PathBuf::from),
+            ["checkpoint", time, elapsed, temp, mhz, usage] => {
+                run.crashed_at_secs = elapsed.parse().unwrap_or(run.crashed_at_secs);
+                run.last_seen = Some(time.to_string());
+                run.cpu_temp_c = temp.parse().ok();
+                run.cpu_mhz = mhz.parse().ok();
+                run.cpu_usage_percent = usage.parse().ok();
+                last_time = Some(time.to_string());
+            }
+            ["end", ..] => return None,
+            ["ack", ..] => run.acknowledged = true,
+            _ => {}
+        }
+    }
+    // A run still checkpointing from another instance isn't a crash (yet)
+    let last = DateTime::parse_from_rfc3339(&last_time?).ok()?;
+    if (Local::now().fixed_offset() - last).num_seconds() < STALE_AFTER_SECS {
+        return None;
+    }
+    if run.kind.is_empty() {
+        return None;
+    }
+    Some(run)
+}
+
+// Runs whose journal has no clean end marker, newest first
+pub fn find_crashed(log_dir: &Path) -> Vec<CrashedRun> {
+    let mut runs: Vec<CrashedRun> = fs::read_dir(journal_dir(log_dir))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("journal"))
+                .filter(|p| !ACTIVE.lock().unwrap().contains_key(p))
+                .filter_map(|p| read_journal(&p))
+                .collect()
+        })
+        .unwrap_or_default();
+    runs.sort_by(|a, b| b.started.cmp(&a.started));
+    runs
+}
+
+// Hides the startup notice for these runs; they stay marked in the analyzer
+pub fn acknowledge(runs: &mut [CrashedRun]) {
+    for run in runs.iter_mut().filter(|r| !r.acknowledged) {
+        append_record(&run.journal, &format!("ack,{}", Local::now().to_rfc3339()));
+        run.acknowledged = true;
+    }
+}
+
+// Journal times are RFC 3339; shown like the analyzer's log dates
+pub fn format_time(rfc3339: &str) -> String {
+    DateTime::parse_from_rfc3339(rfc3339)
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| rfc3339.to_string())
+}
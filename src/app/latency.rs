@@ -0,0 +1,100 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+// 10 log-spaced buckets per decade from 1 µs up to ~100 s
+const BUCKETS_PER_DECADE: f64 = 10.0;
+pub const BUCKET_COUNT: usize = 80;
+
+// Lock-free log-bucketed histogram so millions of operations don't need to be kept individually
+pub struct LatencyHistogram {
+    buckets: Vec<AtomicU64>,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self { buckets: (0..BUCKET_COUNT).map(|_| AtomicU64::new(0)).collect() }
+    }
+
+    pub fn record(&self, latency: Duration) {
+        let us = (latency.as_secs_f64() * 1e6).max(1.0);
+        let bucket = ((us.log10() * BUCKETS_PER_DECADE).ceil() as usize).min(BUCKET_COUNT - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> Vec<u64> {
+        self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect()
+    }
+}
+
+pub fn bucket_upper_us(bucket: usize) -> f64 {
+    10f64.powf(bucket as f64 / BUCKETS_PER_DECADE)
+}
+
+// Upper bound of the bucket containing the p-th percentile
+pub fn histogram_percentile(counts: &[u64], p: f64) -> Option<f64> {
+    let total: u64 = counts.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let rank = ((total as f64 * p).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (bucket, &count) in counts.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            return Some(bucket_upper_us(bucket));
+        }
+    }
+    None
+}
+
+// Upper bound of the highest non-empty bucket
+pub fn histogram_max(counts: &[u64]) -> Option<f64> {
+    counts.iter().rposition(|&c| c > 0).map(bucket_upper_us)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_buckets_by_decade() {
+        let histogram = LatencyHistogram::new();
+        histogram.record(Duration::from_nanos(10));
+        histogram.record(Duration::from_micros(10));
+        histogram.record(Duration::from_millis(1));
+        histogram.record(Duration::from_secs(10_000));
+        let counts = histogram.snapshot();
+        assert_eq!(counts.len(), BUCKET_COUNT);
+        // Sub-microsecond latencies share the first bucket and anything past the range the last
+        assert_eq!(counts[0], 1);
+        assert_eq!(counts[10], 1);
+        assert_eq!(counts[30], 1);
+        assert_eq!(counts[BUCKET_COUNT - 1], 1);
+        assert_eq!(counts.iter().sum::<u64>(), 4);
+    }
+
+    #[test]
+    fn percentiles_report_the_bucket_upper_bound() {
+        let mut counts = vec![0u64; BUCKET_COUNT];
+        counts[10] = 90;
+        counts[20] = 9;
+        counts[30] = 1;
+        let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9 * b;
+        assert!(close(histogram_percentile(&counts, 0.5), 10.0));
+        assert!(close(histogram_percentile(&counts, 0.9), 10.0));
+        assert!(close(histogram_percentile(&counts, 0.95), 100.0));
+        assert!(close(histogram_percentile(&counts, 0.99), 100.0));
+        assert!(close(histogram_percentile(&counts, 1.0), 1000.0));
+        assert!(close(histogram_percentile(&counts, 0.0), 10.0));
+        assert!(close(histogram_max(&counts), 1000.0));
+    }
+
+    #[test]
+    fn empty_histogram_has_no_percentiles() {
+        let counts = vec![0u64; BUCKET_COUNT];
+        assert_eq!(histogram_percentile(&counts, 0.5), None);
+        assert_eq!(histogram_max(&counts), None);
+    }
+}
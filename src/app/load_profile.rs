@@ -0,0 +1,296 @@
+use eframe::egui;
+use std::{
+    sync::atomic::Ordering,
+    thread,
+    time::{Duration, Instant},
+};
+use atomic_float::AtomicF64;
+
+// Continuous load levels are produced by on/off modulation over this window
+const PWM_WINDOW_SECS: f64 = 0.1;
+// Fraction of the utilization error corrected per controller sample
+const CONTROLLER_GAIN: f64 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LoadProfile {
+    Constant,
+    DutyCycle { period_ms: u32, duty_percent: u32 },
+    Ramp { ramp_secs: u32 },
+    SquareWave { frequency_hz: f64 },
+    RandomBursts { slot_ms: u32 },
+}
+
+impl LoadProfile {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoadProfile::Constant => "Constant (Full Load)",
+            LoadProfile::DutyCycle { .. } => "Duty Cycle",
+            LoadProfile::Ramp { .. } => "Linear Ramp 0-100%",
+            LoadProfile::SquareWave { .. } => "Square Wave Bursts",
+            LoadProfile::RandomBursts { .. } => "Random Bursts",
+        }
+    }
+
+    // Short form used in log file names
+    pub fn tag(&self) -> String {
+        match self {
+            LoadProfile::Constant => "const".to_string(),
+            LoadProfile::DutyCycle { period_ms, duty_percent } => format!("duty{}of{}ms", duty_percent, period_ms),
+            LoadProfile::Ramp { ramp_secs } => format!("ramp{}s", ramp_secs),
+            LoadProfile::SquareWave { frequency_hz } => format!("sq{}hz", frequency_hz),
+            LoadProfile::RandomBursts { slot_ms } => format!("rand{}ms", slot_ms),
+        }
+    }
+
+    // Target load in 0.0..=1.0 at `elapsed` seconds into the run
+    pub fn level(&self, elapsed: f64) -> f64 {
+        match *self {
+            LoadProfile::Constant => 1.0,
+            LoadProfile::DutyCycle { period_ms, duty_percent } => {
+                let period = period_ms.max(1) as f64 / 1000.0;
+                let on = period * duty_percent.min(100) as f64 / 100.0;
+                if elapsed % period < on { 1.0 } else { 0.0 }
+            }
+            LoadProfile::Ramp { ramp_secs } => (elapsed / ramp_secs.max(1) as f64).min(1.0),
+            LoadProfile::SquareWave { frequency_hz } => {
+                if frequency_hz <= 0.0 || (elapsed * frequency_hz).fract() < 0.5 { 1.0 } else { 0.0 }
+            }
+            LoadProfile::RandomBursts { slot_ms } => {
+                // Hash the slot index so every worker agrees on the pattern without sharing an RNG
+                let slot = (elapsed * 1000.0 / slot_ms.max(1) as f64) as u64;
+                let mut z = slot.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                if (z ^ (z >> 31)) & 1 == 1 { 1.0 } else { 0.0 }
+            }
+        }
+    }
+}
+
+// Every worker of a run gets a gate with the same profile and start so they follow one waveform
+pub struct LoadGate {
+    profile: LoadProfile,
+    start: Instant,
+    scale: AtomicF64,
+}
+
+impl LoadGate {
+    pub fn new(profile: LoadProfile) -> Self {
+        Self::starting_at(profile, Instant::now())
+    }
+
+    pub fn starting_at(profile: LoadProfile, start: Instant) -> Self {
+        Self {
+            profile,
+            start,
+            scale: AtomicF64::new(1.0),
+        }
+    }
+
+    // Multiplier on the waveform, used by the utilization controller to throttle workers
+    pub fn set_scale(&self, scale: f64) {
+        self.scale.store(scale.clamp(0.0, 1.0), Ordering::Relaxed);
+    }
+
+    pub fn level(&self) -> f64 {
+        let level = self.profile.level(self.start.elapsed().as_secs_f64());
+        (level * self.scale.load(Ordering::Relaxed)).clamp(0.0, 1.0)
+    }
+
+    fn is_full_load(&self) -> bool {
+        self.profile == LoadProfile::Constant && self.scale.load(Ordering::Relaxed) >= 1.0
+    }
+
+    // Blocks while the waveform is in an "off" phase. `keep_going` lets the caller abort the wait.
+    // Returns how long it blocked, so callers measuring throughput can leave the idle time out.
+    pub fn pace(&self, keep_going: impl Fn() -> bool) -> Duration {
+        if self.is_full_load() {
+            return Duration::ZERO;
+        }
+        let start = Instant::now();
+        while keep_going() {
+            let level = self.level();
+            let phase = self.start.elapsed().as_secs_f64() % PWM_WINDOW_SECS;
+            if level >= 1.0 || phase < level * PWM_WINDOW_SECS {
+                break;
+            }
+            let remaining = PWM_WINDOW_SECS - phase;
+            thread::sleep(Duration::from_secs_f64(remaining.min(0.001)));
+        }
+        start.elapsed()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum UtilizationTarget {
+    Off,
+    Total(f64),
+    PerCore(f64),
+}
+
+impl UtilizationTarget {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UtilizationTarget::Off => "Off",
+            UtilizationTarget::Total(_) => "Total CPU",
+            UtilizationTarget::PerCore(_) => "Per Core",
+        }
+    }
+
+    pub fn tag(&self) -> Option<String> {
+        match self {
+            UtilizationTarget::Off => None,
+            UtilizationTarget::Total(t) => Some(format!("util{}", t)),
+            UtilizationTarget::PerCore(t) => Some(format!("utilcore{}", t)),
+        }
+    }
+}
+
+// Integral controller: nudges the gate scale until measured utilization settles on the target
+pub struct UtilizationController {
+    target: f64,
+    output: f64,
+}
+
+impl UtilizationController {
+    pub fn new(target_percent: f64) -> Self {
+        Self {
+            target: target_percent,
+            output: (target_percent / 100.0).clamp(0.05, 1.0),
+        }
+    }
+
+    pub fn output(&self) -> f64 {
+        self.output
+    }
+
+    pub fn update(&mut self, measured_percent: f64) -> f64 {
+        let error = (self.target - measured_percent) / 100.0;
+        self.output = (self.output + CONTROLLER_GAIN * error).clamp(0.0, 1.0);
+        self.output
+    }
+}
+
+pub fn utilization_target_ui(ui: &mut egui::Ui, id_salt: &str, target: &mut UtilizationTarget) {
+    ui.horizontal(|ui| {
+        ui.label("Hold Utilization:");
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(target.label())
+            .show_ui(ui, |ui| {
+                let percent = match *target {
+                    UtilizationTarget::Total(t) | UtilizationTarget::PerCore(t) => t,
+                    UtilizationTarget::Off => 60.0,
+                };
+                for option in [UtilizationTarget::Off, UtilizationTarget::Total(percent), UtilizationTarget::PerCore(percent)] {
+                    let selected = std::mem::discriminant(target) == std::mem::discriminant(&option);
+                    if ui.selectable_label(selected, option.label()).clicked() && !selected {
+                        *target = option;
+                    }
+                }
+            });
+        match target {
+            UtilizationTarget::Total(t) | UtilizationTarget::PerCore(t) => {
+                ui.label("Target (%):");
+                ui.add(egui::DragValue::new(t).range(1.0..=100.0));
+            }
+            UtilizationTarget::Off => {}
+        }
+    });
+}
+
+pub fn load_profile_ui(ui: &mut egui::Ui, id_salt: &str, profile: &mut LoadProfile) {
+    ui.horizontal(|ui| {
+        ui.label("Load Profile:");
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(profile.label())
+            .show_ui(ui, |ui| {
+                let options = [
+                    LoadProfile::Constant,
+                    LoadProfile::DutyCycle { period_ms: 1000, duty_percent: 50 },
+                    LoadProfile::Ramp { ramp_secs: 10 },
+                    LoadProfile::SquareWave { frequency_hz: 1.0 },
+                    LoadProfile::RandomBursts { slot_ms: 250 },
+                ];
+                for option in options {
+                    let selected = std::mem::discriminant(profile) == std::mem::discriminant(&option);
+                    if ui.selectable_label(selected, option.label()).clicked() && !selected {
+                        *profile = option;
+                    }
+                }
+            });
+        match profile {
+            LoadProfile::Constant => {}
+            LoadProfile::DutyCycle { period_ms, duty_percent } => {
+                ui.label("Period (ms):");
+                ui.add(egui::DragValue::new(period_ms).range(10..=60000));
+                ui.label("Duty (%):");
+                ui.add(egui::DragValue::new(duty_percent).range(0..=100));
+            }
+            LoadProfile::Ramp { ramp_secs } => {
+                ui.label("Ramp (s):");
+                ui.add(egui::DragValue::new(ramp_secs).range(1..=3600));
+            }
+            LoadProfile::SquareWave { frequency_hz } => {
+                ui.label("Frequency (Hz):");
+                ui.add(egui::DragValue::new(frequency_hz).range(0.01..=50.0).speed(0.05));
+            }
+            LoadProfile::RandomBursts { slot_ms } => {
+                ui.label("Burst Slot (ms):");
+                ui.add(egui::DragValue::new(slot_ms).range(10..=10000));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_is_always_full_load() {
+        for t in [0.0, 0.5, 1e6] {
+            assert_eq!(LoadProfile::Constant.level(t), 1.0);
+        }
+    }
+
+    #[test]
+    fn duty_cycle_is_on_for_its_share_of_each_period() {
+        let profile = LoadProfile::DutyCycle { period_ms: 1000, duty_percent: 25 };
+        assert_eq!(profile.level(0.0), 1.0);
+        assert_eq!(profile.level(0.2), 1.0);
+        assert_eq!(profile.level(0.3), 0.0);
+        assert_eq!(profile.level(1.1), 1.0);
+        assert_eq!(profile.level(1.9), 0.0);
+        // Out-of-range settings are clamped instead of dividing by zero
+        assert_eq!(LoadProfile::DutyCycle { period_ms: 0, duty_percent: 200 }.level(5.0), 1.0);
+    }
+
+    #[test]
+    fn ramp_rises_linearly_then_holds() {
+        let profile = LoadProfile::Ramp { ramp_secs: 10 };
+        assert_eq!(profile.level(0.0), 0.0);
+        assert!((profile.level(2.5) - 0.25).abs() < 1e-12);
+        assert_eq!(profile.level(10.0), 1.0);
+        assert_eq!(profile.level(60.0), 1.0);
+    }
+
+    #[test]
+    fn square_wave_alternates_every_half_period() {
+        let profile = LoadProfile::SquareWave { frequency_hz: 2.0 };
+        assert_eq!(profile.level(0.1), 1.0);
+        assert_eq!(profile.level(0.3), 0.0);
+        assert_eq!(profile.level(0.6), 1.0);
+        assert_eq!(LoadProfile::SquareWave { frequency_hz: 0.0 }.level(0.3), 1.0);
+    }
+
+    #[test]
+    fn random_bursts_are_on_or_off_and_fixed_within_a_slot() {
+        let profile = LoadProfile::RandomBursts { slot_ms: 100 };
+        let levels: Vec<f64> = (0..200).map(|slot| profile.level(slot as f64 * 0.1 + 0.05)).collect();
+        assert!(levels.iter().all(|&l| l == 0.0 || l == 1.0));
+        assert!(levels.contains(&0.0) && levels.contains(&1.0));
+        for slot in 0..200 {
+            assert_eq!(profile.level(slot as f64 * 0.1 + 0.01), profile.level(slot as f64 * 0.1 + 0.09));
+        }
+    }
+}
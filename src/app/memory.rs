@@ -2,6 +2,7 @@ use eframe::egui::{self, Color32};
 use sysinfo::SystemExt;
 use crate::app::SystemMonitorApp;
 use crate::app::config::Config;
+use crate::app::telemetry;
 // use crate::app::cpu::History; // Only if needed
 
 pub fn update_memory_data(app: &mut SystemMonitorApp) {
@@ -14,6 +15,7 @@ pub fn update_memory_data(app: &mut SystemMonitorApp) {
     }
     let percent = (used / total) * 100.0;
     app.memory_history.push((app.time_counter, percent));
+    telemetry::emit("system", "memory_used_percent", percent, None);
 }
 
 pub fn ui_memory_info(app: &mut SystemMonitorApp, ui: &mut egui::Ui, config: &Config) {
@@ -0,0 +1,417 @@
+use eframe::egui;
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use sysinfo::{Pid, PidExt, System, SystemExt};
+use crate::app::latency::{histogram_max, histogram_percentile, LatencyHistogram, BUCKET_COUNT};
+use crate::app::telemetry;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::seed;
+
+// Scratch directories are named <prefix><pid>_<hash> so stale ones from a killed run can be recognised
+const SCRATCH_PREFIX: &str = "stressor_metadata_";
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MetadataOp {
+    Create,
+    Stat,
+    Rename,
+    Delete,
+}
+
+impl MetadataOp {
+    pub const ALL: [MetadataOp; 4] = [MetadataOp::Create, MetadataOp::Stat, MetadataOp::Rename, MetadataOp::Delete];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MetadataOp::Create => "create",
+            MetadataOp::Stat => "stat",
+            MetadataOp::Rename => "rename",
+            MetadataOp::Delete => "delete",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.name() == name)
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MetadataOpSummary {
+    pub op: MetadataOp,
+    pub ops_per_sec: f64,
+    pub p50_us: Option<f64>,
+    pub p95_us: Option<f64>,
+    pub p99_us: Option<f64>,
+    pub max_us: Option<f64>,
+}
+
+impl MetadataOpSummary {
+    fn from_counts(op: MetadataOp, ops_per_sec: f64, counts: &[u64]) -> Self {
+        Self {
+            op,
+            ops_per_sec,
+            p50_us: histogram_percentile(counts, 0.50),
+            p95_us: histogram_percentile(counts, 0.95),
+            p99_us: histogram_percentile(counts, 0.99),
+            max_us: histogram_max(counts),
+        }
+    }
+}
+
+fn write_row(log_file: &mut File, kind: &str, s: &MetadataOpSummary) {
+    let fmt = |v: Option<f64>| v.map(|v| v.to_string()).unwrap_or_default();
+    writeln!(
+        log_file,
+        "{},{},{},{},{},{},{},{}",
+        Local::now().to_rfc3339(),
+        kind,
+        s.op.name(),
+        s.ops_per_sec,
+        fmt(s.p50_us),
+        fmt(s.p95_us),
+        fmt(s.p99_us),
+        fmt(s.max_us)
+    ).ok();
+}
+
+// Removes the run's scratch tree when dropped, so Stop, errors and panics in the run thread all clean up
+struct ScratchGuard(PathBuf);
+
+impl Drop for ScratchGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+// Deletes scratch trees left behind by runs whose process no longer exists (e.g. the app was killed mid-test)
+fn remove_stale_scratch(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut sys = System::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(pid) = name.strip_prefix(SCRATCH_PREFIX).and_then(|rest| rest.split('_').next()).and_then(|p| p.parse::<u32>().ok()) else {
+            continue;
+        };
+        if !sys.refresh_process(Pid::from_u32(pid)) {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+struct OpCounters {
+    counts: [AtomicU64; 4],
+    histograms: [LatencyHistogram; 4],
+    errors: AtomicU64,
+}
+
+impl OpCounters {
+    fn new() -> Self {
+        Self {
+            counts: Default::default(),
+            histograms: [LatencyHistogram::new(), LatencyHistogram::new(), LatencyHistogram::new(), LatencyHistogram::new()],
+            errors: AtomicU64::new(0),
+        }
+    }
+
+    fn time<T>(&self, op: MetadataOp, f: impl FnOnce() -> std::io::Result<T>) {
+        let start = Instant::now();
+        match f() {
+            Ok(_) => {
+                self.histograms[op.index()].record(start.elapsed());
+                self.counts[op.index()].fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+// One storm round: mkdir, create N small files, stat them, rename them, delete them, rmdir
+fn metadata_worker(root: PathBuf, files_per_dir: usize, counters: &OpCounters, running: &AtomicBool, end: Instant) {
+    let payload = [0xA5u8; 64];
+    let mut round = 0u64;
+    while running.load(Ordering::SeqCst) && Instant::now() < end {
+        let dir = root.join(format!("d{}", round));
+        round += 1;
+        counters.time(MetadataOp::Create, || std::fs::create_dir(&dir));
+        for i in 0..files_per_dir {
+            counters.time(MetadataOp::Create, || File::create(dir.join(format!("f{}", i)))?.write_all(&payload));
+        }
+        for i in 0..files_per_dir {
+            counters.time(MetadataOp::Stat, || std::fs::metadata(dir.join(format!("f{}", i))));
+        }
+        for i in 0..files_per_dir {
+            counters.time(MetadataOp::Rename, || std::fs::rename(dir.join(format!("f{}", i)), dir.join(format!("r{}", i))));
+        }
+        for i in 0..files_per_dir {
+            counters.time(MetadataOp::Delete, || std::fs::remove_file(dir.join(format!("r{}", i))));
+        }
+        counters.time(MetadataOp::Delete, || std::fs::remove_dir(&dir));
+    }
+}
+
+pub struct MetadataStress {
+    running: Arc<AtomicBool>,
+    target_dir: Arc<Mutex<String>>,
+    duration_secs: Arc<Mutex<u32>>,
+    threads: Arc<Mutex<u32>>,
+    files_per_dir: Arc<Mutex<u32>>,
+    live: Arc<Mutex<Vec<MetadataOpSummary>>>,
+    summaries: Arc<Mutex<Vec<MetadataOpSummary>>>,
+    errors: Arc<AtomicU64>,
+    scratch_dir: Arc<Mutex<Option<PathBuf>>>,
+    log_path: Arc<Mutex<Option<PathBuf>>>,
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl MetadataStress {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            target_dir: Arc::new(Mutex::new(config.metadata_dir.clone())),
+            duration_secs: Arc::new(Mutex::new(config.metadata_duration_secs)),
+            threads: Arc::new(Mutex::new(config.metadata_threads as u32)),
+            files_per_dir: Arc::new(Mutex::new(config.metadata_files_per_dir)),
+            live: Arc::new(Mutex::new(Vec::new())),
+            summaries: Arc::new(Mutex::new(Vec::new())),
+            errors: Arc::new(AtomicU64::new(0)),
+            scratch_dir: Arc::new(Mutex::new(None)),
+            log_path: Arc::new(Mutex::new(None)),
+            error: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl MetadataStress {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    // Called on app exit: stop workers and remove the scratch tree without waiting for the run thread
+    pub fn cleanup(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(dir) = self.scratch_dir.lock().unwrap().take() {
+            // Workers may still be mid-round; retry until the tree is gone
+            for _ in 0..20 {
+                if std::fs::remove_dir_all(&dir).is_ok() || !dir.exists() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
+        ui.heading("Filesystem Metadata Stress Test");
+        ui.add_space(10.0);
+        ui.label("Creates, stats, renames and deletes large numbers of small files and directories from several threads to measure metadata ops/sec and latency. All scratch files are removed when the test ends or is stopped.");
+        ui.add_space(10.0);
+        let mut target_dir = self.target_dir.lock().unwrap().clone();
+        let mut duration = *self.duration_secs.lock().unwrap();
+        let mut threads = *self.threads.lock().unwrap();
+        let mut files_per_dir = *self.files_per_dir.lock().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Target Directory:");
+            if ui.text_edit_singleline(&mut target_dir).changed() {
+                *self.target_dir.lock().unwrap() = target_dir.clone();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Duration (seconds):");
+            if ui.add(egui::DragValue::new(&mut duration).range(5..=600)).changed() {
+                *self.duration_secs.lock().unwrap() = duration;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Threads:");
+            if ui.add(egui::DragValue::new(&mut threads).range(1..=256)).changed() {
+                *self.threads.lock().unwrap() = threads;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Files per Directory:");
+            if ui.add(egui::DragValue::new(&mut files_per_dir).range(1..=10000)).changed() {
+                *self.files_per_dir.lock().unwrap() = files_per_dir;
+            }
+        });
+        ui.add_space(10.0);
+        if ui.button(if self.running.load(Ordering::SeqCst) { "Stop Metadata Stress" } else { "Start Metadata Stress" }).clicked() {
+            let running = self.running.clone();
+            if running.load(Ordering::SeqCst) {
+                running.store(false, Ordering::SeqCst);
+            } else {
+                running.store(true, Ordering::SeqCst);
+                self.live.lock().unwrap().clear();
+                self.summaries.lock().unwrap().clear();
+                self.errors.store(0, Ordering::SeqCst);
+                *self.error.lock().unwrap() = None;
+                let live = self.live.clone();
+                let summaries = self.summaries.clone();
+                let errors = self.errors.clone();
+                let scratch_dir = self.scratch_dir.clone();
+                let log_path = self.log_path.clone();
+                let error = self.error.clone();
+                let ctx = ctx.clone();
+                if dev_mode {
+                    println!("[DEV] Starting metadata stress test: dir={}, threads={}, files_per_dir={}, duration_secs={}", target_dir, threads, files_per_dir, duration);
+                }
+                thread::spawn(move || {
+                    let hash = seed::file_hash();
+                    let base = PathBuf::from(if target_dir.trim().is_empty() { "." } else { target_dir.trim() });
+                    remove_stale_scratch(&base);
+                    let root = base.join(format!("{}{}_{}", SCRATCH_PREFIX, std::process::id(), hash));
+                    let worker_dirs: Vec<PathBuf> = (0..threads).map(|t| root.join(format!("t{}", t))).collect();
+                    if let Err(e) = worker_dirs.iter().try_for_each(std::fs::create_dir_all) {
+                        let _ = std::fs::remove_dir_all(&root);
+                        *error.lock().unwrap() = Some(format!("Failed to create scratch directory in {}: {}", base.display(), e));
+                        running.store(false, Ordering::SeqCst);
+                        ctx.request_repaint();
+                        return;
+                    }
+                    let guard = ScratchGuard(root.clone());
+                    *scratch_dir.lock().unwrap() = Some(root.clone());
+                    let date = Local::now().format("%Y%m%d_%H%M%S");
+                    let log_dir = crate::app::log_dir(dev_mode);
+                    let _ = std::fs::create_dir_all(&log_dir);
+                    let log_file_name = log_dir.join(format!("metadata_stress_{}_{}_thr{}_files{}_dur{}.csv", hash, date, threads, files_per_dir, duration));
+                    let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).ok();
+                    if let Some(f) = log_file.as_mut() {
+                        writeln!(f, "timestamp,kind,operation,ops_per_sec,p50_us,p95_us,p99_us,max_us").ok();
+                        inventory::get().write_csv_comments(f);
+                        *log_path.lock().unwrap() = Some(log_file_name.clone());
+                        if dev_mode {
+                            println!("[DEV] Created log file: {}", log_file_name.display());
+                        }
+                    }
+                    let journal = RunJournal::begin(dev_mode, "metadata_stress", Some(&log_file_name));
+                    let watch = watchdog::watch("metadata_stress", duration as u64, {
+                        let running = running.clone();
+                        move || running.store(false, Ordering::SeqCst)
+                    });
+                    let counters = Arc::new(OpCounters::new());
+                    let start = Instant::now();
+                    let end = start + Duration::from_secs(duration as u64);
+                    let workers: Vec<_> = worker_dirs.into_iter().map(|dir| {
+                        let counters = counters.clone();
+                        let running = running.clone();
+                        thread::spawn(move || metadata_worker(dir, files_per_dir as usize, &counters, &running, end))
+                    }).collect();
+                    let mut last_counts = [0u64; 4];
+                    let mut last_hist: Vec<Vec<u64>> = vec![vec![0; BUCKET_COUNT]; 4];
+                    let mut last_report = Instant::now();
+                    while running.load(Ordering::SeqCst) && Instant::now() < end {
+                        thread::sleep(SAMPLE_INTERVAL);
+                        watch.heartbeat();
+                        let elapsed = last_report.elapsed().as_secs_f64();
+                        last_report = Instant::now();
+                        let mut samples = Vec::new();
+                        for op in MetadataOp::ALL {
+                            let i = op.index();
+                            let count = counters.counts[i].load(Ordering::Relaxed);
+                            let hist = counters.histograms[i].snapshot();
+                            let delta: Vec<u64> = hist.iter().zip(&last_hist[i]).map(|(now, before)| now - before).collect();
+                            let sample = MetadataOpSummary::from_counts(op, (count - last_counts[i]) as f64 / elapsed, &delta);
+                            if let Some(f) = log_file.as_mut() {
+                                write_row(f, "sample", &sample);
+                            }
+                            telemetry::emit("metadata_stress", &format!("{}_ops_per_sec", op.name()), sample.ops_per_sec, None);
+                            samples.push(sample);
+                            last_counts[i] = count;
+                            last_hist[i] = hist;
+                        }
+                        *live.lock().unwrap() = samples;
+                        errors.store(counters.errors.load(Ordering::Relaxed), Ordering::SeqCst);
+                        telemetry::emit("metadata_stress", "errors", counters.errors.load(Ordering::Relaxed) as f64, None);
+                        ctx.request_repaint();
+                    }
+                    for worker in workers {
+                        let _ = worker.join();
+                    }
+                    let elapsed = start.elapsed().as_secs_f64();
+                    let totals: Vec<MetadataOpSummary> = MetadataOp::ALL.iter().map(|&op| {
+                        let i = op.index();
+                        let count = counters.counts[i].load(Ordering::Relaxed);
+                        MetadataOpSummary::from_counts(op, count as f64 / elapsed, &counters.histograms[i].snapshot())
+                    }).collect();
+                    if let Some(f) = log_file.as_mut() {
+                        for total in &totals {
+                            write_row(f, "total", total);
+                        }
+                    }
+                    *summaries.lock().unwrap() = totals;
+                    errors.store(counters.errors.load(Ordering::Relaxed), Ordering::SeqCst);
+                    *scratch_dir.lock().unwrap() = None;
+                    drop(guard);
+                    if let Some(journal) = journal {
+                        journal.finish();
+                    }
+                    running.store(false, Ordering::SeqCst);
+                    ctx.request_repaint();
+                    if dev_mode {
+                        println!("[DEV] Metadata stress test thread finished");
+                    }
+                });
+            }
+        }
+        ui.add_space(10.0);
+        if let Some(err) = &*self.error.lock().unwrap() {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+        let errors = self.errors.load(Ordering::SeqCst);
+        if errors > 0 {
+            ui.colored_label(egui::Color32::YELLOW, format!("{} operations failed", errors));
+        }
+        if self.running.load(Ordering::SeqCst) {
+            ui.colored_label(egui::Color32::YELLOW, "Test Running...");
+            let live = self.live.lock().unwrap().clone();
+            if !live.is_empty() {
+                ui_summary_table(ui, "metadata_live_table", &live);
+            }
+        } else {
+            if let Some(log_path) = &*self.log_path.lock().unwrap() {
+                ui.label(format!("Log saved to: {}", log_path.display()));
+            }
+            let summaries = self.summaries.lock().unwrap().clone();
+            if !summaries.is_empty() {
+                ui_summary_table(ui, "metadata_summary_table", &summaries);
+            }
+        }
+    }
+}
+
+pub fn ui_summary_table(ui: &mut egui::Ui, id: &str, summaries: &[MetadataOpSummary]) {
+    let fmt_us = |v: Option<f64>| v.map(|v| format!("≤{:.0}", v)).unwrap_or_else(|| "N/A".to_string());
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Operation"); ui.label("Ops/s"); ui.label("p50 µs"); ui.label("p95 µs"); ui.label("p99 µs"); ui.label("Max µs"); ui.end_row();
+        for s in summaries {
+            ui.label(s.op.name());
+            ui.label(format!("{:.0}", s.ops_per_sec));
+            ui.label(fmt_us(s.p50_us));
+            ui.label(fmt_us(s.p95_us));
+            ui.label(fmt_us(s.p99_us));
+            ui.label(fmt_us(s.max_us));
+            ui.end_row();
+        }
+    });
+}
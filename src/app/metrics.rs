@@ -0,0 +1,140 @@
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{atomic::{AtomicBool, Ordering}, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use once_cell::sync::Lazy;
+
+// Series not updated for this long belong to a test that has stopped and are left out of scrapes
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+// (metric, subsystem, thread) -> latest value and when it was recorded
+type MetricKey = (String, String, Option<usize>);
+static LATEST: Lazy<Mutex<BTreeMap<MetricKey, (f64, Instant)>>> = Lazy::new(|| Mutex::new(BTreeMap::new()));
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Called for every telemetry sample; cheap no-op unless the exporter is running
+pub fn record(subsystem: &str, metric: &str, value: f64, thread: Option<usize>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    LATEST.lock().unwrap().insert((metric.to_string(), subsystem.to_string(), thread), (value, Instant::now()));
+}
+
+// Prometheus text exposition format. Whole-system samples are unlabeled, everything else gets a
+// `test` label and, for per-worker series, a `thread` label.
+pub fn render() -> String {
+    let mut out = String::new();
+    let mut last_name = String::new();
+    let latest = LATEST.lock().unwrap();
+    for ((metric, subsystem, thread), (value, at)) in latest.iter() {
+        if at.elapsed() > STALE_AFTER || !value.is_finite() {
+            continue;
+        }
+        let name = format!("stressor_{}", metric);
+        if name != last_name {
+            out.push_str(&format!("# TYPE {} gauge\n", name));
+            last_name = name.clone();
+        }
+        let mut labels = Vec::new();
+        if subsystem != "system" {
+            labels.push(format!("test=\"{}\"", subsystem));
+        }
+        if let Some(t) = thread {
+            labels.push(format!("thread=\"{}\"", t));
+        }
+        if labels.is_empty() {
+            out.push_str(&format!("{} {}\n", name, value));
+        } else {
+            out.push_str(&format!("{}{{{}}} {}\n", name, labels.join(","), value));
+        }
+    }
+    out
+}
+
+fn handle_client(mut stream: TcpStream) {
+    stream.set_read_timeout(Some(Duration::from_secs(2))).ok();
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    // Only the request line matters, but read the headers so clients don't see a reset
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", "text/plain; version=0.0.4; charset=utf-8", render()),
+        (Some("GET"), _) => ("404 Not Found", "text/plain; charset=utf-8", "Not found, try /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain; charset=utf-8", "Only GET is supported\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).ok();
+}
+
+// Serves /metrics on 127.0.0.1:<port>. Port 0 leaves the exporter off.
+pub fn start_server(port: u16) -> Result<(), String> {
+    if port == 0 || ENABLED.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| format!("Failed to serve metrics on port {}: {}", port, e))?;
+    ENABLED.store(true, Ordering::SeqCst);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            thread::spawn(move || handle_client(stream));
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sends one raw request to handle_client over loopback and returns the whole response
+    fn exchange(request: &str) -> String {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || handle_client(listener.accept().unwrap().0));
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+        response
+    }
+
+    #[test]
+    fn metrics_endpoint_serves_labelled_gauges() {
+        ENABLED.store(true, Ordering::SeqCst);
+        record("system", "test_cpu_usage_percent", 42.5, None);
+        record("cpu", "test_ops_per_sec", 1000.0, Some(3));
+        record("cpu", "test_errors", f64::NAN, None);
+        let response = exchange("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(response.contains("# TYPE stressor_test_cpu_usage_percent gauge\nstressor_test_cpu_usage_percent 42.5\n"));
+        assert!(response.contains("stressor_test_ops_per_sec{test=\"cpu\",thread=\"3\"} 1000\n"));
+        // Non-finite values would break Prometheus' parser
+        assert!(!response.contains("stressor_test_errors"));
+        let body = response.split_once("\r\n\r\n").unwrap().1;
+        assert!(response.contains(&format!("Content-Length: {}\r\n", body.len())));
+    }
+
+    #[test]
+    fn metrics_server_rejects_other_paths_and_methods() {
+        assert!(exchange("GET / HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(exchange("POST /metrics HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
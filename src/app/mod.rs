@@ -8,6 +8,27 @@ pub mod analyzer;
 pub mod onload;
 pub mod selectable_stress;
 pub mod config;
+pub mod topology;
+pub mod core_sweep;
+pub mod load_profile;
+pub mod processes;
+pub mod network_stress;
+pub mod metadata_stress;
+pub mod latency;
+pub mod storage_sync;
+pub mod system_stress;
+pub mod scheduler;
+pub mod telemetry;
+pub mod metrics;
+pub mod control;
+pub mod coordinator;
+pub mod journal;
+pub mod watchdog;
+pub mod inventory;
+pub mod scoring;
+pub mod bench;
+pub mod throttle;
+pub mod seed;
 use self::analyzer::Analyzer;
 use self::stress_test::StressTest;
 use eframe::egui;
@@ -16,9 +37,21 @@ use std::{
 };
 use crate::app::cpu::History;
 use crate::app::config::Config;
+use crate::app::processes::ProcessMonitor;
 use sysinfo::{System, CpuExt};
 use sysinfo::SystemExt;
 
+// Where every run writes its logs and where the analyzer, journal and watchdog look for them: ./log
+// in dev mode, otherwise next to the executable
+pub fn log_dir(dev_mode: bool) -> std::path::PathBuf {
+    if dev_mode {
+        std::path::PathBuf::from("log")
+    } else {
+        std::env::current_exe().ok().and_then(|p| p.parent().map(|d| d.to_path_buf())).unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("log")
+    }
+}
+
 pub enum Tab {
     SystemInfo,
     Stress,
@@ -42,6 +75,8 @@ pub struct SystemMonitorApp {
     pub dev_mode: bool,
     pub last_error: Option<String>,
     pub config: Config,
+    pub processes: ProcessMonitor,
+    pub inventory_status: Option<String>,
 }
 
 impl Default for SystemMonitorApp {
@@ -65,6 +100,25 @@ impl SystemMonitorApp {
             (com, con)
         };
         let config = Config::load(dev_mode);
+        // Each server starts on its own so one bad setting doesn't keep the others down
+        let startup_errors: Vec<String> = [
+            telemetry::TelemetryTarget::parse(&config.telemetry).and_then(telemetry::start),
+            metrics::start_server(config.metrics_port),
+            control::start_server(&config.control_bind, config.control_port, &config.control_token),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+        let last_error = (!startup_errors.is_empty()).then(|| startup_errors.join("; "));
+        seed::init(config.seed);
+        watchdog::start(&config, dev_mode);
+        inventory::prefetch();
+        let log_dir = log_dir(dev_mode);
+        // Scanned before any test starts so only runs from earlier sessions are picked up
+        let crashed_runs = journal::find_crashed(&log_dir);
+        if dev_mode && !crashed_runs.is_empty() {
+            println!("[DEV] Found {} journaled run(s) without a clean end", crashed_runs.len());
+        }
         Self {
             sys,
             current_tab: Tab::SystemInfo,
@@ -77,10 +131,12 @@ impl SystemMonitorApp {
             current_cpu_freq: 0,
             #[cfg(windows)]
             wmi_con,
-            analyzer: Analyzer::default(),
+            analyzer: Analyzer { crashed_runs, ..Analyzer::default() },
             cpu_temperature_celsius: None,
             dev_mode,
-            last_error: None,
+            last_error,
+            processes: ProcessMonitor::from_config(&config),
+            inventory_status: None,
             config,
         }
     }
@@ -88,6 +144,7 @@ impl SystemMonitorApp {
         cpu::update_cpu_data(self);
         memory::update_memory_data(self);
         storage::update_storage_data(self);
+        processes::update_process_data(self);
     }
 
     pub fn ui_system_info(&mut self, ui: &mut egui::Ui) {
@@ -96,12 +153,59 @@ impl SystemMonitorApp {
         // Only borrow self mutably once, call memory last
         let config_ptr: *const _ = &self.config;
         memory::ui_memory_info(self, ui, unsafe { &*config_ptr });
+        processes::ui_process_info(self, ui);
+        inventory::ui_inventory(ui, self.dev_mode, &mut self.inventory_status);
+    }
+
+    // Called when the window closes so tests that own scratch files can remove them
+    pub fn on_exit(&mut self) {
+        self.stress_test.metadata_stress.cleanup();
+        // Closing the app mid-test is a clean end, not a crash
+        journal::close_active("app_closed");
+    }
+
+    // Shown above every tab until dismissed; the runs stay listed in the analyzer
+    fn ui_crash_notice(&mut self, ui: &mut egui::Ui) {
+        let pending: Vec<&journal::CrashedRun> = self.analyzer.crashed_runs.iter().filter(|r| !r.acknowledged).collect();
+        if pending.is_empty() {
+            return;
+        }
+        let mut dismiss = false;
+        let mut open = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {} test run(s) did not finish cleanly last time:", pending.len()));
+            for run in &pending {
+                ui.label(format!("{} ({}, started {}): {}", run.label(), run.kind, journal::format_time(&run.started), run.readings()));
+            }
+            ui.horizontal(|ui| {
+                open = ui.button("Show in Analyzers").clicked();
+                dismiss = ui.button("Dismiss").clicked();
+            });
+        });
+        ui.add_space(8.0);
+        if open {
+            self.current_tab = Tab::Analyzers;
+            self.analyzer.analyzer_tab = analyzer::AnalyzerTab::CrashedRuns;
+            self.analyzer.selected_log_index = None;
+        }
+        if dismiss || open {
+            journal::acknowledge(&mut self.analyzer.crashed_runs);
+        }
     }
 }
 
 impl eframe::App for SystemMonitorApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Minimized, unfocused or occluded windows may not get frames (Wayland and macOS throttle
+        // them), so the watchdog only judges a focused, visible UI
+        watchdog::ui_heartbeat(ctx.input(|i| {
+            let viewport = i.viewport();
+            viewport.minimized.unwrap_or(false) || !viewport.focused.unwrap_or(true)
+        }));
         self.update_system_data();
+        // Checked every frame so queued runs start even when the Stress tab isn't open
+        self.stress_test.run_scheduled(ctx, self.dev_mode);
+        control::process_pending(&mut self.stress_test, ctx, self.dev_mode, Duration::ZERO);
         egui::TopBottomPanel::top("tab_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 if ui.selectable_label(matches!(self.current_tab, Tab::SystemInfo), "System Info").clicked() {
@@ -122,11 +226,17 @@ impl eframe::App for SystemMonitorApp {
             });
         });
         egui::CentralPanel::default().show(ctx, |ui| {
-            match self.current_tab {
-                Tab::SystemInfo => self.ui_system_info(ui),
-                Tab::Stress => self.stress_test.ui(ctx, ui, self.dev_mode),
-                Tab::Analyzers => self.analyzer.ui(ctx, ui),
-            }
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                self.ui_crash_notice(ui);
+                match self.current_tab {
+                    Tab::SystemInfo => self.ui_system_info(ui),
+                    Tab::Stress => {
+                        processes::ui_competition_warning(self, ui);
+                        self.stress_test.ui(ctx, ui, self.dev_mode);
+                    }
+                    Tab::Analyzers => self.analyzer.ui(ctx, ui),
+                }
+            });
         });
         ctx.request_repaint_after(Duration::from_millis(500));
     }
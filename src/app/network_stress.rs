@@ -0,0 +1,677 @@
+use eframe::egui;
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket},
+    path::PathBuf,
+    sync::{atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use atomic_float::AtomicF64;
+use chrono::Local;
+use crate::app::telemetry;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::seed;
+
+// First byte a client sends on a TCP connection tells the peer how to treat it
+const MODE_SINK: u8 = b'S';
+const MODE_PING: u8 = b'P';
+const THROUGHPUT_CHUNK: usize = 64 * 1024;
+// Short socket timeouts so every blocking call notices a stop request quickly
+const IO_TIMEOUT: Duration = Duration::from_millis(200);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NetworkPhase {
+    Throughput,
+    Churn,
+    Pps,
+}
+
+impl NetworkPhase {
+    pub const ALL: [NetworkPhase; 3] = [NetworkPhase::Throughput, NetworkPhase::Churn, NetworkPhase::Pps];
+
+    // Name used in the CSV log
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkPhase::Throughput => "throughput",
+            NetworkPhase::Churn => "churn",
+            NetworkPhase::Pps => "pps",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NetworkPhase::Throughput => "TCP Throughput",
+            NetworkPhase::Churn => "TCP Connection Churn",
+            NetworkPhase::Pps => "UDP Small-Packet PPS",
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            NetworkPhase::Throughput => "Mb/s",
+            NetworkPhase::Churn => "conn/s",
+            NetworkPhase::Pps => "pkt/s",
+        }
+    }
+
+    // Converts a raw counter delta (bytes, connections or packets) into the phase unit
+    fn scale(&self, count: u64) -> f64 {
+        match self {
+            NetworkPhase::Throughput => count as f64 * 8.0 / 1_000_000.0,
+            NetworkPhase::Churn | NetworkPhase::Pps => count as f64,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NetworkPhaseSummary {
+    pub phase: NetworkPhase,
+    pub rate: f64,
+    pub p50_us: Option<f64>,
+    pub p95_us: Option<f64>,
+    pub p99_us: Option<f64>,
+    pub errors: u64,
+}
+
+fn percentiles(latencies: &mut [f64]) -> Option<(f64, f64, f64)> {
+    if latencies.is_empty() {
+        return None;
+    }
+    latencies.sort_by(|a, b| a.total_cmp(b));
+    let at = |p: f64| latencies[((latencies.len() - 1) as f64 * p).round() as usize];
+    Some((at(0.50), at(0.95), at(0.99)))
+}
+
+fn is_timeout(e: &std::io::Error) -> bool {
+    matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut)
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    matches!(host.trim(), "" | "localhost" | "127.0.0.1" | "::1")
+}
+
+// Echo/sink peer. Bound to loopback for local runs, or to NETWORK_PEER_BIND so another machine can
+// target this one. It has no authentication, so connections beyond `max_connections` are refused
+// rather than each getting a thread.
+pub struct PeerServer {
+    pub tcp_port: u16,
+    pub udp_port: u16,
+    stop: Arc<AtomicBool>,
+    wake_addr: SocketAddr,
+}
+
+// Frees a connection slot when its serving thread ends
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Takes a connection slot for an accepted stream. A failed accept (e.g. out of file descriptors)
+// never took a slot, so it must not give one back either.
+fn admit<T>(stream: std::io::Result<T>, active: &Arc<AtomicUsize>, max_connections: usize) -> Option<(T, ConnectionSlot)> {
+    let stream = stream.ok()?;
+    if active.fetch_add(1, Ordering::SeqCst) >= max_connections {
+        active.fetch_sub(1, Ordering::SeqCst);
+        return None;
+    }
+    Some((stream, ConnectionSlot(active.clone())))
+}
+
+impl PeerServer {
+    pub fn start(bind_ip: &str, port: u16, max_connections: usize) -> std::io::Result<Self> {
+        let listener = TcpListener::bind((bind_ip, port))?;
+        let mut wake_addr = listener.local_addr()?;
+        if wake_addr.ip().is_unspecified() {
+            wake_addr.set_ip(std::net::Ipv4Addr::LOCALHOST.into());
+        }
+        let tcp_port = wake_addr.port();
+        let udp = UdpSocket::bind((bind_ip, port))?;
+        let udp_port = udp.local_addr()?.port();
+        udp.set_read_timeout(Some(IO_TIMEOUT))?;
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let stop = stop.clone();
+            let active = Arc::new(AtomicUsize::new(0));
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    // Dropping the stream closes it, so a full server refuses instead of queueing
+                    let Some((stream, slot)) = admit(stream, &active, max_connections) else {
+                        continue;
+                    };
+                    let stop = stop.clone();
+                    thread::spawn(move || {
+                        let _slot = slot;
+                        serve_tcp(stream, &stop)
+                    });
+                }
+            });
+        }
+        {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut buf = vec![0u8; 65536];
+                while !stop.load(Ordering::SeqCst) {
+                    if let Ok((n, from)) = udp.recv_from(&mut buf) {
+                        let _ = udp.send_to(&buf[..n], from);
+                    }
+                }
+            });
+        }
+        Ok(Self { tcp_port, udp_port, stop, wake_addr })
+    }
+
+    pub fn stop(&self) {
+        if !self.stop.swap(true, Ordering::SeqCst) {
+            // The accept loop blocks, so poke it with a throwaway connection
+            let _ = TcpStream::connect_timeout(&self.wake_addr, CONNECT_TIMEOUT);
+        }
+    }
+}
+
+impl Drop for PeerServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn serve_tcp(mut stream: TcpStream, stop: &AtomicBool) {
+    let _ = stream.set_nodelay(true);
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let mut mode = [0u8; 1];
+    loop {
+        match stream.read(&mut mode) {
+            Ok(1) => break,
+            Err(e) if is_timeout(&e) && !stop.load(Ordering::SeqCst) => continue,
+            _ => return,
+        }
+    }
+    let mut buf = vec![0u8; THROUGHPUT_CHUNK];
+    while !stop.load(Ordering::SeqCst) {
+        match stream.read(&mut buf) {
+            Ok(0) => return,
+            Ok(n) => {
+                if mode[0] == MODE_PING {
+                    // Reply once and close first, so TIME_WAIT lands here instead of exhausting client ports
+                    let _ = stream.write_all(&buf[..n]);
+                    return;
+                }
+            }
+            Err(e) if is_timeout(&e) => continue,
+            Err(_) => return,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PhaseCounters {
+    count: AtomicU64,
+    errors: AtomicU64,
+    latencies_us: Mutex<Vec<f64>>,
+}
+
+fn throughput_worker(addr: SocketAddr, counters: &PhaseCounters, running: &AtomicBool, end: Instant) {
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) else {
+        counters.errors.fetch_add(1, Ordering::SeqCst);
+        return;
+    };
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+    if stream.write_all(&[MODE_SINK]).is_err() {
+        counters.errors.fetch_add(1, Ordering::SeqCst);
+        return;
+    }
+    let buf = vec![0x5Au8; THROUGHPUT_CHUNK];
+    while running.load(Ordering::SeqCst) && Instant::now() < end {
+        match stream.write(&buf) {
+            Ok(n) => {
+                counters.count.fetch_add(n as u64, Ordering::SeqCst);
+            }
+            Err(e) if is_timeout(&e) => continue,
+            Err(_) => {
+                counters.errors.fetch_add(1, Ordering::SeqCst);
+                return;
+            }
+        }
+    }
+}
+
+fn churn_worker(addr: SocketAddr, counters: &PhaseCounters, running: &AtomicBool, end: Instant) {
+    let mut reply = [0u8; 1];
+    while running.load(Ordering::SeqCst) && Instant::now() < end {
+        let start = Instant::now();
+        let ok = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).and_then(|mut stream| {
+            stream.set_nodelay(true)?;
+            stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+            stream.write_all(&[MODE_PING, 0xAB])?;
+            stream.read_exact(&mut reply)?;
+            let latency = start.elapsed();
+            // Wait for the peer's FIN so the connection is fully torn down before the next one
+            let _ = stream.read(&mut reply);
+            Ok(latency)
+        });
+        match ok {
+            Ok(latency) => {
+                counters.count.fetch_add(1, Ordering::SeqCst);
+                counters.latencies_us.lock().unwrap().push(latency.as_secs_f64() * 1e6);
+            }
+            Err(_) => {
+                counters.errors.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+fn pps_worker(addr: SocketAddr, packet_bytes: usize, counters: &PhaseCounters, running: &AtomicBool, end: Instant) {
+    let bind: SocketAddr = if addr.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0u16; 8], 0).into() };
+    let socket = UdpSocket::bind(bind).and_then(|s| {
+        s.connect(addr)?;
+        s.set_read_timeout(Some(IO_TIMEOUT))?;
+        Ok(s)
+    });
+    let Ok(socket) = socket else {
+        counters.errors.fetch_add(1, Ordering::SeqCst);
+        return;
+    };
+    let mut payload = vec![0u8; packet_bytes.max(8)];
+    let mut reply = vec![0u8; payload.len()];
+    let mut seq = 0u64;
+    while running.load(Ordering::SeqCst) && Instant::now() < end {
+        seq += 1;
+        payload[..8].copy_from_slice(&seq.to_le_bytes());
+        let start = Instant::now();
+        if socket.send(&payload).is_err() {
+            counters.errors.fetch_add(1, Ordering::SeqCst);
+            continue;
+        }
+        loop {
+            match socket.recv(&mut reply) {
+                Ok(n) if n >= 8 && reply[..8] == payload[..8] => {
+                    counters.count.fetch_add(1, Ordering::SeqCst);
+                    counters.latencies_us.lock().unwrap().push(start.elapsed().as_secs_f64() * 1e6);
+                    break;
+                }
+                // Late reply to a packet already counted as lost
+                Ok(_) => continue,
+                Err(_) => {
+                    counters.errors.fetch_add(1, Ordering::SeqCst);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn write_row(log_file: &mut File, phase: NetworkPhase, kind: &str, rate: f64, latencies: &mut [f64], errors: u64) {
+    let (p50, p95, p99) = match percentiles(latencies) {
+        Some((p50, p95, p99)) => (p50.to_string(), p95.to_string(), p99.to_string()),
+        None => (String::new(), String::new(), String::new()),
+    };
+    writeln!(log_file, "{},{},{},{},{},{},{},{}", Local::now().to_rfc3339(), phase.name(), kind, rate, p50, p95, p99, errors).ok();
+}
+
+struct PhaseRun<'a> {
+    tcp_addr: SocketAddr,
+    udp_addr: SocketAddr,
+    secs: u64,
+    streams: usize,
+    packet_bytes: usize,
+    running: &'a Arc<AtomicBool>,
+    live_rate: &'a AtomicF64,
+    ctx: &'a egui::Context,
+}
+
+fn run_phase(phase: NetworkPhase, run: &PhaseRun, log_file: &mut File) -> NetworkPhaseSummary {
+    let counters = Arc::new(PhaseCounters::default());
+    let start = Instant::now();
+    let end = start + Duration::from_secs(run.secs);
+    let workers: Vec<_> = (0..run.streams).map(|_| {
+        let counters = counters.clone();
+        let running = run.running.clone();
+        let (tcp_addr, udp_addr, packet_bytes) = (run.tcp_addr, run.udp_addr, run.packet_bytes);
+        thread::spawn(move || match phase {
+            NetworkPhase::Throughput => throughput_worker(tcp_addr, &counters, &running, end),
+            NetworkPhase::Churn => churn_worker(tcp_addr, &counters, &running, end),
+            NetworkPhase::Pps => pps_worker(udp_addr, packet_bytes, &counters, &running, end),
+        })
+    }).collect();
+    let mut all_latencies = Vec::new();
+    let mut last_count = 0;
+    let mut last_report = Instant::now();
+    while run.running.load(Ordering::SeqCst) && Instant::now() < end {
+        thread::sleep(Duration::from_millis(200));
+        let count = counters.count.load(Ordering::SeqCst);
+        let rate = phase.scale(count - last_count) / last_report.elapsed().as_secs_f64();
+        last_count = count;
+        last_report = Instant::now();
+        let mut interval = std::mem::take(&mut *counters.latencies_us.lock().unwrap());
+        write_row(log_file, phase, "sample", rate, &mut interval, counters.errors.load(Ordering::SeqCst));
+        all_latencies.extend(interval);
+        run.live_rate.store(rate, Ordering::SeqCst);
+        let metric = match phase {
+            NetworkPhase::Throughput => "throughput_mbps",
+            NetworkPhase::Churn => "connections_per_sec",
+            NetworkPhase::Pps => "packets_per_sec",
+        };
+        telemetry::emit("network_stress", metric, rate, None);
+        telemetry::emit("network_stress", "errors", counters.errors.load(Ordering::SeqCst) as f64, None);
+        run.ctx.request_repaint();
+    }
+    for worker in workers {
+        let _ = worker.join();
+    }
+    all_latencies.extend(std::mem::take(&mut *counters.latencies_us.lock().unwrap()));
+    let rate = phase.scale(counters.count.load(Ordering::SeqCst)) / start.elapsed().as_secs_f64();
+    let errors = counters.errors.load(Ordering::SeqCst);
+    write_row(log_file, phase, "total", rate, &mut all_latencies, errors);
+    let pct = percentiles(&mut all_latencies);
+    NetworkPhaseSummary {
+        phase,
+        rate,
+        p50_us: pct.map(|p| p.0),
+        p95_us: pct.map(|p| p.1),
+        p99_us: pct.map(|p| p.2),
+        errors,
+    }
+}
+
+pub struct NetworkStress {
+    running: Arc<AtomicBool>,
+    host: Arc<Mutex<String>>,
+    port: u16,
+    peer_bind: String,
+    peer_max_connections: usize,
+    duration_secs: Arc<Mutex<u32>>,
+    streams: Arc<Mutex<u32>>,
+    packet_bytes: Arc<Mutex<u32>>,
+    current_phase: Arc<Mutex<Option<NetworkPhase>>>,
+    current_rate: Arc<AtomicF64>,
+    summaries: Arc<Mutex<Vec<NetworkPhaseSummary>>>,
+    log_path: Arc<Mutex<Option<PathBuf>>>,
+    error: Arc<Mutex<Option<String>>>,
+    peer_server: Option<PeerServer>,
+}
+
+impl NetworkStress {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            host: Arc::new(Mutex::new(config.network_host.clone())),
+            port: config.network_port,
+            peer_bind: config.network_peer_bind.clone(),
+            peer_max_connections: config.network_peer_max_connections,
+            duration_secs: Arc::new(Mutex::new(config.network_duration_secs)),
+            streams: Arc::new(Mutex::new(config.network_streams)),
+            packet_bytes: Arc::new(Mutex::new(config.network_packet_bytes)),
+            current_phase: Arc::new(Mutex::new(None)),
+            current_rate: Arc::new(AtomicF64::new(0.0)),
+            summaries: Arc::new(Mutex::new(Vec::new())),
+            log_path: Arc::new(Mutex::new(None)),
+            error: Arc::new(Mutex::new(None)),
+            peer_server: None,
+        }
+    }
+}
+
+impl NetworkStress {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
+        ui.heading("Network Stress Test");
+        ui.add_space(10.0);
+        ui.label("Runs TCP throughput, TCP connection churn and UDP small-packet tests against an echo/sink peer. Leave the host as 127.0.0.1 to test the local network stack over loopback, or point it at another machine running the peer server.");
+        ui.add_space(10.0);
+        let mut host = self.host.lock().unwrap().clone();
+        let mut duration = *self.duration_secs.lock().unwrap();
+        let mut streams = *self.streams.lock().unwrap();
+        let mut packet_bytes = *self.packet_bytes.lock().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Peer Host:");
+            if ui.text_edit_singleline(&mut host).changed() {
+                *self.host.lock().unwrap() = host.clone();
+            }
+            if !is_loopback_host(&host) {
+                ui.label(format!("(port {})", self.port));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Duration (seconds):");
+            if ui.add(egui::DragValue::new(&mut duration).range(3..=600)).changed() {
+                *self.duration_secs.lock().unwrap() = duration;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Parallel Streams:");
+            if ui.add(egui::DragValue::new(&mut streams).range(1..=256)).changed() {
+                *self.streams.lock().unwrap() = streams;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("UDP Packet Size (bytes):");
+            if ui.add(egui::DragValue::new(&mut packet_bytes).range(8..=1400)).changed() {
+                *self.packet_bytes.lock().unwrap() = packet_bytes;
+            }
+        });
+        ui.horizontal(|ui| {
+            let serving = self.peer_server.is_some();
+            if ui.button(if serving { "Stop Peer Server" } else { "Start Peer Server" }).clicked() {
+                if let Some(server) = self.peer_server.take() {
+                    server.stop();
+                } else {
+                    match PeerServer::start(&self.peer_bind, self.port, self.peer_max_connections) {
+                        Ok(server) => {
+                            *self.error.lock().unwrap() = None;
+                            self.peer_server = Some(server);
+                        }
+                        Err(e) => *self.error.lock().unwrap() = Some(format!("Failed to start peer server on {}:{}: {}", self.peer_bind, self.port, e)),
+                    }
+                }
+            }
+            if let Some(server) = &self.peer_server {
+                ui.colored_label(egui::Color32::LIGHT_BLUE, format!("Serving TCP/UDP on {}:{}", self.peer_bind, server.tcp_port));
+                if is_loopback_host(&self.peer_bind) {
+                    ui.label("(loopback only; set NETWORK_PEER_BIND to accept other machines)");
+                }
+            }
+        });
+        ui.add_space(10.0);
+        if ui.button(if self.running.load(Ordering::SeqCst) { "Stop Network Stress" } else { "Start Network Stress" }).clicked() {
+            let running = self.running.clone();
+            if running.load(Ordering::SeqCst) {
+                running.store(false, Ordering::SeqCst);
+            } else {
+                running.store(true, Ordering::SeqCst);
+                self.summaries.lock().unwrap().clear();
+                *self.error.lock().unwrap() = None;
+                self.current_rate.store(0.0, Ordering::SeqCst);
+                let summaries = self.summaries.clone();
+                let current_phase = self.current_phase.clone();
+                let current_rate = self.current_rate.clone();
+                let log_path = self.log_path.clone();
+                let error = self.error.clone();
+                let port = self.port;
+                let ctx = ctx.clone();
+                if dev_mode {
+                    println!("[DEV] Starting network stress test: host={}, streams={}, packet_bytes={}, duration_secs={}", host, streams, packet_bytes, duration);
+                }
+                thread::spawn(move || {
+                    // A loopback run brings its own peer on ephemeral ports; a remote run expects a peer server on the configured port
+                    let local_server = if is_loopback_host(&host) {
+                        // Every stream plus connection-churn sockets still closing on the peer side
+                        match PeerServer::start("127.0.0.1", 0, streams as usize * 4 + 16) {
+                            Ok(server) => Some(server),
+                            Err(e) => {
+                                *error.lock().unwrap() = Some(format!("Failed to start loopback peer: {}", e));
+                                running.store(false, Ordering::SeqCst);
+                                ctx.request_repaint();
+                                return;
+                            }
+                        }
+                    } else {
+                        None
+                    };
+                    let (tcp_addr, udp_addr) = match &local_server {
+                        Some(server) => (
+                            SocketAddr::from(([127, 0, 0, 1], server.tcp_port)),
+                            SocketAddr::from(([127, 0, 0, 1], server.udp_port)),
+                        ),
+                        None => match (host.trim(), port).to_socket_addrs().ok().and_then(|mut addrs| addrs.next()) {
+                            Some(addr) => (addr, addr),
+                            None => {
+                                *error.lock().unwrap() = Some(format!("Could not resolve peer host '{}'", host));
+                                running.store(false, Ordering::SeqCst);
+                                ctx.request_repaint();
+                                return;
+                            }
+                        },
+                    };
+                    let hash = seed::file_hash();
+                    let date = Local::now().format("%Y%m%d_%H%M%S");
+                    let log_dir = crate::app::log_dir(dev_mode);
+                    let _ = std::fs::create_dir_all(&log_dir);
+                    let log_file_name = log_dir.join(format!("network_stress_{}_{}_str{}_dur{}.csv", hash, date, streams, duration));
+                    let mut log_file = match OpenOptions::new().create(true).append(true).open(&log_file_name) {
+                        Ok(f) => f,
+                        Err(e) => {
+                            *error.lock().unwrap() = Some(format!("Failed to create log file: {}", e));
+                            running.store(false, Ordering::SeqCst);
+                            ctx.request_repaint();
+                            return;
+                        }
+                    };
+                    if dev_mode {
+                        println!("[DEV] Created log file: {}", log_file_name.display());
+                    }
+                    *log_path.lock().unwrap() = Some(log_file_name.clone());
+                    writeln!(log_file, "timestamp,phase,kind,rate,p50_us,p95_us,p99_us,errors").ok();
+                    inventory::get().write_csv_comments(&mut log_file);
+                    let journal = RunJournal::begin(dev_mode, "network_stress", Some(&log_file_name));
+                    let _watch = watchdog::watch("network_stress", duration as u64, {
+                        let running = running.clone();
+                        move || running.store(false, Ordering::SeqCst)
+                    });
+                    let run = PhaseRun {
+                        tcp_addr,
+                        udp_addr,
+                        secs: (duration / 3).max(1) as u64,
+                        streams: streams as usize,
+                        packet_bytes: packet_bytes as usize,
+                        running: &running,
+                        live_rate: &current_rate,
+                        ctx: &ctx,
+                    };
+                    for phase in NetworkPhase::ALL {
+                        if !running.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        *current_phase.lock().unwrap() = Some(phase);
+                        current_rate.store(0.0, Ordering::SeqCst);
+                        let summary = run_phase(phase, &run, &mut log_file);
+                        summaries.lock().unwrap().push(summary);
+                    }
+                    *current_phase.lock().unwrap() = None;
+                    drop(local_server);
+                    if let Some(journal) = journal {
+                        journal.finish();
+                    }
+                    running.store(false, Ordering::SeqCst);
+                    ctx.request_repaint();
+                    if dev_mode {
+                        println!("[DEV] Network stress test thread finished");
+                    }
+                });
+            }
+        }
+        ui.add_space(10.0);
+        if let Some(err) = &*self.error.lock().unwrap() {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+        if self.running.load(Ordering::SeqCst) {
+            if let Some(phase) = *self.current_phase.lock().unwrap() {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("Running {}: {:.1} {}", phase.label(), self.current_rate.load(Ordering::SeqCst), phase.unit()),
+                );
+            }
+        } else if let Some(log_path) = &*self.log_path.lock().unwrap() {
+            ui.label(format!("Log saved to: {}", log_path.display()));
+        }
+        let summaries = self.summaries.lock().unwrap().clone();
+        if !summaries.is_empty() {
+            ui.add_space(10.0);
+            ui_summary_table(ui, "network_summary_table", &summaries);
+        }
+    }
+}
+
+pub fn ui_summary_table(ui: &mut egui::Ui, id: &str, summaries: &[NetworkPhaseSummary]) {
+    let fmt_us = |v: Option<f64>| v.map(|v| format!("{:.1}", v)).unwrap_or_else(|| "N/A".to_string());
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Phase"); ui.label("Rate"); ui.label("p50 µs"); ui.label("p95 µs"); ui.label("p99 µs"); ui.label("Errors"); ui.end_row();
+        for s in summaries {
+            ui.label(s.phase.label());
+            ui.label(format!("{:.1} {}", s.rate, s.phase.unit()));
+            ui.label(fmt_us(s.p50_us));
+            ui.label(fmt_us(s.p95_us));
+            ui.label(fmt_us(s.p99_us));
+            if s.errors > 0 {
+                ui.colored_label(egui::Color32::YELLOW, format!("{}", s.errors));
+            } else {
+                ui.label("0");
+            }
+            ui.end_row();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_accepts_leave_the_connection_count_alone() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let error = || Err::<(), _>(std::io::Error::from_raw_os_error(24));
+        for _ in 0..3 {
+            assert!(admit(error(), &active, 2).is_none());
+        }
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+        // A later connection is still served
+        let first = admit(Ok(()), &active, 2).unwrap();
+        assert_eq!(active.load(Ordering::SeqCst), 1);
+        drop(first);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn connections_past_the_cap_are_refused_until_a_slot_frees() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let first = admit(Ok(1), &active, 2).unwrap();
+        let _second = admit(Ok(2), &active, 2).unwrap();
+        assert!(admit(Ok(3), &active, 2).is_none());
+        assert!(admit(Err::<i32, _>(std::io::ErrorKind::ConnectionAborted.into()), &active, 2).is_none());
+        assert_eq!(active.load(Ordering::SeqCst), 2);
+        drop(first);
+        assert_eq!(admit(Ok(4), &active, 2).map(|(s, _)| s), Some(4));
+        assert_eq!(active.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,251 @@
+use eframe::egui;
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use crate::app::SystemMonitorApp;
+
+// Process enumeration is much heavier than the CPU/memory refresh, so it runs less often
+const PROCESS_REFRESH_SECS: f64 = 2.0;
+const TOP_PROCESS_COUNT: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct ProcessSample {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f64, // share of the whole machine, 0-100
+    pub memory_bytes: u64,
+    pub disk_read_bps: f64,
+    pub disk_write_bps: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ThreadCpuTime {
+    pub tid: u32,
+    pub name: String,
+    pub cpu_secs: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProcessSort {
+    Cpu,
+    Memory,
+    DiskIo,
+}
+
+pub struct ProcessMonitor {
+    pub sort: ProcessSort,
+    pub top: Vec<ProcessSample>,
+    pub own: Option<ProcessSample>,
+    pub own_cpu_secs: Option<f64>,
+    pub own_threads: Vec<ThreadCpuTime>,
+    pub competing: Vec<ProcessSample>,
+    pub competition_threshold: f64,
+    last_refresh: Option<std::time::Instant>,
+    // Every process from the last refresh, so a sort change can re-rank without refreshing early
+    samples: Vec<ProcessSample>,
+}
+
+impl ProcessMonitor {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            sort: ProcessSort::Cpu,
+            top: Vec::new(),
+            own: None,
+            own_cpu_secs: None,
+            own_threads: Vec::new(),
+            competing: Vec::new(),
+            competition_threshold: config.process_competition_threshold,
+            last_refresh: None,
+            samples: Vec::new(),
+        }
+    }
+
+    fn rank_top(&mut self) {
+        let mut samples = self.samples.clone();
+        match self.sort {
+            ProcessSort::Cpu => samples.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent)),
+            ProcessSort::Memory => samples.sort_by_key(|s| std::cmp::Reverse(s.memory_bytes)),
+            ProcessSort::DiskIo => samples.sort_by(|a, b| (b.disk_read_bps + b.disk_write_bps).total_cmp(&(a.disk_read_bps + a.disk_write_bps))),
+        }
+        samples.truncate(TOP_PROCESS_COUNT);
+        self.top = samples;
+    }
+}
+
+pub fn update_process_data(app: &mut SystemMonitorApp) {
+    let now = std::time::Instant::now();
+    let interval = match app.processes.last_refresh {
+        Some(last) if now.duration_since(last).as_secs_f64() < PROCESS_REFRESH_SECS => return,
+        Some(last) => now.duration_since(last).as_secs_f64(),
+        None => PROCESS_REFRESH_SECS,
+    };
+    app.processes.last_refresh = Some(now);
+    app.sys.refresh_processes();
+    let cpu_count = app.sys.cpus().len().max(1) as f64;
+    let own_pid = sysinfo::get_current_pid().ok().map(|p| p.as_u32());
+    let samples: Vec<ProcessSample> = app.sys.processes().values().map(|p| {
+        let disk = p.disk_usage();
+        ProcessSample {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string(),
+            cpu_percent: p.cpu_usage() as f64 / cpu_count,
+            memory_bytes: p.memory(),
+            disk_read_bps: disk.read_bytes as f64 / interval,
+            disk_write_bps: disk.written_bytes as f64 / interval,
+        }
+    }).collect();
+    let monitor = &mut app.processes;
+    monitor.own = samples.iter().find(|s| Some(s.pid) == own_pid).cloned();
+    monitor.own_cpu_secs = own_cpu_time_secs();
+    monitor.own_threads = own_thread_cpu_times();
+    monitor.competing = samples.iter()
+        .filter(|s| Some(s.pid) != own_pid && s.cpu_percent >= monitor.competition_threshold)
+        .cloned()
+        .collect();
+    monitor.competing.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+    monitor.samples = samples;
+    monitor.rank_top();
+}
+
+#[cfg(target_os = "linux")]
+fn clock_ticks_per_sec() -> f64 {
+    (unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as f64).max(1.0)
+}
+
+// utime + stime from a /proc/<...>/stat line; the comm field may contain spaces so parse after ')'
+#[cfg(target_os = "linux")]
+fn parse_stat_cpu_secs(stat: &str) -> Option<f64> {
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime = fields.get(11)?.parse::<f64>().ok()?;
+    let stime = fields.get(12)?.parse::<f64>().ok()?;
+    Some((utime + stime) / clock_ticks_per_sec())
+}
+
+#[cfg(target_os = "linux")]
+fn own_cpu_time_secs() -> Option<f64> {
+    parse_stat_cpu_secs(&std::fs::read_to_string("/proc/self/stat").ok()?)
+}
+
+#[cfg(windows)]
+fn own_cpu_time_secs() -> Option<f64> {
+    use winapi::shared::minwindef::FILETIME;
+    use winapi::um::processthreadsapi::{GetCurrentProcess, GetProcessTimes};
+    let to_secs = |ft: &FILETIME| (((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64) as f64 / 1e7;
+    unsafe {
+        let mut creation: FILETIME = std::mem::zeroed();
+        let mut exit: FILETIME = std::mem::zeroed();
+        let mut kernel: FILETIME = std::mem::zeroed();
+        let mut user: FILETIME = std::mem::zeroed();
+        if GetProcessTimes(GetCurrentProcess(), &mut creation, &mut exit, &mut kernel, &mut user) == 0 {
+            return None;
+        }
+        Some(to_secs(&kernel) + to_secs(&user))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn own_cpu_time_secs() -> Option<f64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn own_thread_cpu_times() -> Vec<ThreadCpuTime> {
+    let mut threads = Vec::new();
+    let Ok(entries) = std::fs::read_dir("/proc/self/task") else {
+        return threads;
+    };
+    for entry in entries.flatten() {
+        let Some(tid) = entry.file_name().to_str().and_then(|t| t.parse::<u32>().ok()) else {
+            continue;
+        };
+        let name = std::fs::read_to_string(entry.path().join("comm")).unwrap_or_default().trim().to_string();
+        if let Some(cpu_secs) = std::fs::read_to_string(entry.path().join("stat")).ok().as_deref().and_then(parse_stat_cpu_secs) {
+            threads.push(ThreadCpuTime { tid, name, cpu_secs });
+        }
+    }
+    threads.sort_by(|a, b| b.cpu_secs.total_cmp(&a.cpu_secs));
+    threads
+}
+
+#[cfg(not(target_os = "linux"))]
+fn own_thread_cpu_times() -> Vec<ThreadCpuTime> {
+    Vec::new()
+}
+
+fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.2} GB", bytes / 1024.0 / 1024.0 / 1024.0)
+    } else if bytes >= 1024.0 * 1024.0 {
+        format!("{:.1} MB", bytes / 1024.0 / 1024.0)
+    } else {
+        format!("{:.0} KB", bytes / 1024.0)
+    }
+}
+
+pub fn ui_competition_warning(app: &SystemMonitorApp, ui: &mut egui::Ui) {
+    if app.stress_test.is_running() && !app.processes.competing.is_empty() {
+        let names: Vec<String> = app.processes.competing.iter()
+            .take(3)
+            .map(|p| format!("{} ({:.1}%)", p.name, p.cpu_percent))
+            .collect();
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            format!("⚠ Background processes are competing with the running test and may skew results: {}", names.join(", ")),
+        );
+    }
+}
+
+pub fn ui_process_info(app: &mut SystemMonitorApp, ui: &mut egui::Ui) {
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.label(egui::RichText::new("⚙️ Processes").strong());
+        ui_competition_warning(app, ui);
+        let monitor = &mut app.processes;
+        if let Some(own) = &monitor.own {
+            ui.label(egui::RichText::new(format!(
+                "Stressor (PID {}): CPU {:.1}% | RSS {}{}",
+                own.pid,
+                own.cpu_percent,
+                format_bytes(own.memory_bytes as f64),
+                monitor.own_cpu_secs.map(|s| format!(" | CPU time {:.1} s", s)).unwrap_or_default()
+            )).color(egui::Color32::LIGHT_BLUE));
+        }
+        if !monitor.own_threads.is_empty() {
+            egui::CollapsingHeader::new(format!("Stressor Threads ({})", monitor.own_threads.len()))
+                .id_salt("own_threads")
+                .show(ui, |ui| {
+                    egui::Grid::new("own_thread_table").striped(true).show(ui, |ui| {
+                        ui.label("TID"); ui.label("Name"); ui.label("CPU Time (s)"); ui.end_row();
+                        for t in monitor.own_threads.iter().take(32) {
+                            ui.label(format!("{}", t.tid));
+                            ui.label(&t.name);
+                            ui.label(format!("{:.2}", t.cpu_secs));
+                            ui.end_row();
+                        }
+                    });
+                });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Top processes by:");
+            let previous = monitor.sort;
+            ui.selectable_value(&mut monitor.sort, ProcessSort::Cpu, "CPU");
+            ui.selectable_value(&mut monitor.sort, ProcessSort::Memory, "Memory");
+            ui.selectable_value(&mut monitor.sort, ProcessSort::DiskIo, "Disk I/O");
+            if monitor.sort != previous {
+                // Re-rank the last refresh now; refreshing early would divide disk bytes by the wrong interval
+                monitor.rank_top();
+            }
+        });
+        egui::Grid::new("process_table").striped(true).show(ui, |ui| {
+            ui.label("PID"); ui.label("Name"); ui.label("CPU %"); ui.label("Memory"); ui.label("Disk Read/s"); ui.label("Disk Write/s"); ui.end_row();
+            for p in &monitor.top {
+                ui.label(format!("{}", p.pid));
+                ui.label(&p.name);
+                ui.label(format!("{:.1}", p.cpu_percent));
+                ui.label(format_bytes(p.memory_bytes as f64));
+                ui.label(format_bytes(p.disk_read_bps));
+                ui.label(format_bytes(p.disk_write_bps));
+                ui.end_row();
+            }
+        });
+    });
+    ui.add_space(8.0);
+    ui.separator();
+}
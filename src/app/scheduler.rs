@@ -0,0 +1,318 @@
+use eframe::egui;
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDateTime, NaiveTime, TimeZone};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScheduleTarget {
+    SystemPreset,
+    Cpu,
+    Storage,
+    Score,
+}
+
+impl ScheduleTarget {
+    pub const ALL: [ScheduleTarget; 4] = [ScheduleTarget::SystemPreset, ScheduleTarget::Cpu, ScheduleTarget::Storage, ScheduleTarget::Score];
+
+    // Name accepted on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScheduleTarget::SystemPreset => "system",
+            ScheduleTarget::Cpu => "cpu",
+            ScheduleTarget::Storage => "storage",
+            ScheduleTarget::Score => "score",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScheduleTarget::SystemPreset => "System Stress Preset",
+            ScheduleTarget::Cpu => "CPU Stress Test",
+            ScheduleTarget::Storage => "Storage Stress Test",
+            ScheduleTarget::Score => "Benchmark Score",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScheduledRun {
+    pub target: ScheduleTarget,
+    pub start_at: DateTime<Local>,
+    pub repeat_hours: Option<u32>,
+}
+
+// Accepts "90s", "15m", "2h" or a bare number of minutes
+pub fn parse_delay(text: &str) -> Result<ChronoDuration, String> {
+    let text = text.trim();
+    let (number, unit) = match text.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&text[..i], c.to_ascii_lowercase()),
+        _ => (text, 'm'),
+    };
+    let value: i64 = number.trim().parse().map_err(|_| format!("invalid delay '{}'", text))?;
+    if value < 0 {
+        return Err(format!("invalid delay '{}'", text));
+    }
+    match unit {
+        's' => Ok(ChronoDuration::seconds(value)),
+        'm' => Ok(ChronoDuration::minutes(value)),
+        'h' => Ok(ChronoDuration::hours(value)),
+        _ => Err(format!("invalid delay unit in '{}', use s, m or h", text)),
+    }
+}
+
+// Accepts "HH:MM" (the next time the clock reads that) or a full "YYYY-MM-DDTHH:MM"
+pub fn parse_start_time(text: &str, now: DateTime<Local>) -> Result<DateTime<Local>, String> {
+    let text = text.trim();
+    if let Ok(time) = NaiveTime::parse_from_str(text, "%H:%M") {
+        let mut date = now.date_naive();
+        if time <= now.time() {
+            date = date.succ_opt().ok_or("date out of range")?;
+        }
+        return Local.from_local_datetime(&date.and_time(time)).earliest().ok_or_else(|| format!("'{}' does not exist in local time", text));
+    }
+    let dt = NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M"))
+        .map_err(|_| format!("invalid time '{}', use HH:MM or YYYY-MM-DDTHH:MM", text))?;
+    Local.from_local_datetime(&dt).earliest().ok_or_else(|| format!("'{}' does not exist in local time", text))
+}
+
+// Each --schedule starts a new entry; --in/--at/--every apply to the most recent one
+pub fn parse_args(args: &[String]) -> Result<Vec<ScheduledRun>, String> {
+    let now = Local::now();
+    let mut runs: Vec<ScheduledRun> = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--schedule" => {
+                let name = value()?;
+                let target = ScheduleTarget::from_name(name).ok_or_else(|| format!("unknown target '{}'", name))?;
+                runs.push(ScheduledRun { target, start_at: now, repeat_hours: None });
+            }
+            "--in" | "--at" | "--every" => {
+                let text = value()?;
+                let run = runs.last_mut().ok_or_else(|| format!("{} must follow --schedule", arg))?;
+                match arg.as_str() {
+                    "--in" => run.start_at = now + parse_delay(text)?,
+                    "--at" => run.start_at = parse_start_time(text, now)?,
+                    _ => {
+                        let hours: u32 = text.parse().map_err(|_| format!("invalid repeat interval '{}'", text))?;
+                        if hours == 0 {
+                            return Err("--every must be at least 1 hour".to_string());
+                        }
+                        run.repeat_hours = Some(hours);
+                    }
+                }
+            }
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    Ok(runs)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum StartMode {
+    Delay,
+    AtTime,
+}
+
+pub struct Scheduler {
+    queue: Vec<ScheduledRun>,
+    new_target: ScheduleTarget,
+    new_mode: StartMode,
+    new_delay: String,
+    new_time: String,
+    new_repeat: bool,
+    new_repeat_hours: u32,
+    error: Option<String>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self {
+            queue: Vec::new(),
+            new_target: ScheduleTarget::SystemPreset,
+            new_mode: StartMode::Delay,
+            new_delay: "30m".to_string(),
+            new_time: "22:00".to_string(),
+            new_repeat: false,
+            new_repeat_hours: 24,
+            error: None,
+        }
+    }
+}
+
+impl Scheduler {
+    pub fn enqueue(&mut self, run: ScheduledRun) {
+        self.queue.push(run);
+        self.queue.sort_by_key(|r| r.start_at);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    // Pops the earliest run once it is due. Runs that come due while another test is active wait
+    // for it to finish rather than starting on top of it.
+    pub fn take_due(&mut self, busy: bool) -> Option<ScheduleTarget> {
+        let now = Local::now();
+        if busy || self.queue.first().is_none_or(|r| r.start_at > now) {
+            return None;
+        }
+        let mut run = self.queue.remove(0);
+        let target = run.target;
+        if let Some(hours) = run.repeat_hours {
+            // Skip any repeats missed while the previous run was still going
+            while run.start_at <= now {
+                run.start_at += ChronoDuration::hours(hours as i64);
+            }
+            self.enqueue(run);
+        }
+        Some(target)
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        ui.label("Queue a test to start after a delay or at a set time, e.g. to begin an overnight burn-in after everyone leaves. Runs that come due while another test is active start as soon as it finishes.");
+        ui.add_space(10.0);
+        ui.horizontal(|ui| {
+            ui.label("Test:");
+            egui::ComboBox::from_id_salt("schedule_target")
+                .selected_text(self.new_target.label())
+                .show_ui(ui, |ui| {
+                    for target in ScheduleTarget::ALL {
+                        ui.selectable_value(&mut self.new_target, target, target.label());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.new_mode, StartMode::Delay, "Start in:");
+            ui.add_enabled(
+                self.new_mode == StartMode::Delay,
+                egui::TextEdit::singleline(&mut self.new_delay).desired_width(80.0).hint_text("e.g. 90s, 15m, 2h"),
+            );
+            ui.radio_value(&mut self.new_mode, StartMode::AtTime, "Start at:");
+            ui.add_enabled(
+                self.new_mode == StartMode::AtTime,
+                egui::TextEdit::singleline(&mut self.new_time).desired_width(120.0).hint_text("HH:MM"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.new_repeat, "Repeat every");
+            ui.add_enabled(self.new_repeat, egui::DragValue::new(&mut self.new_repeat_hours).range(1..=168));
+            ui.label("hours");
+        });
+        if ui.button("Add to Queue").clicked() {
+            let now = Local::now();
+            let start_at = match self.new_mode {
+                StartMode::Delay => parse_delay(&self.new_delay).map(|d| now + d),
+                StartMode::AtTime => parse_start_time(&self.new_time, now),
+            };
+            match start_at {
+                Ok(start_at) => {
+                    self.enqueue(ScheduledRun {
+                        target: self.new_target,
+                        start_at,
+                        repeat_hours: self.new_repeat.then_some(self.new_repeat_hours),
+                    });
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+        if let Some(err) = &self.error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+        ui.add_space(10.0);
+        if self.queue.is_empty() {
+            ui.label("No runs scheduled.");
+            return;
+        }
+        let now = Local::now();
+        let mut remove = None;
+        egui::Grid::new("schedule_queue_table").striped(true).show(ui, |ui| {
+            ui.label("Test"); ui.label("Starts"); ui.label("In"); ui.label("Repeat"); ui.label(""); ui.end_row();
+            for (i, run) in self.queue.iter().enumerate() {
+                ui.label(run.target.label());
+                ui.label(run.start_at.format("%Y-%m-%d %H:%M:%S").to_string());
+                let remaining = (run.start_at - now).num_seconds();
+                if remaining > 0 {
+                    ui.label(format!("{:02}:{:02}:{:02}", remaining / 3600, remaining / 60 % 60, remaining % 60));
+                } else if busy {
+                    ui.colored_label(egui::Color32::YELLOW, "Waiting for running test");
+                } else {
+                    ui.label("Starting...");
+                }
+                ui.label(run.repeat_hours.map(|h| format!("Every {} h", h)).unwrap_or_else(|| "Once".to_string()));
+                if ui.button("Remove").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = remove {
+            self.queue.remove(i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_delay_accepts_units_and_bare_minutes() {
+        assert_eq!(parse_delay("90s"), Ok(ChronoDuration::seconds(90)));
+        assert_eq!(parse_delay("15m"), Ok(ChronoDuration::minutes(15)));
+        assert_eq!(parse_delay(" 2H "), Ok(ChronoDuration::hours(2)));
+        assert_eq!(parse_delay("45"), Ok(ChronoDuration::minutes(45)));
+        assert_eq!(parse_delay("0s"), Ok(ChronoDuration::zero()));
+    }
+
+    #[test]
+    fn parse_delay_rejects_bad_input() {
+        for text in ["", "m", "-5m", "5d", "1.5h", "abc"] {
+            assert!(parse_delay(text).is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn parse_start_time_picks_the_next_matching_clock_time() {
+        let now = Local.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let later = parse_start_time("22:00", now).unwrap();
+        assert_eq!(later, Local.with_ymd_and_hms(2026, 3, 10, 22, 0, 0).unwrap());
+        let tomorrow = parse_start_time("08:30", now).unwrap();
+        assert_eq!(tomorrow, Local.with_ymd_and_hms(2026, 3, 11, 8, 30, 0).unwrap());
+        let full = parse_start_time("2026-04-01T06:15", now).unwrap();
+        assert_eq!(full, Local.with_ymd_and_hms(2026, 4, 1, 6, 15, 0).unwrap());
+        assert!(parse_start_time("25:00", now).is_err());
+    }
+
+    #[test]
+    fn parse_args_applies_options_to_the_latest_schedule() {
+        let runs = parse_args(&args(&["--schedule", "cpu", "--in", "0s", "--every", "24", "--schedule", "score"])).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].target, ScheduleTarget::Cpu);
+        assert_eq!(runs[0].repeat_hours, Some(24));
+        assert_eq!(runs[1].target, ScheduleTarget::Score);
+        assert_eq!(runs[1].repeat_hours, None);
+    }
+
+    #[test]
+    fn parse_args_rejects_misplaced_or_invalid_options() {
+        assert!(parse_args(&args(&["--in", "5m"])).is_err());
+        assert!(parse_args(&args(&["--schedule", "gpu"])).is_err());
+        assert!(parse_args(&args(&["--schedule", "cpu", "--every", "0"])).is_err());
+        assert!(parse_args(&args(&["--schedule"])).is_err());
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+    }
+}
@@ -0,0 +1,579 @@
+use eframe::egui;
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::bench::{self, BenchSettings, BenchStats};
+use crate::app::seed;
+
+// Bump whenever a kernel, its inputs or a reference constant changes. Scores are only comparable
+// between logs with the same version; the analyzer warns about the rest.
+pub const SCORE_VERSION: u32 = 3;
+// A score of 1000 means "each thread as fast as the reference machine's one thread". The constants
+// below are the median single-thread ops/sec of five 5 s runs after a 3 s warm-up, measured on:
+//   Intel Xeon KVM guest, 1 logical CPU, Linux 6.18, Stressor built with rustc 1.95.0 --release
+// The multi-thread score is per thread: total ops/sec divided by the number of workers (one per
+// logical CPU), against the same references. It shows how well throughput holds up with every
+// thread busy (shared caches, SMT, power limits), not how many threads the machine has; the total
+// ops/sec stays in the log. To recalibrate, run the score on the reference machine, copy the
+// single-thread summary ops_per_sec of each workload here and bump SCORE_VERSION.
+pub const REFERENCE_SCORE: f64 = 1000.0;
+const REFERENCE_INTEGER_OPS: f64 = 1_140_000.0;
+const REFERENCE_FLOAT_OPS: f64 = 23_900.0;
+const REFERENCE_MEMORY_OPS: f64 = 7_600.0;
+
+// 16 MiB of u32 links, larger than most L2/L3 slices so the chase mostly misses cache
+const CHASE_ENTRIES: usize = 4 * 1024 * 1024;
+const CHASE_STEPS: usize = 1024;
+const MATRIX_N: usize = 32;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScoreWorkload {
+    Integer,
+    FloatingPoint,
+    Memory,
+}
+
+impl ScoreWorkload {
+    pub const ALL: [ScoreWorkload; 3] = [ScoreWorkload::Integer, ScoreWorkload::FloatingPoint, ScoreWorkload::Memory];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreWorkload::Integer => "integer",
+            ScoreWorkload::FloatingPoint => "float",
+            ScoreWorkload::Memory => "memory",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoreWorkload::Integer => "Integer",
+            ScoreWorkload::FloatingPoint => "Floating Point",
+            ScoreWorkload::Memory => "Memory Latency",
+        }
+    }
+
+    fn reference_ops(&self) -> f64 {
+        match self {
+            ScoreWorkload::Integer => REFERENCE_INTEGER_OPS,
+            ScoreWorkload::FloatingPoint => REFERENCE_FLOAT_OPS,
+            ScoreWorkload::Memory => REFERENCE_MEMORY_OPS,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScoreMode {
+    SingleThread,
+    MultiThread,
+}
+
+impl ScoreMode {
+    pub const ALL: [ScoreMode; 2] = [ScoreMode::SingleThread, ScoreMode::MultiThread];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreMode::SingleThread => "st",
+            ScoreMode::MultiThread => "mt",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoreMode::SingleThread => "Single-Thread",
+            ScoreMode::MultiThread => "Multi-Thread (per thread)",
+        }
+    }
+}
+
+// ops_per_sec is the total over all workers; the score compares what each of them achieved
+pub fn score_for(workload: ScoreWorkload, ops_per_sec: f64, workers: usize) -> f64 {
+    REFERENCE_SCORE * ops_per_sec / workers.max(1) as f64 / workload.reference_ops()
+}
+
+// Same loop as the CPU stress test, with the multiplier fed through black_box so it can't be folded
+fn integer_op(seed: u64) -> u64 {
+    let seed = std::hint::black_box(seed);
+    let mut acc = 1u64;
+    for i in 1..1000 {
+        acc = acc.wrapping_mul(i ^ seed);
+    }
+    acc
+}
+
+fn float_op(a: &[f64], b: &[f64], c: &mut [f64]) {
+    let (a, b) = (std::hint::black_box(a), std::hint::black_box(b));
+    for i in 0..MATRIX_N {
+        for j in 0..MATRIX_N {
+            let mut sum = 0.0;
+            for k in 0..MATRIX_N {
+                sum += a[i * MATRIX_N + k] * b[k * MATRIX_N + j];
+            }
+            c[i * MATRIX_N + j] = sum;
+        }
+    }
+    std::hint::black_box(c);
+}
+
+fn memory_op(table: &[u32], start: u32) -> u32 {
+    let mut idx = start;
+    for _ in 0..CHASE_STEPS {
+        idx = table[idx as usize];
+    }
+    idx
+}
+
+// One cycle through every entry, always built from the same seed so every machine chases the same path
+fn chase_table() -> Vec<u32> {
+    let mut table: Vec<u32> = (0..CHASE_ENTRIES as u32).collect();
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    for i in (1..CHASE_ENTRIES).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let j = (state % i as u64) as usize;
+        table.swap(i, j);
+    }
+    table
+}
+
+fn run_workload(workload: ScoreWorkload, table: &[u32], tid: usize, running: &AtomicBool, end: Instant, ops: &AtomicU64) {
+    let a: Vec<f64> = (0..MATRIX_N * MATRIX_N).map(|i| (i % 17) as f64 * 0.25 + 1.0).collect();
+    let b: Vec<f64> = (0..MATRIX_N * MATRIX_N).map(|i| (i % 13) as f64 * 0.5 - 2.0).collect();
+    let mut c = vec![0.0; MATRIX_N * MATRIX_N];
+    let mut idx = (tid * 7919 % CHASE_ENTRIES) as u32;
+    let mut n = 0u64;
+    while running.load(Ordering::Relaxed) && Instant::now() < end {
+        // Check the clock every few ops; the memory kernel alone is ~100 µs per op
+        for _ in 0..16 {
+            match workload {
+                ScoreWorkload::Integer => {
+                    std::hint::black_box(integer_op(n));
+                }
+                ScoreWorkload::FloatingPoint => float_op(&a, &b, &mut c),
+                ScoreWorkload::Memory => idx = std::hint::black_box(memory_op(table, idx)),
+            }
+            n += 1;
+        }
+        ops.store(n, Ordering::Relaxed);
+    }
+    ops.store(n, Ordering::Relaxed);
+}
+
+// Runs one workload on `workers` threads for `secs`; None if the run was stopped partway, since a
+// shorter measurement isn't comparable with the others
+fn measure(workload: ScoreWorkload, workers: usize, secs: u32, table: &Arc<Vec<u32>>, running: &Arc<AtomicBool>, watch: &watchdog::Watch) -> Option<f64> {
+    let start = Instant::now();
+    let end = start + Duration::from_secs(secs as u64);
+    let counters: Arc<Vec<AtomicU64>> = Arc::new((0..workers).map(|_| AtomicU64::new(0)).collect());
+    let handles: Vec<_> = (0..workers)
+        .map(|tid| {
+            let (running, counters, table) = (running.clone(), counters.clone(), table.clone());
+            thread::spawn(move || run_workload(workload, &table, tid, &running, end, &counters[tid]))
+        })
+        .collect();
+    while running.load(Ordering::SeqCst) && Instant::now() < end {
+        thread::sleep(Duration::from_millis(200));
+        watch.heartbeat();
+    }
+    for handle in handles {
+        let _ = handle.join();
+    }
+    if !running.load(Ordering::SeqCst) {
+        return None;
+    }
+    let total: u64 = counters.iter().map(|c| c.load(Ordering::SeqCst)).sum();
+    Some(total as f64 / start.elapsed().as_secs_f64().max(1e-6))
+}
+
+// Two-sided 95% Student's t critical values for 1..=30 degrees of freedom
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228,
+    2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086,
+    2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+];
+
+// Mean with a 95% confidence interval; a single run has no interval
+pub fn confidence_interval(values: &[f64]) -> (f64, f64, f64) {
+    let n = values.len();
+    if n == 0 {
+        return (0.0, 0.0, 0.0);
+    }
+    let mean = values.iter().sum::<f64>() / n as f64;
+    if n == 1 {
+        return (mean, mean, mean);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+    let t = T_95.get(n - 2).copied().unwrap_or(1.96);
+    let half = t * variance.sqrt() / (n as f64).sqrt();
+    (mean, mean - half, mean + half)
+}
+
+#[derive(Clone, Debug)]
+pub struct ScoreSummary {
+    pub mode: ScoreMode,
+    // None for the composite (geometric mean of the workload scores)
+    pub workload: Option<ScoreWorkload>,
+    pub ops_per_sec: Option<f64>,
+    pub score: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+    // None for logs written before the benchmark harness
+    pub stats: Option<BenchStats>,
+}
+
+impl ScoreSummary {
+    pub fn workload_name(&self) -> &'static str {
+        self.workload.map(|w| w.name()).unwrap_or("composite")
+    }
+
+    pub fn workload_label(&self) -> &'static str {
+        self.workload.map(|w| w.label()).unwrap_or("Composite")
+    }
+}
+
+// Per-run scores of each workload in one mode, in ScoreWorkload::ALL order. Outlier runs are left
+// out of the score and its interval but still counted in the stats.
+fn summarize_mode(mode: ScoreMode, runs: &[Vec<(f64, f64)>], stable_cv_percent: f64) -> Vec<ScoreSummary> {
+    let mut summaries = Vec::new();
+    for (w, workload) in ScoreWorkload::ALL.iter().enumerate() {
+        let ops: Vec<f64> = runs.iter().filter_map(|r| r.get(w).map(|&(ops, _)| ops)).collect();
+        let scores: Vec<f64> = runs.iter().filter_map(|r| r.get(w).map(|&(_, score)| score)).collect();
+        if scores.is_empty() {
+            continue;
+        }
+        let outliers = bench::outliers(&scores);
+        let kept_ops: Vec<f64> = ops.iter().zip(&outliers).filter(|&(_, &out)| !out).map(|(&o, _)| o).collect();
+        let (score, ci_low, ci_high) = confidence_interval(&bench::reject_outliers(&scores));
+        summaries.push(ScoreSummary {
+            mode,
+            workload: Some(*workload),
+            ops_per_sec: Some(kept_ops.iter().sum::<f64>() / kept_ops.len().max(1) as f64),
+            score,
+            ci_low,
+            ci_high,
+            stats: BenchStats::from_samples(&scores, stable_cv_percent),
+        });
+    }
+    // Only complete runs count towards the composite
+    let composites: Vec<f64> = runs
+        .iter()
+        .filter(|r| r.len() == ScoreWorkload::ALL.len())
+        .map(|r| (r.iter().map(|&(_, s)| s.max(1e-9).ln()).sum::<f64>() / r.len() as f64).exp())
+        .collect();
+    if !composites.is_empty() {
+        let (score, ci_low, ci_high) = confidence_interval(&bench::reject_outliers(&composites));
+        let stats = BenchStats::from_samples(&composites, stable_cv_percent);
+        summaries.push(ScoreSummary { mode, workload: None, ops_per_sec: None, score, ci_low, ci_high, stats });
+    }
+    summaries
+}
+
+pub struct ScoreBench {
+    running: Arc<AtomicBool>,
+    runs: Arc<Mutex<u32>>,
+    secs_per_run: Arc<Mutex<u32>>,
+    warmup_secs: Arc<Mutex<u32>>,
+    stable_cv_percent: f64,
+    progress: Arc<Mutex<String>>,
+    results: Arc<Mutex<Vec<ScoreSummary>>>,
+    log_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl ScoreBench {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            runs: Arc::new(Mutex::new(config.score_runs.max(1))),
+            secs_per_run: Arc::new(Mutex::new(config.score_run_secs.max(1))),
+            warmup_secs: Arc::new(Mutex::new(BenchSettings::from_config(config).warmup_secs)),
+            stable_cv_percent: BenchSettings::from_config(config).stable_cv_percent,
+            progress: Arc::new(Mutex::new(String::new())),
+            results: Arc::new(Mutex::new(Vec::new())),
+            log_path: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl ScoreBench {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    // Every workload runs once per mode per repetition
+    fn measurements(&self) -> u32 {
+        *self.runs.lock().unwrap() * (ScoreWorkload::ALL.len() * ScoreMode::ALL.len()) as u32
+    }
+
+    // Including one warm-up per workload and mode, which isn't measured
+    pub fn duration_secs(&self) -> u32 {
+        let warmups = (ScoreWorkload::ALL.len() * ScoreMode::ALL.len()) as u32 * *self.warmup_secs.lock().unwrap();
+        self.measurements() * *self.secs_per_run.lock().unwrap() + warmups
+    }
+
+    // Spreads a total duration (scheduler and control API) over the configured repetitions
+    pub fn set_duration_secs(&self, secs: u32) {
+        *self.secs_per_run.lock().unwrap() = (secs / self.measurements().max(1)).max(1);
+    }
+
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.log_path.lock().unwrap().clone()
+    }
+
+    pub fn start(&self, ctx: &egui::Context, dev_mode: bool) {
+        let running = self.running.clone();
+        let runs = *self.runs.lock().unwrap();
+        let secs_per_run = *self.secs_per_run.lock().unwrap();
+        let warmup_secs = *self.warmup_secs.lock().unwrap();
+        let stable_cv_percent = self.stable_cv_percent;
+        let duration = self.duration_secs();
+        let progress = self.progress.clone();
+        let results = self.results.clone();
+        let log_path = self.log_path.clone();
+        let ctx = ctx.clone();
+        running.store(true, Ordering::SeqCst);
+        results.lock().unwrap().clear();
+        let threads = num_cpus::get();
+        let hash = seed::file_hash();
+        let date = Local::now().format("%Y%m%d_%H%M%S");
+        let log_dir = crate::app::log_dir(dev_mode);
+        let _ = std::fs::create_dir_all(&log_dir);
+        let log_file_name = log_dir.join(format!("score_{}_{}_v{}_runs{}.csv", hash, date, SCORE_VERSION, runs));
+        *log_path.lock().unwrap() = Some(log_file_name.clone());
+        if dev_mode {
+            println!("[DEV] Starting benchmark score: runs={}, secs_per_run={}, warmup_secs={}, threads={}", runs, secs_per_run, warmup_secs, threads);
+        }
+        thread::spawn(move || {
+            let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).unwrap();
+            writeln!(log_file, "kind,mode,workload,run,ops_per_sec,score,ci_low,ci_high,median,stddev,cv_percent,rejected,stable").unwrap();
+            inventory::get().write_csv_comments(&mut log_file);
+            writeln!(log_file, "# Multi-thread workers: {}", threads).ok();
+            let journal = RunJournal::begin(dev_mode, "score", Some(&log_file_name));
+            let watch = watchdog::watch("score", duration as u64, {
+                let running = running.clone();
+                move || running.store(false, Ordering::SeqCst)
+            });
+            *progress.lock().unwrap() = "Preparing memory workload...".to_string();
+            ctx.request_repaint();
+            let table = Arc::new(chase_table());
+            for mode in ScoreMode::ALL {
+                let workers = match mode {
+                    ScoreMode::SingleThread => 1,
+                    ScoreMode::MultiThread => threads,
+                };
+                // Lets clocks, caches and the scheduler settle; these results are thrown away
+                if warmup_secs > 0 {
+                    for workload in ScoreWorkload::ALL {
+                        if !running.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        *progress.lock().unwrap() = format!("{} {} (warm-up)...", mode.label(), workload.label());
+                        ctx.request_repaint();
+                        measure(workload, workers, warmup_secs, &table, &running, &watch);
+                    }
+                }
+                let mut mode_runs: Vec<Vec<(f64, f64)>> = Vec::new();
+                'runs: for run in 1..=runs {
+                    let mut run_scores = Vec::new();
+                    for workload in ScoreWorkload::ALL {
+                        if !running.load(Ordering::SeqCst) {
+                            break 'runs;
+                        }
+                        *progress.lock().unwrap() = format!("{} {} (run {} of {})...", mode.label(), workload.label(), run, runs);
+                        ctx.request_repaint();
+                        let Some(ops_per_sec) = measure(workload, workers, secs_per_run, &table, &running, &watch) else {
+                            break 'runs;
+                        };
+                        let score = score_for(workload, ops_per_sec, workers);
+                        writeln!(log_file, "run,{},{},{},{},{},,,,,,,", mode.name(), workload.name(), run, ops_per_sec, score).ok();
+                        run_scores.push((ops_per_sec, score));
+                    }
+                    mode_runs.push(run_scores);
+                    let mut all = results.lock().unwrap();
+                    all.retain(|s| s.mode != mode);
+                    all.extend(summarize_mode(mode, &mode_runs, stable_cv_percent));
+                }
+                for s in results.lock().unwrap().iter().filter(|s| s.mode == mode) {
+                    writeln!(
+                        log_file,
+                        "summary,{},{},,{},{},{},{},{}",
+                        s.mode.name(),
+                        s.workload_name(),
+                        s.ops_per_sec.map(|o| o.to_string()).unwrap_or_default(),
+                        s.score,
+                        s.ci_low,
+                        s.ci_high,
+                        s.stats.as_ref().map(|st| format!("{},{},{},{},{}", st.median, st.stddev, st.cv_percent, st.rejected, st.stable)).unwrap_or_else(|| ",,,,".to_string())
+                    ).ok();
+                }
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+            if let Some(journal) = journal {
+                journal.finish();
+            }
+            running.store(false, Ordering::SeqCst);
+            progress.lock().unwrap().clear();
+            ctx.request_repaint();
+            if dev_mode {
+                println!("[DEV] Benchmark score finished: {}", log_file_name.display());
+            }
+        });
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
+        ui.heading("Benchmark Score");
+        ui.add_space(10.0);
+        ui.label("Runs fixed integer, floating-point and memory-latency workloads on one thread and on all threads, and scores them against a reference machine (1000 = reference). The multi-thread score is per thread, so it shows how well each thread keeps up when all of them are busy. Unlike the stress tests, scores don't depend on any settings and can be compared across machines.");
+        ui.add_space(10.0);
+        let mut runs = *self.runs.lock().unwrap();
+        let mut secs = *self.secs_per_run.lock().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Repetitions:");
+            if ui.add(egui::DragValue::new(&mut runs).range(1..=30)).changed() {
+                *self.runs.lock().unwrap() = runs;
+            }
+            ui.label("Seconds per Workload:");
+            if ui.add(egui::DragValue::new(&mut secs).range(1..=60)).changed() {
+                *self.secs_per_run.lock().unwrap() = secs;
+            }
+            let mut warmup = *self.warmup_secs.lock().unwrap();
+            ui.label("Warm-up (s):");
+            if ui.add(egui::DragValue::new(&mut warmup).range(0..=60)).changed() {
+                *self.warmup_secs.lock().unwrap() = warmup;
+            }
+        });
+        ui.label(format!("Estimated duration: {} s | Score version: v{}", self.duration_secs(), SCORE_VERSION));
+        if runs < 3 {
+            ui.colored_label(egui::Color32::YELLOW, "At least 3 repetitions are needed for a useful confidence interval.");
+        }
+        ui.add_space(10.0);
+        if ui.button(if self.is_running() { "Stop Benchmark" } else { "Start Benchmark" }).clicked() {
+            if self.is_running() {
+                self.stop();
+            } else {
+                self.start(ctx, dev_mode);
+            }
+        }
+        if self.is_running() {
+            ui.colored_label(egui::Color32::YELLOW, self.progress.lock().unwrap().clone());
+        } else if let Some(log_path) = &*self.log_path.lock().unwrap() {
+            ui.label(format!("Log saved to: {}", log_path.display()));
+        }
+        let results = self.results.lock().unwrap().clone();
+        if !results.is_empty() {
+            ui.add_space(10.0);
+            ui_summary_table(ui, "score_table", &results);
+        }
+    }
+}
+
+pub fn ui_summary_table(ui: &mut egui::Ui, id: &str, summaries: &[ScoreSummary]) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Mode"); ui.label("Workload"); ui.label("Score"); ui.label("95% CI"); ui.label("Median"); ui.label("CV"); ui.label("Ops/s"); ui.label("Result"); ui.end_row();
+        for s in summaries {
+            ui.label(s.mode.label());
+            if s.workload.is_none() {
+                ui.strong(s.workload_label());
+                ui.strong(format!("{:.0}", s.score));
+            } else {
+                ui.label(s.workload_label());
+                ui.label(format!("{:.0}", s.score));
+            }
+            ui.label(format!("{:.0} – {:.0} (±{:.1}%)", s.ci_low, s.ci_high, (s.ci_high - s.score) / s.score.max(1e-9) * 100.0));
+            match &s.stats {
+                Some(st) => {
+                    ui.label(format!("{:.0}", st.median));
+                    ui.label(format!("{:.2}%", st.cv_percent));
+                }
+                None => {
+                    ui.label("N/A");
+                    ui.label("N/A");
+                }
+            }
+            ui.label(s.ops_per_sec.map(|o| format!("{:.0}", o)).unwrap_or_default());
+            match &s.stats {
+                Some(st) if st.rejected > 0 => {
+                    ui.horizontal(|ui| {
+                        bench::ui_stable_label(ui, st);
+                        ui.label(format!("({} outlier(s) dropped)", st.rejected));
+                    });
+                }
+                Some(st) => bench::ui_stable_label(ui, st),
+                None => {
+                    ui.label("");
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn confidence_interval_uses_the_t_distribution() {
+        let (mean, low, high) = confidence_interval(&[10.0, 12.0, 14.0]);
+        // stddev 2, n 3, t(2 df) = 4.303
+        let half = 4.303 * 2.0 / 3f64.sqrt();
+        assert!(close(mean, 12.0));
+        assert!(close(low, 12.0 - half));
+        assert!(close(high, 12.0 + half));
+    }
+
+    #[test]
+    fn confidence_interval_narrows_to_the_normal_limit_for_many_runs() {
+        let values: Vec<f64> = (0..40).map(|i| if i % 2 == 0 { 9.0 } else { 11.0 }).collect();
+        let (mean, low, high) = confidence_interval(&values);
+        let stddev = (40.0f64 / 39.0).sqrt();
+        assert!(close(mean, 10.0));
+        assert!(close(high - mean, 1.96 * stddev / 40f64.sqrt()));
+        assert!(close(mean - low, high - mean));
+    }
+
+    #[test]
+    fn confidence_interval_of_one_or_no_runs_is_a_point() {
+        assert_eq!(confidence_interval(&[7.5]), (7.5, 7.5, 7.5));
+        assert_eq!(confidence_interval(&[]), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn reference_throughput_scores_exactly_the_reference_score() {
+        for workload in ScoreWorkload::ALL {
+            assert!(close(score_for(workload, workload.reference_ops(), 1), REFERENCE_SCORE));
+            assert!(close(score_for(workload, 2.0 * workload.reference_ops(), 1), 2.0 * REFERENCE_SCORE));
+        }
+    }
+
+    #[test]
+    fn multi_thread_scores_are_per_thread() {
+        for workload in ScoreWorkload::ALL {
+            // Eight threads each at reference speed score the same as one
+            assert!(close(score_for(workload, 8.0 * workload.reference_ops(), 8), REFERENCE_SCORE));
+            assert!(close(score_for(workload, 4.0 * workload.reference_ops(), 8), REFERENCE_SCORE / 2.0));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+// Run seed shared by every workload. Input data and access patterns are derived from it so a run
+// can be replayed exactly, and it's written into each result log (see inventory::write_csv_comments).
+static SEED: AtomicU64 = AtomicU64::new(0);
+static CHOSEN: AtomicBool = AtomicBool::new(false);
+// Counts filename hashes handed out this session, so consecutive logs still get different ones
+static FILE_HASHES: AtomicU64 = AtomicU64::new(0);
+
+// 0 means "pick one": the seed is still random, but it's recorded so the run can be replayed
+fn resolve(seed: u64) -> u64 {
+    if seed != 0 { seed } else { thread_rng().gen_range(1..=u32::MAX as u64) }
+}
+
+// Sets the seed unless one was already chosen, so --seed on the command line wins over SEED in the ini
+pub fn init(seed: u64) {
+    if !CHOSEN.swap(true, Ordering::SeqCst) {
+        SEED.store(resolve(seed), Ordering::SeqCst);
+    }
+}
+
+pub fn set(seed: u64) {
+    CHOSEN.store(true, Ordering::SeqCst);
+    SEED.store(resolve(seed), Ordering::SeqCst);
+    FILE_HASHES.store(0, Ordering::SeqCst);
+}
+
+pub fn current() -> u64 {
+    init(0);
+    SEED.load(Ordering::SeqCst)
+}
+
+// splitmix64 finalizer, spreads nearby inputs (thread 0, thread 1, ...) over unrelated seeds
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// FNV-1a, spelled out so stream seeds don't depend on std's hasher staying the same
+fn stream_id(stream: &str) -> u64 {
+    stream.bytes().fold(0xCBF2_9CE4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01B3))
+}
+
+// Independent generator per workload and thread. Take `seed` from current() once when the run
+// starts so changing the seed mid-run doesn't split one run across two seeds.
+pub fn stream_rng(seed: u64, stream: &str, index: u64) -> StdRng {
+    StdRng::seed_from_u64(mix(seed ^ mix(stream_id(stream) ^ mix(index))))
+}
+
+// 4-digit tag used in log filenames, a replayed session names its logs the same way
+pub fn file_hash() -> u16 {
+    let n = FILE_HASHES.fetch_add(1, Ordering::SeqCst);
+    1000 + (mix(current() ^ mix(stream_id("file_hash") ^ n)) % 9000) as u16
+}
+
+// Seed stored in a result log's inventory lines, None for logs written before seeds existed
+pub fn from_entries(entries: &[(String, String)]) -> Option<u64> {
+    entries.iter().find(|(k, _)| k == "Seed").and_then(|(_, v)| v.trim().parse().ok())
+}
+
+// Seed row for the Stress Test tab; changes apply to the next run
+pub fn ui_seed(ui: &mut egui::Ui, running: bool) {
+    ui.horizontal(|ui| {
+        let mut seed = current();
+        ui.label("Seed:");
+        let changed = ui.add_enabled(!running, egui::DragValue::new(&mut seed).range(1..=u64::MAX)).changed();
+        if changed {
+            set(seed);
+        }
+        if ui.add_enabled(!running, egui::Button::new("🎲 New")).on_hover_text("Pick a new random seed").clicked() {
+            set(0);
+        }
+        ui.label("Input data and access patterns follow this seed; reuse it to replay a run exactly.");
+    });
+}
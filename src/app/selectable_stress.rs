@@ -6,15 +6,25 @@ use chrono::Local;
 use std::fs::OpenOptions;
 use std::io::Write;
 use crate::app::config::Config;
-use matrix_stress::MatrixStressConfig;
-use compression_stress::CompressionStressConfig;
+use crate::app::topology::{self, PlacementPolicy};
+use crate::app::load_profile::{self, LoadProfile};
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::bench::{self, BenchSettings, BenchStats};
+use matrix_stress::{MatrixElement, MatrixKernel, MatrixStressConfig, MatrixTotals};
+use compression_stress::{CompressionCodec, CompressionData, CompressionStressConfig, CompressionTotals};
 use ram_stress::RamStressConfig;
 use tightloop_stress::TightLoopStressConfig;
+use crypto_stress::{CryptoAlgorithm, CryptoStressConfig, CryptoTotals};
+use micro_stress::{MicroKernel, MicroStressConfig, MicroTotals};
 
-mod matrix_stress;
-mod compression_stress;
-mod ram_stress;
+pub mod matrix_stress;
+pub mod compression_stress;
+pub mod ram_stress;
 mod tightloop_stress;
+pub mod crypto_stress;
+pub mod micro_stress;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CpuWorkloadKind {
@@ -22,6 +32,8 @@ pub enum CpuWorkloadKind {
     MatrixMultiplication,
     Compression,
     RandomMemoryAccess,
+    Crypto,
+    Micro,
 }
 
 impl CpuWorkloadKind {
@@ -31,6 +43,8 @@ impl CpuWorkloadKind {
             CpuWorkloadKind::MatrixMultiplication,
             CpuWorkloadKind::Compression,
             CpuWorkloadKind::RandomMemoryAccess,
+            CpuWorkloadKind::Crypto,
+            CpuWorkloadKind::Micro,
         ]
     }
     pub fn label(self) -> &'static str {
@@ -39,6 +53,206 @@ impl CpuWorkloadKind {
             CpuWorkloadKind::MatrixMultiplication => "Matrix Multiplication",
             CpuWorkloadKind::Compression => "Compression",
             CpuWorkloadKind::RandomMemoryAccess => "Random Memory Access",
+            CpuWorkloadKind::Crypto => "Cryptography & Hashing",
+            CpuWorkloadKind::Micro => "Micro-workloads (execution units)",
+        }
+    }
+    // Short form used in log file names
+    pub fn tag(self) -> &'static str {
+        match self {
+            CpuWorkloadKind::TightLoop => "tightloop",
+            CpuWorkloadKind::MatrixMultiplication => "matrix",
+            CpuWorkloadKind::Compression => "compression",
+            CpuWorkloadKind::RandomMemoryAccess => "ram",
+            CpuWorkloadKind::Crypto => "crypto",
+            CpuWorkloadKind::Micro => "micro",
+        }
+    }
+}
+
+// The selected workload for one run, holding its config with the placement and load profile
+// applied and the totals its runs add to
+enum Workload {
+    TightLoop(tightloop_stress::TightLoopStress),
+    Matrix(matrix_stress::MatrixStress),
+    Compression(compression_stress::CompressionStress),
+    Ram(ram_stress::RamStress),
+    Crypto(crypto_stress::CryptoStress),
+    Micro(micro_stress::MicroStress),
+}
+
+impl Workload {
+    fn new(stress: &SelectableStress, kind: CpuWorkloadKind, pinned_cpus: &[usize]) -> Self {
+        let load_profile = stress.load_profile;
+        // A placement policy decides the thread count, one worker per resolved CPU
+        let threads = |configured: usize| if pinned_cpus.is_empty() { configured } else { pinned_cpus.len() };
+        let pinned_cpus = pinned_cpus.to_vec();
+        match kind {
+            CpuWorkloadKind::TightLoop => Workload::TightLoop(tightloop_stress::TightLoopStress {
+                config: TightLoopStressConfig { threads: threads(stress.tightloop_config.threads), pinned_cpus, load_profile, ..stress.tightloop_config.clone() },
+            }),
+            CpuWorkloadKind::MatrixMultiplication => Workload::Matrix(matrix_stress::MatrixStress {
+                config: MatrixStressConfig { threads: threads(stress.matrix_config.threads), pinned_cpus, load_profile, ..stress.matrix_config.clone() },
+                totals: Default::default(),
+            }),
+            CpuWorkloadKind::Compression => Workload::Compression(compression_stress::CompressionStress {
+                config: CompressionStressConfig { threads: threads(stress.compression_config.threads), pinned_cpus, load_profile, ..stress.compression_config.clone() },
+                totals: Default::default(),
+            }),
+            CpuWorkloadKind::RandomMemoryAccess => Workload::Ram(ram_stress::RamStress {
+                config: RamStressConfig { threads: threads(stress.ram_config.threads), pinned_cpus, load_profile, ..stress.ram_config.clone() },
+                live_ops: Default::default(),
+            }),
+            CpuWorkloadKind::Crypto => Workload::Crypto(crypto_stress::CryptoStress {
+                config: CryptoStressConfig { threads: threads(stress.crypto_config.threads), pinned_cpus, load_profile, ..stress.crypto_config.clone() },
+                totals: Default::default(),
+            }),
+            CpuWorkloadKind::Micro => Workload::Micro(micro_stress::MicroStress {
+                config: MicroStressConfig { threads: threads(stress.micro_config.threads), pinned_cpus, load_profile, ..stress.micro_config.clone() },
+                totals: Default::default(),
+            }),
+        }
+    }
+
+    fn threads(&self) -> usize {
+        match self {
+            Workload::TightLoop(w) => w.config.threads,
+            Workload::Matrix(w) => w.config.threads,
+            Workload::Compression(w) => w.config.threads,
+            Workload::Ram(w) => w.config.threads,
+            Workload::Crypto(w) => w.config.threads,
+            Workload::Micro(w) => w.config.threads,
+        }
+    }
+
+    fn duration_secs(&self) -> u32 {
+        match self {
+            Workload::TightLoop(w) => w.config.duration_secs,
+            Workload::Matrix(w) => w.config.duration_secs,
+            Workload::Compression(w) => w.config.duration_secs,
+            Workload::Ram(w) => w.config.duration_secs,
+            Workload::Crypto(w) => w.config.duration_secs,
+            Workload::Micro(w) => w.config.duration_secs,
+        }
+    }
+
+    // Runs the workload once for `secs`, adding to its totals
+    fn run(&self, secs: u32, stop_flag: Arc<AtomicBool>, counts: &mut [u64]) -> u64 {
+        match self {
+            Workload::TightLoop(w) => tightloop_stress::TightLoopStress { config: TightLoopStressConfig { duration_secs: secs, ..w.config.clone() } }.run_with_counts(stop_flag, counts),
+            Workload::Matrix(w) => matrix_stress::MatrixStress { config: MatrixStressConfig { duration_secs: secs, ..w.config.clone() }, totals: w.totals.clone() }.run_with_counts(stop_flag, counts),
+            Workload::Compression(w) => compression_stress::CompressionStress { config: CompressionStressConfig { duration_secs: secs, ..w.config.clone() }, totals: w.totals.clone() }.run_with_counts(stop_flag, counts),
+            Workload::Ram(w) => ram_stress::RamStress { config: RamStressConfig { duration_secs: secs, ..w.config.clone() }, live_ops: w.live_ops.clone() }.run_with_counts(stop_flag, counts),
+            Workload::Crypto(w) => crypto_stress::CryptoStress { config: CryptoStressConfig { duration_secs: secs, ..w.config.clone() }, totals: w.totals.clone() }.run_with_counts(stop_flag, counts),
+            Workload::Micro(w) => micro_stress::MicroStress { config: MicroStressConfig { duration_secs: secs, ..w.config.clone() }, totals: w.totals.clone() }.run_with_counts(stop_flag, counts),
+        }
+    }
+
+    // Drops what the warm-up added so only measured runs are reported
+    fn reset_totals(&self) {
+        match self {
+            Workload::Matrix(w) => *w.totals.lock().unwrap() = MatrixTotals::default(),
+            Workload::Compression(w) => *w.totals.lock().unwrap() = CompressionTotals::default(),
+            Workload::Crypto(w) => w.totals.lock().unwrap().clear(),
+            Workload::Micro(w) => w.totals.lock().unwrap().clear(),
+            Workload::TightLoop(_) | Workload::Ram(_) => {}
+        }
+    }
+
+    fn totals(&self) -> Option<WorkloadTotals> {
+        match self {
+            Workload::Matrix(w) => Some(WorkloadTotals::Matrix(w.totals.lock().unwrap().clone())),
+            Workload::Compression(w) => Some(WorkloadTotals::Compression(*w.totals.lock().unwrap())),
+            Workload::Crypto(w) => Some(WorkloadTotals::Crypto(w.totals.lock().unwrap().clone())),
+            Workload::Micro(w) => Some(WorkloadTotals::Micro(w.totals.lock().unwrap().clone())),
+            Workload::TightLoop(_) | Workload::Ram(_) => None,
+        }
+    }
+
+    // Workload settings for the log file name, between the date and the thread count
+    fn file_tag(&self) -> String {
+        match self {
+            Workload::TightLoop(_) => String::new(),
+            Workload::Matrix(w) => format!("_{}_{}_size{}", w.config.kernel.name(), w.config.element.name(), w.config.matrix_size),
+            Workload::Compression(w) => format!("_{}_block{}", w.config.tag(), w.config.block_size),
+            Workload::Ram(w) => format!("_buf{}", w.config.buffer_size),
+            Workload::Crypto(w) => format!("_{}_buf{}", w.config.tag(), w.config.buffer_size),
+            Workload::Micro(w) => format!("_{}_chase{}", w.config.tag(), w.config.chase_bytes),
+        }
+    }
+
+    // The CSV params column, e.g. "kernel=tiled,type=f64,size=512,threads=8,dur=60"
+    fn params(&self) -> String {
+        let common = format!("threads={},dur={}", self.threads(), self.duration_secs());
+        match self {
+            Workload::TightLoop(_) => common,
+            Workload::Matrix(w) => format!("kernel={},type={},size={},{}", w.config.kernel.name(), w.config.element.name(), w.config.matrix_size, common),
+            Workload::Compression(w) => format!("codec={},data={},block={},{}", w.config.codec.tag(w.config.level), w.config.data.name(), w.config.block_size, common),
+            Workload::Ram(w) => format!("buf={},{}", w.config.buffer_size, common),
+            Workload::Crypto(w) => format!("algorithm={},buf={},{}", w.config.tag(), w.config.buffer_size, common),
+            Workload::Micro(w) => format!("kernel={},chase={},{}", w.config.tag(), w.config.chase_bytes, common),
+        }
+    }
+}
+
+// Per-workload results of the last run, for workloads that report more than op counts
+#[derive(Clone)]
+pub enum WorkloadTotals {
+    Matrix(MatrixTotals),
+    Compression(CompressionTotals),
+    Crypto(Vec<CryptoTotals>),
+    Micro(Vec<MicroTotals>),
+}
+
+impl WorkloadTotals {
+    // Fewer than six fields, so readers of the per-thread rows skip them
+    fn write_csv(&self, file: &mut impl Write) {
+        match self {
+            WorkloadTotals::Matrix(totals) => {
+                for (tid, rate) in totals.thread_rates().iter().enumerate() {
+                    writeln!(file, "matrix,{},{:.3},{}", tid, rate, totals.unit()).ok();
+                }
+            }
+            WorkloadTotals::Compression(totals) => {
+                writeln!(
+                    file,
+                    "compression,{:.2},{:.2},{:.3},{}",
+                    totals.compress_mbps(),
+                    totals.decompress_mbps(),
+                    totals.ratio(),
+                    totals.mismatches
+                ).ok();
+            }
+            WorkloadTotals::Crypto(totals) => {
+                for (feature, on) in crypto_stress::acceleration() {
+                    writeln!(file, "accel,{},{}", feature, on as u8).ok();
+                }
+                for t in totals {
+                    writeln!(file, "selftest,{},{}", t.algorithm.name(), t.self_test_passed as u8).ok();
+                    writeln!(
+                        file,
+                        "crypto,{},{:.2},{},{}",
+                        t.algorithm.name(),
+                        t.forward_mbps(),
+                        t.decrypt_mbps().map(|d| format!("{:.2}", d)).unwrap_or_default(),
+                        t.mismatches
+                    ).ok();
+                }
+            }
+            WorkloadTotals::Micro(totals) => {
+                for t in totals {
+                    writeln!(file, "micro,{},{:.2},{}", t.kernel.name(), t.ops_per_sec(), t.mismatches).ok();
+                }
+            }
+        }
+    }
+
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        match self {
+            WorkloadTotals::Matrix(totals) => matrix_stress::ui_totals(ui, "selectable_matrix_table", totals),
+            WorkloadTotals::Compression(totals) => compression_stress::ui_totals(ui, totals),
+            WorkloadTotals::Crypto(totals) => crypto_stress::ui_totals(ui, "selectable_crypto_table", totals),
+            WorkloadTotals::Micro(totals) => micro_stress::ui_totals(ui, "selectable_micro_table", totals),
         }
     }
 }
@@ -53,8 +267,18 @@ pub struct SelectableStress {
     pub compression_config: CompressionStressConfig,
     pub ram_config: RamStressConfig,
     pub tightloop_config: TightLoopStressConfig,
+    pub crypto_config: CryptoStressConfig,
+    pub micro_config: MicroStressConfig,
     pub stop_flag: Option<Arc<AtomicBool>>,
     pub log_path: Arc<Mutex<Option<String>>>,
+    pub placement: PlacementPolicy,
+    pub load_profile: LoadProfile,
+    // Benchmark mode: warm-up, then the workload's duration repeated, with stats over the repetitions
+    pub bench_mode: bool,
+    pub bench: BenchSettings,
+    pub bench_phase: Arc<Mutex<String>>,
+    pub bench_stats: Arc<Mutex<Option<BenchStats>>>,
+    pub totals: Arc<Mutex<Option<WorkloadTotals>>>,
 }
 
 impl SelectableStress {
@@ -69,9 +293,27 @@ impl SelectableStress {
             compression_config: CompressionStressConfig::from_config(config),
             ram_config: RamStressConfig::from_config(config),
             tightloop_config: TightLoopStressConfig::from_config(config),
+            crypto_config: CryptoStressConfig::from_config(config),
+            micro_config: MicroStressConfig::from_config(config),
             stop_flag: None,
             log_path: Arc::new(Mutex::new(None)),
+            placement: PlacementPolicy::OsDefault,
+            load_profile: LoadProfile::Constant,
+            bench_mode: false,
+            bench: BenchSettings::from_config(config),
+            bench_phase: Arc::new(Mutex::new(String::new())),
+            bench_stats: Arc::new(Mutex::new(None)),
+            totals: Arc::new(Mutex::new(None)),
+        }
+    }
+    pub fn is_running(&self) -> bool {
+        self.running_flag.load(Ordering::SeqCst)
+    }
+    pub fn stop(&mut self) {
+        if let Some(flag) = &self.stop_flag {
+            flag.store(true, Ordering::SeqCst);
         }
+        self.running = false;
     }
     pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
         ui.heading("Custom/Selectable Stress Test");
@@ -99,12 +341,31 @@ impl SelectableStress {
                 ui.label("Performs repeated matrix multiplications (CPU, cache, and memory stress).");
                 ui.horizontal(|ui| {
                     ui.label("Matrix size:");
-                    ui.add(egui::DragValue::new(&mut self.matrix_config.matrix_size).range(8..=512));
+                    ui.add(egui::DragValue::new(&mut self.matrix_config.matrix_size).range(8..=4096));
                     ui.label("Threads:");
                     ui.add(egui::DragValue::new(&mut self.matrix_config.threads).range(1..=num_cpus::get()));
                     ui.label("Duration (s):");
                     ui.add(egui::DragValue::new(&mut self.matrix_config.duration_secs).range(1..=300));
                 });
+                ui.horizontal(|ui| {
+                    ui.label("Kernel:");
+                    egui::ComboBox::from_id_salt("matrix_kernel")
+                        .selected_text(self.matrix_config.kernel.label())
+                        .show_ui(ui, |ui| {
+                            for kernel in MatrixKernel::ALL {
+                                ui.selectable_value(&mut self.matrix_config.kernel, kernel, kernel.label());
+                            }
+                        });
+                    ui.label("Type:");
+                    egui::ComboBox::from_id_salt("matrix_element")
+                        .selected_text(self.matrix_config.element.label())
+                        .show_ui(ui, |ui| {
+                            for element in MatrixElement::ALL {
+                                ui.selectable_value(&mut self.matrix_config.element, element, element.label());
+                            }
+                        });
+                    ui.label(format!("Memory: {:.1} MB", self.matrix_config.memory_bytes() as f64 / 1024.0 / 1024.0));
+                });
             }
             CpuWorkloadKind::Compression => {
                 ui.label("Performs repeated compression/decompression (CPU and memory stress).");
@@ -116,6 +377,28 @@ impl SelectableStress {
                     ui.label("Duration (s):");
                     ui.add(egui::DragValue::new(&mut self.compression_config.duration_secs).range(1..=300));
                 });
+                ui.horizontal(|ui| {
+                    ui.label("Codec:");
+                    egui::ComboBox::from_id_salt("compression_codec")
+                        .selected_text(self.compression_config.codec.label())
+                        .show_ui(ui, |ui| {
+                            for codec in CompressionCodec::ALL {
+                                ui.selectable_value(&mut self.compression_config.codec, codec, codec.label());
+                            }
+                        });
+                    if self.compression_config.codec.has_levels() {
+                        ui.label("Level:");
+                        ui.add(egui::Slider::new(&mut self.compression_config.level, 1..=9));
+                    }
+                    ui.label("Data:");
+                    egui::ComboBox::from_id_salt("compression_data")
+                        .selected_text(self.compression_config.data.label())
+                        .show_ui(ui, |ui| {
+                            for data in CompressionData::ALL {
+                                ui.selectable_value(&mut self.compression_config.data, data, data.label());
+                            }
+                        });
+                });
             }
             CpuWorkloadKind::RandomMemoryAccess => {
                 ui.label("Performs random memory accesses (memory bandwidth and latency stress).");
@@ -128,6 +411,77 @@ impl SelectableStress {
                     ui.add(egui::DragValue::new(&mut self.ram_config.duration_secs).range(1..=300));
                 });
             }
+            CpuWorkloadKind::Crypto => {
+                ui.label("Hashes and encrypts/decrypts TLS-record-sized buffers (CPU and crypto extension stress).");
+                ui.horizontal(|ui| {
+                    ui.label("Buffer size (bytes):");
+                    ui.add(egui::DragValue::new(&mut self.crypto_config.buffer_size).range(64..=16*1024*1024));
+                    ui.label("Threads:");
+                    ui.add(egui::DragValue::new(&mut self.crypto_config.threads).range(1..=num_cpus::get()));
+                    ui.label("Duration (s):");
+                    ui.add(egui::DragValue::new(&mut self.crypto_config.duration_secs).range(1..=300));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Algorithm:");
+                    egui::ComboBox::from_id_salt("crypto_algorithm")
+                        .selected_text(self.crypto_config.algorithm.map(|a| a.label()).unwrap_or("All (in turn)"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.crypto_config.algorithm, None, "All (in turn)");
+                            for algorithm in CryptoAlgorithm::ALL {
+                                ui.selectable_value(&mut self.crypto_config.algorithm, Some(algorithm), algorithm.label());
+                            }
+                        });
+                    if self.crypto_config.algorithm.is_none() {
+                        ui.label("The duration is split between the algorithms.");
+                    }
+                });
+                crypto_stress::ui_acceleration(ui);
+            }
+            CpuWorkloadKind::Micro => {
+                ui.label("Small loops that each lean on one execution unit, to isolate which one is unstable.");
+                ui.horizontal(|ui| {
+                    ui.label("Threads:");
+                    ui.add(egui::DragValue::new(&mut self.micro_config.threads).range(1..=num_cpus::get()));
+                    ui.label("Duration (s):");
+                    ui.add(egui::DragValue::new(&mut self.micro_config.duration_secs).range(1..=300));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Kernel:");
+                    egui::ComboBox::from_id_salt("micro_kernel")
+                        .selected_text(self.micro_config.kernel.map(|k| k.label()).unwrap_or("All (in turn)"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.micro_config.kernel, None, "All (in turn)");
+                            for kernel in MicroKernel::ALL {
+                                ui.selectable_value(&mut self.micro_config.kernel, Some(kernel), kernel.label()).on_hover_text(format!("One op = {}", kernel.op()));
+                            }
+                        });
+                    if self.micro_config.kernel.is_none() {
+                        ui.label("The duration is split between the kernels.");
+                    }
+                });
+                if self.micro_config.kernel.is_none_or(|k| k == MicroKernel::Chase) {
+                    ui.horizontal(|ui| {
+                        ui.label("Pointer-chase buffer (bytes):");
+                        ui.add(egui::DragValue::new(&mut self.micro_config.chase_bytes).range(4096..=4usize*1024*1024*1024));
+                    });
+                }
+            }
+        }
+        topology::placement_ui(ui, "selectable_stress_placement", &mut self.placement);
+        if self.placement != PlacementPolicy::OsDefault {
+            ui.label("Thread count follows the placement policy.");
+        }
+        load_profile::load_profile_ui(ui, "selectable_stress_load_profile", &mut self.load_profile);
+        ui.checkbox(&mut self.bench_mode, "Benchmark mode (warm-up + repeated runs with statistics)");
+        if self.bench_mode {
+            self.bench.ui(ui);
+            ui.label(format!(
+                "Each repetition runs for the duration above; {} s warm-up + {} x duration in total. The warm-up isn't measured.",
+                self.bench.warmup_secs, self.bench.repetitions
+            ));
+            if self.load_profile != LoadProfile::Constant {
+                ui.colored_label(egui::Color32::YELLOW, "Load profiles make repetitions vary on purpose; use Constant for stable benchmark results.");
+            }
         }
         ui.add_space(10.0);
         // Check if the background thread finished
@@ -139,20 +493,22 @@ impl SelectableStress {
                 *self.result.lock().unwrap() = None;
                 *self.progress.lock().unwrap() = 0.0;
                 *self.log_path.lock().unwrap() = None;
+                *self.bench_stats.lock().unwrap() = None;
+                *self.totals.lock().unwrap() = None;
+                self.bench_phase.lock().unwrap().clear();
                 let stop_flag = Arc::new(AtomicBool::new(false));
                 self.stop_flag = Some(stop_flag.clone());
                 self.running_flag.store(true, Ordering::SeqCst);
                 self.running = true;
-                let _running_flag = self.running_flag.clone();
+                let running_flag = self.running_flag.clone();
+                let bench_settings = self.bench_mode.then(|| self.bench.clone());
+                let bench_phase = self.bench_phase.clone();
+                let bench_stats = self.bench_stats.clone();
+                let totals_result = self.totals.clone();
                 let kind = self.selected_cpu_workload;
-                let matrix_config = self.matrix_config.clone();
-                let matrix_config_for_csv = self.matrix_config.clone();
-                let compression_config = self.compression_config.clone();
-                let compression_config_for_csv = self.compression_config.clone();
-                let ram_config = self.ram_config.clone();
-                let _ram_config_for_csv = self.ram_config.clone();
-                let tightloop_config = self.tightloop_config.clone();
-                let _tightloop_config_for_csv = self.tightloop_config.clone();
+                let load_profile = self.load_profile;
+                let pinned_cpus = self.placement.resolve(topology::get());
+                let workload = Workload::new(self, kind, &pinned_cpus);
                 let ctx = ctx.clone();
                 let progress = self.progress.clone();
                 let result = self.result.clone();
@@ -160,22 +516,24 @@ impl SelectableStress {
                 let stop_flag = stop_flag.clone();
                 let dev_mode = dev_mode;
                 if dev_mode {
-                    println!("[DEV] Starting selectable stress test: kind={:?}", kind);
+                    println!("[DEV] Starting selectable stress test: kind={:?}, pinned_cpus={:?}", kind, pinned_cpus);
                 }
                 thread::spawn(move || {
-                    let duration = match kind {
-                        CpuWorkloadKind::MatrixMultiplication => matrix_config.duration_secs,
-                        CpuWorkloadKind::Compression => compression_config.duration_secs,
-                        CpuWorkloadKind::TightLoop => tightloop_config.duration_secs,
-                        CpuWorkloadKind::RandomMemoryAccess => ram_config.duration_secs,
+                    let run_secs = workload.duration_secs();
+                    let duration = match &bench_settings {
+                        Some(b) => b.warmup_secs + b.repetitions * run_secs,
+                        None => run_secs,
                     };
+                    // The CSV is only written at the end, so the journal learns its path then
+                    let journal = RunJournal::begin(dev_mode, "selectable", None);
+                    // The workload runs on this thread, so there is no separate engine heartbeat
+                    let _watch = watchdog::watch("selectable", duration as u64, {
+                        let stop_flag = stop_flag.clone();
+                        move || stop_flag.store(true, Ordering::SeqCst)
+                    });
+                    let pin_failures_before = topology::pin_failures();
                     let start = Instant::now();
-                    let mut op_counts = vec![0u64; match kind {
-                        CpuWorkloadKind::MatrixMultiplication => matrix_config.threads,
-                        CpuWorkloadKind::Compression => compression_config.threads,
-                        CpuWorkloadKind::TightLoop => tightloop_config.threads,
-                        CpuWorkloadKind::RandomMemoryAccess => ram_config.threads,
-                    }];
+                    let mut op_counts = vec![0u64; workload.threads()];
                     let stop_flag2 = stop_flag.clone();
                     // Progress updater
                     let progress_clone = progress.clone();
@@ -188,85 +546,122 @@ impl SelectableStress {
                         }
                         *progress_clone.lock().unwrap() = 1.0;
                     });
-                    // Run workload
-                    let _res = match kind {
-                        CpuWorkloadKind::MatrixMultiplication => {
-                            let stress = matrix_stress::MatrixStress { config: matrix_config.clone() };
-                            stress.run_with_counts(stop_flag.clone(), &mut op_counts)
-                        }
-                        CpuWorkloadKind::Compression => {
-                            let stress = compression_stress::CompressionStress { config: compression_config.clone() };
-                            stress.run_with_counts(stop_flag.clone(), &mut op_counts)
+                    // Ops/sec of each measured repetition; op_counts sums the repetitions per thread
+                    let mut repetitions: Vec<f64> = Vec::new();
+                    match &bench_settings {
+                        Some(b) => {
+                            let mut counts = vec![0u64; op_counts.len()];
+                            if b.warmup_secs > 0 {
+                                *bench_phase.lock().unwrap() = format!("Warming up ({} s, not measured)...", b.warmup_secs);
+                                workload.run(b.warmup_secs, stop_flag.clone(), &mut counts);
+                                workload.reset_totals();
+                            }
+                            for rep in 1..=b.repetitions {
+                                if stop_flag.load(Ordering::SeqCst) {
+                                    break;
+                                }
+                                *bench_phase.lock().unwrap() = format!("Repetition {} of {}...", rep, b.repetitions);
+                                ctx.request_repaint();
+                                counts.iter_mut().for_each(|c| *c = 0);
+                                let rep_start = Instant::now();
+                                workload.run(run_secs, stop_flag.clone(), &mut counts);
+                                // A stopped repetition is shorter than the others, so leave it out
+                                if stop_flag.load(Ordering::SeqCst) {
+                                    break;
+                                }
+                                repetitions.push(counts.iter().sum::<u64>() as f64 / rep_start.elapsed().as_secs_f64().max(1e-6));
+                                for (total, &c) in op_counts.iter_mut().zip(&counts) {
+                                    *total += c;
+                                }
+                                *bench_stats.lock().unwrap() = BenchStats::from_samples(&repetitions, b.stable_cv_percent);
+                            }
+                            bench_phase.lock().unwrap().clear();
                         }
-                        CpuWorkloadKind::TightLoop => {
-                            let stress = tightloop_stress::TightLoopStress { config: tightloop_config.clone() };
-                            stress.run_with_counts(stop_flag.clone(), &mut op_counts)
+                        None => {
+                            workload.run(run_secs, stop_flag.clone(), &mut op_counts);
                         }
-                        CpuWorkloadKind::RandomMemoryAccess => {
-                            let stress = ram_stress::RamStress { config: ram_config.clone() };
-                            stress.run_with_counts(stop_flag.clone(), &mut op_counts)
-                        }
-                    };
+                    }
                     stop_flag.store(true, Ordering::SeqCst);
                     let _ = updater.join();
                     let total_ops = op_counts.iter().sum();
                     *result.lock().unwrap() = Some(total_ops);
+                    let totals = workload.totals();
+                    *totals_result.lock().unwrap() = totals.clone();
                     *progress.lock().unwrap() = 1.0;
                     // Save CSV
                     let date = Local::now().format("%Y%m%d_%H%M%S");
-                    let log_dir = if dev_mode {
-                        std::path::PathBuf::from("log")
-                    } else {
-                        std::env::current_exe().ok().and_then(|p| p.parent().map(|d| d.to_path_buf())).unwrap_or_else(|| std::path::PathBuf::from("."))
-                            .join("log")
-                    };
+                    let log_dir = crate::app::log_dir(dev_mode);
                     let _ = std::fs::create_dir_all(&log_dir);
-                    let filename = match kind {
-                        CpuWorkloadKind::MatrixMultiplication => log_dir.join(format!("selectable_matrix_{}_size{}_threads{}_dur{}.csv", date, matrix_config_for_csv.matrix_size, matrix_config_for_csv.threads, matrix_config_for_csv.duration_secs)),
-                        CpuWorkloadKind::Compression => log_dir.join(format!("selectable_compression_{}_block{}_threads{}_dur{}.csv", date, compression_config_for_csv.block_size, compression_config_for_csv.threads, compression_config_for_csv.duration_secs)),
-                        CpuWorkloadKind::TightLoop => log_dir.join(format!("selectable_tightloop_{}_threads{}_dur{}.csv", date, tightloop_config.threads, tightloop_config.duration_secs)),
-                        CpuWorkloadKind::RandomMemoryAccess => log_dir.join(format!("selectable_ram_{}_buf{}_threads{}_dur{}.csv", date, ram_config.buffer_size, ram_config.threads, ram_config.duration_secs)),
-                    };
+                    let mut suffix = bench_settings.as_ref().map(|b| format!("_warm{}_reps{}", b.warmup_secs, b.repetitions)).unwrap_or_default();
+                    // Same filename tag as the CPU stress test so shaped runs stand out in the list
+                    if load_profile != LoadProfile::Constant {
+                        suffix.push_str(&format!("_prof{}", load_profile.tag()));
+                    }
+                    let filename = log_dir.join(format!("selectable_{}_{}{}_threads{}_dur{}{}.csv", kind.tag(), date, workload.file_tag(), workload.threads(), workload.duration_secs(), suffix));
                     let mut file = OpenOptions::new().create(true).append(true).open(&filename).unwrap();
                     writeln!(file, "timestamp,workload,params,total_ops,thread,thread_ops").unwrap();
+                    inventory::get().write_csv_comments(&mut file);
+                    let unpinned = topology::pin_failures() - pin_failures_before;
+                    if !pinned_cpus.is_empty() {
+                        writeln!(file, "# Unpinned workers: {}", unpinned).ok();
+                    }
+                    if dev_mode && unpinned > 0 {
+                        println!("[DEV] {} worker(s) could not be pinned and ran where the OS put them", unpinned);
+                    }
+                    let params = format!("{},profile={}", workload.params(), load_profile.tag());
                     for (tid, &count) in op_counts.iter().enumerate() {
                         let line = format!(
                             "{}\t{}\t{}\t{}\t{}\t{}",
                             date,
                             kind.label(),
-                            match kind {
-                                CpuWorkloadKind::MatrixMultiplication => format!("size={},threads={},dur={}", matrix_config_for_csv.matrix_size, matrix_config_for_csv.threads, matrix_config_for_csv.duration_secs),
-                                CpuWorkloadKind::Compression => format!("block={},threads={},dur={}", compression_config_for_csv.block_size, compression_config_for_csv.threads, compression_config_for_csv.duration_secs),
-                                CpuWorkloadKind::TightLoop => format!("threads={},dur={}", tightloop_config.threads, tightloop_config.duration_secs),
-                                CpuWorkloadKind::RandomMemoryAccess => format!("buf={},threads={},dur={}", ram_config.buffer_size, ram_config.threads, ram_config.duration_secs),
-                            },
+                            params,
                             total_ops,
                             tid,
                             count
                         ).replace('\t', ",");
                         writeln!(file, "{}", line).unwrap();
                     }
+                    if let Some(totals) = &totals {
+                        totals.write_csv(&mut file);
+                    }
+                    if let Some(b) = &bench_settings {
+                        writeln!(file, "bench,{},{},{}", b.warmup_secs, b.repetitions, b.stable_cv_percent).ok();
+                        let outliers = bench::outliers(&repetitions);
+                        for (i, (rate, outlier)) in repetitions.iter().zip(outliers).enumerate() {
+                            writeln!(file, "repetition,{},{},{}", i + 1, rate, outlier as u8).ok();
+                        }
+                    }
                     *log_path.lock().unwrap() = Some(filename.to_string_lossy().to_string());
                     if dev_mode {
                         println!("[DEV] Created log file: {}", filename.display());
                     }
+                    if let Some(journal) = journal {
+                        journal.set_csv(&filename);
+                        journal.finish();
+                    }
+                    running_flag.store(false, Ordering::SeqCst);
                     ctx.request_repaint();
                 });
             }
         }
         else {
             if ui.button("Stop").clicked() {
-                if let Some(flag) = &self.stop_flag {
-                    flag.store(true, Ordering::SeqCst);
-                }
-                self.running = false;
+                self.stop();
             }
             ui.add(egui::ProgressBar::new(*self.progress.lock().unwrap()).show_percentage());
-            ui.label("Running...");
+            let phase = self.bench_phase.lock().unwrap().clone();
+            ui.label(if phase.is_empty() { "Running...".to_string() } else { phase });
         }
         if let Some(res) = *self.result.lock().unwrap() {
             ui.label(format!("Result: {} operations performed.", res));
         }
+        if let Some(totals) = &*self.totals.lock().unwrap() {
+            totals.ui(ui);
+        }
+        if let Some(stats) = &*self.bench_stats.lock().unwrap() {
+            ui.label("Benchmark (ops/sec per repetition):");
+            bench::ui_stats_table(ui, "selectable_bench_table", stats, "ops/s");
+        }
         if let Some(ref path) = *self.log_path.lock().unwrap() {
             ui.label(format!("Log saved to: {}", path));
         }
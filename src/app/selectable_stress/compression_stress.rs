@@ -1,15 +1,200 @@
-use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use eframe::egui;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
 use std::thread;
 use std::time::{Duration, Instant};
-use rand::Rng;
+use crate::app::topology;
+use crate::app::load_profile::{LoadGate, LoadProfile};
+use crate::app::seed;
+use rand::{rngs::StdRng, Rng};
 use flate2::{Compression, write::ZlibEncoder, read::ZlibDecoder};
+use ruzstd::{decoding::StreamingDecoder, encoding::CompressionLevel};
 use std::io::{Write, Read};
 
+const WORDS: [&str; 48] = [
+    "the", "of", "and", "to", "in", "a", "is", "that", "for", "it", "as", "was", "with", "be", "by", "on",
+    "not", "he", "this", "are", "or", "his", "from", "at", "which", "but", "have", "an", "had", "they", "you", "were",
+    "system", "value", "memory", "thread", "result", "error", "request", "server", "window", "process", "data", "file", "time", "number", "test", "report",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionCodec {
+    Deflate,
+    Lz4,
+    Zstd,
+}
+
+impl CompressionCodec {
+    pub const ALL: [CompressionCodec; 3] = [CompressionCodec::Deflate, CompressionCodec::Lz4, CompressionCodec::Zstd];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompressionCodec::Deflate => "deflate",
+            CompressionCodec::Lz4 => "lz4",
+            CompressionCodec::Zstd => "zstd",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompressionCodec::Deflate => "Deflate (zlib)",
+            CompressionCodec::Lz4 => "LZ4",
+            CompressionCodec::Zstd => "Zstandard (fastest)",
+        }
+    }
+
+    // Only deflate has levels; the pure-Rust lz4 and zstd encoders each have a single speed
+    pub fn has_levels(&self) -> bool {
+        *self == CompressionCodec::Deflate
+    }
+
+    // Filename and params tag, e.g. deflate6
+    pub fn tag(&self, level: u32) -> String {
+        if self.has_levels() { format!("{}{}", self.name(), level) } else { self.name().to_string() }
+    }
+
+    fn compress(&self, level: u32, data: &[u8]) -> Vec<u8> {
+        match self {
+            CompressionCodec::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(level.clamp(1, 9)));
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            CompressionCodec::Lz4 => lz4_flex::compress_prepend_size(data),
+            CompressionCodec::Zstd => ruzstd::encoding::compress_to_vec(data, CompressionLevel::Fastest),
+        }
+    }
+
+    // None when the stream can't be decoded at all, which counts as a mismatch like wrong output does
+    fn decompress(&self, compressed: &[u8], size_hint: usize) -> Option<Vec<u8>> {
+        match self {
+            CompressionCodec::Deflate => {
+                let mut out = Vec::with_capacity(size_hint);
+                ZlibDecoder::new(compressed).read_to_end(&mut out).ok()?;
+                Some(out)
+            }
+            CompressionCodec::Lz4 => lz4_flex::decompress_size_prepended(compressed).ok(),
+            CompressionCodec::Zstd => {
+                let mut out = Vec::with_capacity(size_hint);
+                StreamingDecoder::new(compressed).ok()?.read_to_end(&mut out).ok()?;
+                Some(out)
+            }
+        }
+    }
+}
+
+// What the compressed block looks like; everything is generated from the run seed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionData {
+    Random,
+    Text,
+    Repetitive,
+    MixedBinary,
+}
+
+impl CompressionData {
+    pub const ALL: [CompressionData; 4] = [CompressionData::Random, CompressionData::Text, CompressionData::Repetitive, CompressionData::MixedBinary];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompressionData::Random => "random",
+            CompressionData::Text => "text",
+            CompressionData::Repetitive => "repetitive",
+            CompressionData::MixedBinary => "mixed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompressionData::Random => "Random bytes (incompressible)",
+            CompressionData::Text => "Text-like",
+            CompressionData::Repetitive => "Repetitive records",
+            CompressionData::MixedBinary => "Mixed binary",
+        }
+    }
+
+    pub fn generate(&self, rng: &mut StdRng, size: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(size + 4096);
+        match self {
+            CompressionData::Random => {
+                data.resize(size, 0);
+                rng.fill(&mut data[..]);
+            }
+            CompressionData::Text => {
+                while data.len() < size {
+                    push_sentence(rng, &mut data);
+                }
+            }
+            CompressionData::Repetitive => {
+                // One record repeated with the odd changed byte, like a log of near-identical structs
+                let mut record = [0u8; 128];
+                rng.fill(&mut record[..]);
+                while data.len() < size {
+                    if rng.gen_ratio(1, 100) {
+                        record[rng.gen_range(0..record.len())] = rng.r#gen();
+                    }
+                    data.extend_from_slice(&record);
+                }
+            }
+            CompressionData::MixedBinary => {
+                // Segments like an executable or save file: small integers, floats, zero padding,
+                // random payloads and embedded strings
+                while data.len() < size {
+                    let len = rng.gen_range(64..4096);
+                    match rng.gen_range(0..5) {
+                        0 => (0..len / 4).for_each(|_| data.extend_from_slice(&rng.gen_range(0u32..1024).to_le_bytes())),
+                        1 => (0..len / 8).for_each(|_| data.extend_from_slice(&rng.gen_range(-100.0f64..100.0).to_le_bytes())),
+                        2 => data.resize(data.len() + len, 0),
+                        3 => data.extend((0..len).map(|_| rng.r#gen::<u8>())),
+                        _ => {
+                            let end = data.len() + len;
+                            while data.len() < end {
+                                push_sentence(rng, &mut data);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        data.truncate(size);
+        data
+    }
+}
+
+// Common words much more often than rare ones, roughly like real prose
+fn push_sentence(rng: &mut StdRng, data: &mut Vec<u8>) {
+    let words = rng.gen_range(4..16);
+    for i in 0..words {
+        let skew: f64 = rng.r#gen::<f64>().powi(3);
+        let word = WORDS[(skew * WORDS.len() as f64) as usize];
+        if i == 0 {
+            data.extend(word[..1].to_uppercase().bytes());
+            data.extend_from_slice(&word.as_bytes()[1..]);
+        } else {
+            data.push(b' ');
+            data.extend_from_slice(word.as_bytes());
+        }
+    }
+    data.extend_from_slice(if rng.gen_ratio(1, 5) { b".\n" } else { b". " });
+}
+
 #[derive(Clone)]
 pub struct CompressionStressConfig {
     pub block_size: usize,
     pub duration_secs: u32,
     pub threads: usize,
+    pub pinned_cpus: Vec<usize>,
+    pub load_profile: LoadProfile,
+    pub codec: CompressionCodec,
+    pub level: u32,
+    pub data: CompressionData,
 }
 
 impl CompressionStressConfig {
@@ -18,46 +203,109 @@ impl CompressionStressConfig {
             block_size: config.compression_block_size,
             duration_secs: config.compression_duration_secs,
             threads: config.compression_threads,
+            pinned_cpus: Vec::new(),
+            load_profile: LoadProfile::Constant,
+            codec: CompressionCodec::from_name(&config.compression_codec).unwrap_or(CompressionCodec::Deflate),
+            level: config.compression_level.clamp(1, 9),
+            data: CompressionData::from_name(&config.compression_data).unwrap_or(CompressionData::Text),
         }
     }
+
+    // Codec, level and data, shared by the log filename and params column
+    pub fn tag(&self) -> String {
+        format!("{}_{}", self.codec.tag(self.level), self.data.name())
+    }
+}
+
+// Byte and time totals over all threads; compress and decompress are timed separately
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompressionTotals {
+    pub threads: usize,
+    pub input_bytes: u64,
+    pub compressed_bytes: u64,
+    pub compress_secs: f64,
+    pub decompress_secs: f64,
+    // Round trips whose output didn't match the input
+    pub mismatches: u64,
+}
+
+impl CompressionTotals {
+    // Threads run side by side, so the combined rate is the bytes over the average time per thread
+    fn mbps(&self, secs: f64) -> f64 {
+        if secs > 0.0 { self.input_bytes as f64 / 1024.0 / 1024.0 * self.threads as f64 / secs } else { 0.0 }
+    }
+
+    pub fn compress_mbps(&self) -> f64 {
+        self.mbps(self.compress_secs)
+    }
+
+    pub fn decompress_mbps(&self) -> f64 {
+        self.mbps(self.decompress_secs)
+    }
+
+    pub fn ratio(&self) -> f64 {
+        if self.compressed_bytes > 0 { self.input_bytes as f64 / self.compressed_bytes as f64 } else { 0.0 }
+    }
 }
 
 pub struct CompressionStress {
     pub config: CompressionStressConfig,
+    // Added to by every run, so callers can sum several runs or reset it in between
+    pub totals: Arc<Mutex<CompressionTotals>>,
 }
 
 impl CompressionStress {
     pub fn from_config(config: &crate::app::config::Config) -> Self {
         Self {
             config: CompressionStressConfig::from_config(config),
+            totals: Arc::new(Mutex::new(CompressionTotals::default())),
         }
     }
     pub fn run_with_counts(&self, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) -> u64 {
         let mut handles = Vec::new();
         let results = Arc::new(std::sync::Mutex::new(vec![0u64; self.config.threads]));
         let block_size = self.config.block_size;
+        let run_seed = seed::current();
+        let (codec, level, kind) = (self.config.codec, self.config.level, self.config.data);
         let duration = self.config.duration_secs;
+        let pinned_cpus = Arc::new(self.config.pinned_cpus.clone());
+        let gate = Arc::new(LoadGate::new(self.config.load_profile));
+        self.totals.lock().unwrap().threads = self.config.threads;
         for tid in 0..self.config.threads {
             let stop_flag = stop_flag.clone();
             let results = results.clone();
+            let pinned_cpus = pinned_cpus.clone();
+            let gate = gate.clone();
+            let totals = self.totals.clone();
             handles.push(thread::spawn(move || {
-                let mut rng = rand::thread_rng();
+                topology::pin_worker(&pinned_cpus, tid);
+                let mut rng = seed::stream_rng(run_seed, "compression", tid as u64);
                 let mut count = 0u64;
-                let mut data = vec![0u8; block_size];
-                rng.fill(&mut data[..]);
+                let data = kind.generate(&mut rng, block_size);
+                let mut local = CompressionTotals::default();
                 let start = Instant::now();
                 while !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64) {
-                    // Compress
-                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-                    encoder.write_all(&data).unwrap();
-                    let compressed = encoder.finish().unwrap();
-                    // Decompress
-                    let mut decoder = ZlibDecoder::new(&compressed[..]);
-                    let mut out = Vec::with_capacity(block_size);
-                    decoder.read_to_end(&mut out).unwrap();
+                    gate.pace(|| !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64));
+                    let t = Instant::now();
+                    let compressed = codec.compress(level, &data);
+                    local.compress_secs += t.elapsed().as_secs_f64();
+                    let t = Instant::now();
+                    let out = codec.decompress(&compressed, block_size);
+                    local.decompress_secs += t.elapsed().as_secs_f64();
+                    if out.as_deref() != Some(&data[..]) {
+                        local.mismatches += 1;
+                    }
+                    local.input_bytes += data.len() as u64;
+                    local.compressed_bytes += compressed.len() as u64;
                     count += 1;
                 }
                 results.lock().unwrap()[tid] = count;
+                let mut totals = totals.lock().unwrap();
+                totals.input_bytes += local.input_bytes;
+                totals.compressed_bytes += local.compressed_bytes;
+                totals.compress_secs += local.compress_secs;
+                totals.decompress_secs += local.decompress_secs;
+                totals.mismatches += local.mismatches;
             }));
         }
         for h in handles { let _ = h.join(); }
@@ -70,3 +318,15 @@ impl CompressionStress {
         results.iter().sum()
     }
 }
+
+pub fn ui_totals(ui: &mut egui::Ui, totals: &CompressionTotals) {
+    ui.label(format!(
+        "Compress: {:.2} MB/s, Decompress: {:.2} MB/s, Ratio: {:.2}:1",
+        totals.compress_mbps(),
+        totals.decompress_mbps(),
+        totals.ratio()
+    ));
+    if totals.mismatches > 0 {
+        ui.colored_label(egui::Color32::RED, format!("⚠ {} round trip(s) did not reproduce the input", totals.mismatches));
+    }
+}
@@ -0,0 +1,337 @@
+use eframe::egui;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::app::topology;
+use crate::app::load_profile::{LoadGate, LoadProfile};
+use crate::app::seed;
+use rand::Rng;
+use aes_gcm::{Aes256Gcm, aead::{AeadInPlace, KeyInit}};
+use chacha20poly1305::ChaCha20Poly1305;
+use sha2::{Digest, Sha256};
+
+// RFC 8439 section 2.8.2
+const CHACHA_PLAINTEXT: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+const CHACHA_NONCE: &str = "070000004041424344454647";
+const CHACHA_AAD: &str = "50515253c0c1c2c3c4c5c6c7";
+const CHACHA_CIPHERTEXT: &str = "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b6116";
+const CHACHA_TAG: &str = "1ae10b594f09e26a7e902ecbd0600691";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CryptoAlgorithm {
+    Sha256,
+    Blake3,
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+impl CryptoAlgorithm {
+    pub const ALL: [CryptoAlgorithm; 4] = [CryptoAlgorithm::Sha256, CryptoAlgorithm::Blake3, CryptoAlgorithm::Aes256Gcm, CryptoAlgorithm::ChaCha20Poly1305];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CryptoAlgorithm::Sha256 => "sha256",
+            CryptoAlgorithm::Blake3 => "blake3",
+            CryptoAlgorithm::Aes256Gcm => "aes256gcm",
+            CryptoAlgorithm::ChaCha20Poly1305 => "chacha20poly1305",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CryptoAlgorithm::Sha256 => "SHA-256",
+            CryptoAlgorithm::Blake3 => "BLAKE3",
+            CryptoAlgorithm::Aes256Gcm => "AES-256-GCM",
+            CryptoAlgorithm::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+        }
+    }
+
+    // Ciphers are timed for encryption and decryption separately, hashes only have one direction
+    pub fn is_cipher(&self) -> bool {
+        matches!(self, CryptoAlgorithm::Aes256Gcm | CryptoAlgorithm::ChaCha20Poly1305)
+    }
+
+    // Known-answer test, run before the algorithm is measured so a broken build can't post a number
+    pub fn self_test(&self) -> bool {
+        match self {
+            CryptoAlgorithm::Sha256 => {
+                Sha256::digest(b"abc")[..] == hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")[..]
+            }
+            CryptoAlgorithm::Blake3 => {
+                blake3::hash(b"abc").as_bytes()[..] == hex("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85")[..]
+            }
+            CryptoAlgorithm::Aes256Gcm => {
+                // NIST GCM spec test case 14: zero key, zero IV, one zero block
+                let cipher = Aes256Gcm::new(&[0u8; 32].into());
+                let mut buf = [0u8; 16];
+                let Ok(tag) = cipher.encrypt_in_place_detached(&[0u8; 12].into(), b"", &mut buf) else {
+                    return false;
+                };
+                buf[..] == hex("cea7403d4d606b6e074ec5d3baf39d18")[..] && tag[..] == hex("d0d1c8a799996bf0265b98b5d48ab919")[..]
+            }
+            CryptoAlgorithm::ChaCha20Poly1305 => {
+                let key: Vec<u8> = (0x80..=0x9f).collect();
+                let cipher = ChaCha20Poly1305::new(key[..].into());
+                let mut buf = CHACHA_PLAINTEXT.to_vec();
+                let Ok(tag) = cipher.encrypt_in_place_detached(hex(CHACHA_NONCE)[..].into(), &hex(CHACHA_AAD), &mut buf) else {
+                    return false;
+                };
+                buf == hex(CHACHA_CIPHERTEXT) && tag[..] == hex(CHACHA_TAG)[..]
+            }
+        }
+    }
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+// Instruction set extensions the crates pick up at runtime; what this CPU has decides how close to
+// hardware speed the numbers get
+pub fn acceleration() -> Vec<(&'static str, bool)> {
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    return vec![
+        ("AES-NI", std::arch::is_x86_feature_detected!("aes")),
+        ("PCLMULQDQ", std::arch::is_x86_feature_detected!("pclmulqdq")),
+        ("SHA extensions", std::arch::is_x86_feature_detected!("sha")),
+        ("AVX2", std::arch::is_x86_feature_detected!("avx2")),
+    ];
+    #[cfg(target_arch = "aarch64")]
+    return vec![
+        ("AES", std::arch::is_aarch64_feature_detected!("aes")),
+        ("PMULL", std::arch::is_aarch64_feature_detected!("pmull")),
+        ("SHA2", std::arch::is_aarch64_feature_detected!("sha2")),
+        ("NEON", std::arch::is_aarch64_feature_detected!("neon")),
+    ];
+    #[allow(unreachable_code)]
+    Vec::new()
+}
+
+// Comma-free summary for the hardware inventory
+pub fn acceleration_summary() -> String {
+    let present: Vec<&str> = acceleration().into_iter().filter(|&(_, on)| on).map(|(name, _)| name).collect();
+    if present.is_empty() { "none".to_string() } else { present.join(" / ") }
+}
+
+#[derive(Clone)]
+pub struct CryptoStressConfig {
+    pub buffer_size: usize,
+    pub duration_secs: u32,
+    pub threads: usize,
+    pub pinned_cpus: Vec<usize>,
+    pub load_profile: LoadProfile,
+    // None runs every algorithm in turn, splitting the duration between them
+    pub algorithm: Option<CryptoAlgorithm>,
+}
+
+impl CryptoStressConfig {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            buffer_size: config.crypto_buffer_size,
+            duration_secs: config.crypto_duration_secs,
+            threads: config.crypto_threads,
+            pinned_cpus: Vec::new(),
+            load_profile: LoadProfile::Constant,
+            algorithm: CryptoAlgorithm::from_name(&config.crypto_algorithm),
+        }
+    }
+
+    pub fn algorithms(&self) -> Vec<CryptoAlgorithm> {
+        self.algorithm.map(|a| vec![a]).unwrap_or_else(|| CryptoAlgorithm::ALL.to_vec())
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.algorithm.map(|a| a.name()).unwrap_or("all")
+    }
+}
+
+// Bytes and time for one algorithm over all threads
+#[derive(Clone, Copy, Debug)]
+pub struct CryptoTotals {
+    pub algorithm: CryptoAlgorithm,
+    pub self_test_passed: bool,
+    pub threads: usize,
+    pub bytes: u64,
+    // Hashing, or encryption for ciphers
+    pub forward_secs: f64,
+    pub decrypt_secs: f64,
+    // Decryptions that failed authentication or didn't give back the plaintext
+    pub mismatches: u64,
+}
+
+impl CryptoTotals {
+    fn new(algorithm: CryptoAlgorithm, self_test_passed: bool) -> Self {
+        Self { algorithm, self_test_passed, threads: 0, bytes: 0, forward_secs: 0.0, decrypt_secs: 0.0, mismatches: 0 }
+    }
+
+    // Threads run side by side, so the combined rate is the bytes over the average time per thread
+    fn mbps(&self, secs: f64) -> f64 {
+        if secs > 0.0 { self.bytes as f64 / 1024.0 / 1024.0 * self.threads as f64 / secs } else { 0.0 }
+    }
+
+    pub fn forward_mbps(&self) -> f64 {
+        self.mbps(self.forward_secs)
+    }
+
+    pub fn decrypt_mbps(&self) -> Option<f64> {
+        self.algorithm.is_cipher().then(|| self.mbps(self.decrypt_secs))
+    }
+}
+
+pub struct CryptoStress {
+    pub config: CryptoStressConfig,
+    // One entry per algorithm, added to by every run so callers can sum several runs or reset it in between
+    pub totals: Arc<Mutex<Vec<CryptoTotals>>>,
+}
+
+impl CryptoStress {
+    pub fn run_with_counts(&self, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) -> u64 {
+        let algorithms = self.config.algorithms();
+        let secs_each = (self.config.duration_secs / algorithms.len() as u32).max(1);
+        let mut counts = vec![0u64; op_counts.len()];
+        op_counts.iter_mut().for_each(|c| *c = 0);
+        for algorithm in algorithms {
+            if stop_flag.load(Ordering::SeqCst) {
+                break;
+            }
+            let passed = algorithm.self_test();
+            {
+                let mut totals = self.totals.lock().unwrap();
+                match totals.iter_mut().find(|t| t.algorithm == algorithm) {
+                    Some(t) => t.self_test_passed &= passed,
+                    None => totals.push(CryptoTotals::new(algorithm, passed)),
+                }
+            }
+            // A failed known-answer test means the numbers would be for wrong output, so skip it
+            if !passed {
+                continue;
+            }
+            self.run_algorithm(algorithm, secs_each, stop_flag.clone(), &mut counts);
+            for (total, &c) in op_counts.iter_mut().zip(&counts) {
+                *total += c;
+            }
+        }
+        op_counts.iter().sum()
+    }
+    fn run_algorithm(&self, algorithm: CryptoAlgorithm, duration: u32, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) {
+        let mut handles = Vec::new();
+        let results = Arc::new(std::sync::Mutex::new(vec![0u64; self.config.threads]));
+        let buffer_size = self.config.buffer_size;
+        let run_seed = seed::current();
+        let pinned_cpus = Arc::new(self.config.pinned_cpus.clone());
+        let gate = Arc::new(LoadGate::new(self.config.load_profile));
+        self.totals.lock().unwrap().iter_mut().filter(|t| t.algorithm == algorithm).for_each(|t| t.threads = self.config.threads);
+        for tid in 0..self.config.threads {
+            let stop_flag = stop_flag.clone();
+            let results = results.clone();
+            let pinned_cpus = pinned_cpus.clone();
+            let gate = gate.clone();
+            let totals = self.totals.clone();
+            handles.push(thread::spawn(move || {
+                topology::pin_worker(&pinned_cpus, tid);
+                let mut rng = seed::stream_rng(run_seed, algorithm.name(), tid as u64);
+                let mut plaintext = vec![0u8; buffer_size];
+                rng.fill(&mut plaintext[..]);
+                let key: [u8; 32] = rng.r#gen();
+                let aes = Aes256Gcm::new(&key.into());
+                let chacha = ChaCha20Poly1305::new(&key.into());
+                let mut buf = plaintext.clone();
+                let mut local = CryptoTotals::new(algorithm, true);
+                let mut count = 0u64;
+                let start = Instant::now();
+                while !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64) {
+                    gate.pace(|| !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64));
+                    // Fresh nonce per record, as a TLS connection would use
+                    let mut nonce = [0u8; 12];
+                    nonce[..8].copy_from_slice(&count.to_le_bytes());
+                    let t = Instant::now();
+                    match algorithm {
+                        CryptoAlgorithm::Sha256 => {
+                            std::hint::black_box(Sha256::digest(&plaintext));
+                        }
+                        CryptoAlgorithm::Blake3 => {
+                            std::hint::black_box(blake3::hash(&plaintext));
+                        }
+                        CryptoAlgorithm::Aes256Gcm | CryptoAlgorithm::ChaCha20Poly1305 => {
+                            buf.copy_from_slice(&plaintext);
+                            let tag = match algorithm {
+                                CryptoAlgorithm::Aes256Gcm => aes.encrypt_in_place_detached(&nonce.into(), b"", &mut buf),
+                                _ => chacha.encrypt_in_place_detached(&nonce.into(), b"", &mut buf),
+                            };
+                            local.forward_secs += t.elapsed().as_secs_f64();
+                            let t = Instant::now();
+                            let opened = tag.and_then(|tag| match algorithm {
+                                CryptoAlgorithm::Aes256Gcm => aes.decrypt_in_place_detached(&nonce.into(), b"", &mut buf, &tag),
+                                _ => chacha.decrypt_in_place_detached(&nonce.into(), b"", &mut buf, &tag),
+                            });
+                            local.decrypt_secs += t.elapsed().as_secs_f64();
+                            if opened.is_err() || buf != plaintext {
+                                local.mismatches += 1;
+                            }
+                        }
+                    }
+                    if !algorithm.is_cipher() {
+                        local.forward_secs += t.elapsed().as_secs_f64();
+                    }
+                    local.bytes += buffer_size as u64;
+                    count += 1;
+                }
+                results.lock().unwrap()[tid] = count;
+                let mut totals = totals.lock().unwrap();
+                if let Some(t) = totals.iter_mut().find(|t| t.algorithm == algorithm) {
+                    t.bytes += local.bytes;
+                    t.forward_secs += local.forward_secs;
+                    t.decrypt_secs += local.decrypt_secs;
+                    t.mismatches += local.mismatches;
+                }
+            }));
+        }
+        for h in handles { let _ = h.join(); }
+        let results = results.lock().unwrap();
+        for (i, &v) in results.iter().enumerate() {
+            if i < op_counts.len() {
+                op_counts[i] = v;
+            }
+        }
+    }
+}
+
+pub fn ui_acceleration(ui: &mut egui::Ui) {
+    let features = acceleration();
+    if features.is_empty() {
+        ui.label("Hardware acceleration: unknown on this architecture");
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.label("Hardware acceleration:");
+        for (name, on) in features {
+            let color = if on { egui::Color32::GREEN } else { egui::Color32::GRAY };
+            ui.colored_label(color, format!("{} {}", if on { "✔" } else { "✖" }, name));
+        }
+    });
+}
+
+pub fn ui_totals(ui: &mut egui::Ui, id: &str, totals: &[CryptoTotals]) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Algorithm"); ui.label("Self-test"); ui.label("Hash/Encrypt"); ui.label("Decrypt"); ui.end_row();
+        for t in totals {
+            ui.label(t.algorithm.label());
+            if t.self_test_passed {
+                ui.colored_label(egui::Color32::GREEN, "✔ pass");
+            } else {
+                ui.colored_label(egui::Color32::RED, "✖ FAIL");
+            }
+            ui.label(format!("{:.2} MB/s", t.forward_mbps()));
+            ui.label(t.decrypt_mbps().map(|d| format!("{:.2} MB/s", d)).unwrap_or_else(|| "-".to_string()));
+            ui.end_row();
+        }
+    });
+    let mismatches: u64 = totals.iter().map(|t| t.mismatches).sum();
+    if mismatches > 0 {
+        ui.colored_label(egui::Color32::RED, format!("⚠ {} decryption(s) failed or did not reproduce the plaintext", mismatches));
+    }
+}
@@ -1,13 +1,174 @@
-use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use eframe::egui;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
 use std::thread;
 use std::time::{Duration, Instant};
-use rand::Rng;
+use crate::app::topology;
+use crate::app::load_profile::{LoadGate, LoadProfile};
+use crate::app::seed;
+use rand::{rngs::StdRng, Rng};
+
+// Tile edge for the blocked kernel; three 64x64 f64 tiles (96 KB) stay within a typical L2
+const TILE: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatrixKernel {
+    Naive,
+    Blocked,
+}
+
+impl MatrixKernel {
+    pub const ALL: [MatrixKernel; 2] = [MatrixKernel::Naive, MatrixKernel::Blocked];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MatrixKernel::Naive => "naive",
+            MatrixKernel::Blocked => "blocked",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatrixKernel::Naive => "Naive i-j-k",
+            MatrixKernel::Blocked => "Cache-blocked (tiled)",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatrixElement {
+    F32,
+    F64,
+    I32,
+}
+
+impl MatrixElement {
+    pub const ALL: [MatrixElement; 3] = [MatrixElement::F32, MatrixElement::F64, MatrixElement::I32];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MatrixElement::F32 => "f32",
+            MatrixElement::F64 => "f64",
+            MatrixElement::I32 => "i32",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatrixElement::F32 => "f32 (single precision)",
+            MatrixElement::F64 => "f64 (double precision)",
+            MatrixElement::I32 => "i32 (integer)",
+        }
+    }
+
+    pub fn bytes(&self) -> usize {
+        match self {
+            MatrixElement::F32 | MatrixElement::I32 => 4,
+            MatrixElement::F64 => 8,
+        }
+    }
+
+    // Integer multiply-adds aren't floating point, so they're reported as GOPS
+    pub fn unit(&self) -> &'static str {
+        match self {
+            MatrixElement::F32 | MatrixElement::F64 => "GFLOPS",
+            MatrixElement::I32 => "GOPS",
+        }
+    }
+}
+
+trait Element: Copy + Send + Sync + 'static {
+    const ZERO: Self;
+    fn random(rng: &mut StdRng) -> Self;
+    // c + a * b, counted as two operations like LINPACK does
+    fn mul_add(c: Self, a: Self, b: Self) -> Self;
+}
+
+impl Element for f32 {
+    const ZERO: Self = 0.0;
+    fn random(rng: &mut StdRng) -> Self { rng.r#gen() }
+    fn mul_add(c: Self, a: Self, b: Self) -> Self { c + a * b }
+}
+
+impl Element for f64 {
+    const ZERO: Self = 0.0;
+    fn random(rng: &mut StdRng) -> Self { rng.r#gen() }
+    fn mul_add(c: Self, a: Self, b: Self) -> Self { c + a * b }
+}
+
+impl Element for i32 {
+    const ZERO: Self = 0;
+    fn random(rng: &mut StdRng) -> Self { rng.gen_range(-1000..1000) }
+    fn mul_add(c: Self, a: Self, b: Self) -> Self { c.wrapping_add(a.wrapping_mul(b)) }
+}
+
+// c = a * b one panel at a time; `keep_going` is asked between panels so large matrices can stop
+// partway. Returns the multiply-adds of the panels that finished.
+fn multiply<T: Element>(kernel: MatrixKernel, n: usize, a: &[T], b: &[T], c: &mut [T], mut keep_going: impl FnMut() -> bool) -> u64 {
+    let mut done = 0u64;
+    match kernel {
+        MatrixKernel::Naive => {
+            for i in 0..n {
+                if !keep_going() {
+                    return done;
+                }
+                for j in 0..n {
+                    let mut sum = T::ZERO;
+                    for k in 0..n {
+                        sum = T::mul_add(sum, a[i*n + k], b[k*n + j]);
+                    }
+                    c[i*n + j] = sum;
+                }
+                done += (n * n) as u64;
+            }
+        }
+        MatrixKernel::Blocked => {
+            c.fill(T::ZERO);
+            for ii in (0..n).step_by(TILE) {
+                let i_end = (ii + TILE).min(n);
+                for kk in (0..n).step_by(TILE) {
+                    if !keep_going() {
+                        return done;
+                    }
+                    let k_end = (kk + TILE).min(n);
+                    for jj in (0..n).step_by(TILE) {
+                        let j_end = (jj + TILE).min(n);
+                        // i-k-j inside the tile walks b and c row-wise, which the compiler vectorizes
+                        for i in ii..i_end {
+                            for k in kk..k_end {
+                                let aik = a[i*n + k];
+                                let b_row = &b[k*n + jj..k*n + j_end];
+                                let c_row = &mut c[i*n + jj..i*n + j_end];
+                                for (cv, &bv) in c_row.iter_mut().zip(b_row) {
+                                    *cv = T::mul_add(*cv, aik, bv);
+                                }
+                            }
+                        }
+                    }
+                    done += ((i_end - ii) * (k_end - kk) * n) as u64;
+                }
+            }
+        }
+    }
+    done
+}
 
 #[derive(Clone)]
 pub struct MatrixStressConfig {
     pub matrix_size: usize,
     pub duration_secs: u32,
     pub threads: usize,
+    pub pinned_cpus: Vec<usize>,
+    pub load_profile: LoadProfile,
+    pub kernel: MatrixKernel,
+    pub element: MatrixElement,
 }
 
 impl MatrixStressConfig {
@@ -16,49 +177,112 @@ impl MatrixStressConfig {
             matrix_size: config.matrix_size,
             duration_secs: config.matrix_duration_secs,
             threads: config.matrix_threads,
+            pinned_cpus: Vec::new(),
+            load_profile: LoadProfile::Constant,
+            kernel: MatrixKernel::from_name(&config.matrix_kernel).unwrap_or(MatrixKernel::Blocked),
+            element: MatrixElement::from_name(&config.matrix_element).unwrap_or(MatrixElement::F64),
         }
     }
+
+    // A and B are shared by all threads, each thread has its own C
+    pub fn memory_bytes(&self) -> usize {
+        self.matrix_size * self.matrix_size * self.element.bytes() * (2 + self.threads)
+    }
+}
+
+// Operations (two per multiply-add) and busy time per thread, summed over runs
+#[derive(Clone, Debug, Default)]
+pub struct MatrixTotals {
+    pub element: Option<MatrixElement>,
+    pub thread_ops: Vec<f64>,
+    pub thread_secs: Vec<f64>,
+}
+
+impl MatrixTotals {
+    pub fn unit(&self) -> &'static str {
+        self.element.map(|e| e.unit()).unwrap_or("GFLOPS")
+    }
+
+    pub fn thread_rates(&self) -> Vec<f64> {
+        self.thread_ops.iter().zip(&self.thread_secs).map(|(&ops, &secs)| if secs > 0.0 { ops / secs / 1e9 } else { 0.0 }).collect()
+    }
+
+    // Threads run side by side, so their rates add up
+    pub fn total_rate(&self) -> f64 {
+        self.thread_rates().iter().sum()
+    }
 }
 
 pub struct MatrixStress {
     pub config: MatrixStressConfig,
+    // Added to by every run, so callers can sum several runs or reset it in between
+    pub totals: Arc<Mutex<MatrixTotals>>,
 }
 
 impl MatrixStress {
     pub fn from_config(config: &crate::app::config::Config) -> Self {
         Self {
             config: MatrixStressConfig::from_config(config),
+            totals: Arc::new(Mutex::new(MatrixTotals::default())),
         }
     }
     pub fn run_with_counts(&self, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) -> u64 {
+        match self.config.element {
+            MatrixElement::F32 => self.run_typed::<f32>(stop_flag, op_counts),
+            MatrixElement::F64 => self.run_typed::<f64>(stop_flag, op_counts),
+            MatrixElement::I32 => self.run_typed::<i32>(stop_flag, op_counts),
+        }
+    }
+    fn run_typed<T: Element>(&self, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) -> u64 {
         let mut handles = Vec::new();
         let results = Arc::new(std::sync::Mutex::new(vec![0u64; self.config.threads]));
         let size = self.config.matrix_size;
+        let kernel = self.config.kernel;
+        let mut rng = seed::stream_rng(seed::current(), "matrix", 0);
+        let a: Arc<Vec<T>> = Arc::new((0..size*size).map(|_| T::random(&mut rng)).collect());
+        let b: Arc<Vec<T>> = Arc::new((0..size*size).map(|_| T::random(&mut rng)).collect());
         let duration = self.config.duration_secs;
+        let pinned_cpus = Arc::new(self.config.pinned_cpus.clone());
+        let gate = Arc::new(LoadGate::new(self.config.load_profile));
+        {
+            let mut totals = self.totals.lock().unwrap();
+            totals.element = Some(self.config.element);
+            totals.thread_ops.resize(self.config.threads, 0.0);
+            totals.thread_secs.resize(self.config.threads, 0.0);
+        }
         for tid in 0..self.config.threads {
             let stop_flag = stop_flag.clone();
             let results = results.clone();
+            let pinned_cpus = pinned_cpus.clone();
+            let gate = gate.clone();
+            let totals = self.totals.clone();
+            let (a, b) = (a.clone(), b.clone());
             handles.push(thread::spawn(move || {
-                let mut rng = rand::thread_rng();
+                topology::pin_worker(&pinned_cpus, tid);
                 let mut count = 0u64;
-                let a: Vec<f64> = (0..size*size).map(|_| rng.r#gen::<f64>()).collect();
-                let b: Vec<f64> = (0..size*size).map(|_| rng.r#gen::<f64>()).collect();
-                let mut c = vec![0.0f64; size*size];
+                let mut multiply_adds = 0u64;
+                let mut c = vec![T::ZERO; size*size];
+                // Time spent paused by the load profile isn't compute time, so it's left out of GFLOPS
+                let mut paced = Duration::ZERO;
                 let start = Instant::now();
-                while !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64) {
-                    // Matrix multiplication: c = a * b
-                    for i in 0..size {
-                        for j in 0..size {
-                            let mut sum = 0.0;
-                            for k in 0..size {
-                                sum += a[i*size + k] * b[k*size + j];
-                            }
-                            c[i*size + j] = sum;
-                        }
+                let keep_going = || !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64);
+                while keep_going() {
+                    let done = multiply(kernel, size, &a, &b, &mut c, || {
+                        paced += gate.pace(keep_going);
+                        keep_going()
+                    });
+                    multiply_adds += done;
+                    // Only whole multiplications count as operations; partial ones still add to GFLOPS
+                    if done == (size * size * size) as u64 {
+                        count += 1;
                     }
-                    count += 1;
+                    std::hint::black_box(&c);
                 }
+                let secs = start.elapsed().saturating_sub(paced).as_secs_f64();
                 results.lock().unwrap()[tid] = count;
+                let mut totals = totals.lock().unwrap();
+                totals.thread_ops[tid] += 2.0 * multiply_adds as f64;
+                totals.thread_secs[tid] += secs;
             }));
         }
         for h in handles { let _ = h.join(); }
@@ -71,3 +295,16 @@ impl MatrixStress {
         results.iter().sum()
     }
 }
+
+pub fn ui_totals(ui: &mut egui::Ui, id: &str, totals: &MatrixTotals) {
+    let unit = totals.unit();
+    ui.label(format!("Total: {:.2} {}", totals.total_rate(), unit));
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Thread"); ui.label(unit); ui.end_row();
+        for (tid, rate) in totals.thread_rates().iter().enumerate() {
+            ui.label(format!("{}", tid));
+            ui.label(format!("{:.2}", rate));
+            ui.end_row();
+        }
+    });
+}
@@ -0,0 +1,315 @@
+use eframe::egui;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::app::topology;
+use crate::app::load_profile::{LoadGate, LoadProfile};
+use crate::app::seed;
+use rand::Rng;
+
+// Per-thread input table; 128 KB of u64 fits in L2, and 16K random branch outcomes are far more
+// than a branch predictor can learn
+const TABLE_LEN: usize = 1 << 14;
+// Pointer-chase steps between stop checks
+const CHASE_BATCH: usize = 1 << 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MicroKernel {
+    Branch,
+    Divide,
+    Transcendental,
+    Chase,
+    Mixed,
+}
+
+impl MicroKernel {
+    pub const ALL: [MicroKernel; 5] = [MicroKernel::Branch, MicroKernel::Divide, MicroKernel::Transcendental, MicroKernel::Chase, MicroKernel::Mixed];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MicroKernel::Branch => "branch",
+            MicroKernel::Divide => "divide",
+            MicroKernel::Transcendental => "transcendental",
+            MicroKernel::Chase => "chase",
+            MicroKernel::Mixed => "mixed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MicroKernel::Branch => "Unpredictable branches",
+            MicroKernel::Divide => "Integer divide",
+            MicroKernel::Transcendental => "FP transcendental",
+            MicroKernel::Chase => "Pointer chasing",
+            MicroKernel::Mixed => "Mixed instructions",
+        }
+    }
+
+    // What one counted operation is
+    pub fn op(&self) -> &'static str {
+        match self {
+            MicroKernel::Branch => "data-dependent branch",
+            MicroKernel::Divide => "64-bit divide + remainder",
+            MicroKernel::Transcendental => "sin, cos, exp, ln and sqrt",
+            MicroKernel::Chase => "dependent load",
+            MicroKernel::Mixed => "int multiply, shift, FP multiply-add, branch and dependent load",
+        }
+    }
+}
+
+// One pass over the table; the same table always gives the same checksum, so a different one
+// means the core computed something wrong
+fn table_pass(kernel: MicroKernel, table: &[u64]) -> u64 {
+    match kernel {
+        MicroKernel::Branch => {
+            let mut acc = 0u64;
+            for &v in table {
+                // Four unrelated arms keep the compiler from turning this into conditional moves
+                match v & 3 {
+                    0 => acc = acc.wrapping_add(v),
+                    1 => acc ^= v.rotate_left(13),
+                    2 => acc = acc.wrapping_mul(v | 1),
+                    _ => acc = acc.wrapping_sub(v >> 7),
+                }
+            }
+            acc
+        }
+        MicroKernel::Divide => {
+            let mut acc = 0u64;
+            for &v in table {
+                // Divisor comes from the data, so it can't be strength-reduced to a multiply
+                let d = (v >> 32) | 3;
+                let n = acc ^ v;
+                acc = acc.wrapping_add(n / d).wrapping_add(n % d);
+            }
+            acc
+        }
+        MicroKernel::Transcendental => {
+            let mut acc = 0.0f64;
+            for &v in table {
+                let x = (v >> 11) as f64 / (1u64 << 53) as f64 * 10.0;
+                acc += x.sin() * x.cos() + (-x).exp() + (x + 1.0).ln() + x.sqrt();
+            }
+            acc.to_bits()
+        }
+        MicroKernel::Mixed => {
+            let mask = table.len() - 1;
+            let mut a = 0u64;
+            let mut f = 1.0f64;
+            for (i, &v) in table.iter().enumerate() {
+                let w = table[(a as usize ^ i) & mask];
+                a = a.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(v ^ w);
+                a ^= a >> 29;
+                f = f * 0.999_999 + (w >> 11) as f64 * 1e-16;
+                if a & 1 == 0 {
+                    a = a.rotate_left(5);
+                }
+            }
+            a ^ f.to_bits()
+        }
+        MicroKernel::Chase => unreachable!("pointer chasing walks its own buffer"),
+    }
+}
+
+// Sattolo's shuffle: one cycle through every slot, so the walk returns to its start after exactly
+// `len` steps and the prefetcher can't guess the next address
+fn chase_cycle(len: usize) -> Vec<u32> {
+    let mut rng = seed::stream_rng(seed::current(), "micro_chase", 0);
+    let mut next: Vec<u32> = (0..len as u32).collect();
+    for i in (1..len).rev() {
+        let j = rng.gen_range(0..i);
+        next.swap(i, j);
+    }
+    next
+}
+
+#[derive(Clone)]
+pub struct MicroStressConfig {
+    pub duration_secs: u32,
+    pub threads: usize,
+    pub pinned_cpus: Vec<usize>,
+    pub load_profile: LoadProfile,
+    // None runs every kernel in turn, splitting the duration between them
+    pub kernel: Option<MicroKernel>,
+    // Pointer-chase buffer, shared by all threads; sized past the last-level cache by default
+    pub chase_bytes: usize,
+}
+
+impl MicroStressConfig {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            duration_secs: config.micro_duration_secs,
+            threads: config.micro_threads,
+            pinned_cpus: Vec::new(),
+            load_profile: LoadProfile::Constant,
+            kernel: MicroKernel::from_name(&config.micro_kernel),
+            chase_bytes: config.micro_chase_bytes,
+        }
+    }
+
+    pub fn kernels(&self) -> Vec<MicroKernel> {
+        self.kernel.map(|k| vec![k]).unwrap_or_else(|| MicroKernel::ALL.to_vec())
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.kernel.map(|k| k.name()).unwrap_or("all")
+    }
+}
+
+// Operations and busy time per thread for one kernel, summed over runs
+#[derive(Clone, Debug)]
+pub struct MicroTotals {
+    pub kernel: MicroKernel,
+    pub thread_ops: Vec<f64>,
+    pub thread_secs: Vec<f64>,
+    // Passes whose checksum differed from the thread's first pass, or chase cycles that didn't close
+    pub mismatches: u64,
+}
+
+impl MicroTotals {
+    fn new(kernel: MicroKernel) -> Self {
+        Self { kernel, thread_ops: Vec::new(), thread_secs: Vec::new(), mismatches: 0 }
+    }
+
+    pub fn thread_rates(&self) -> Vec<f64> {
+        self.thread_ops.iter().zip(&self.thread_secs).map(|(&ops, &secs)| if secs > 0.0 { ops / secs } else { 0.0 }).collect()
+    }
+
+    // Threads run side by side, so their rates add up
+    pub fn ops_per_sec(&self) -> f64 {
+        self.thread_rates().iter().sum()
+    }
+}
+
+pub struct MicroStress {
+    pub config: MicroStressConfig,
+    // One entry per kernel, added to by every run so callers can sum several runs or reset it in between
+    pub totals: Arc<Mutex<Vec<MicroTotals>>>,
+}
+
+impl MicroStress {
+    pub fn run_with_counts(&self, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) -> u64 {
+        let kernels = self.config.kernels();
+        let secs_each = (self.config.duration_secs / kernels.len() as u32).max(1);
+        let mut counts = vec![0u64; op_counts.len()];
+        op_counts.iter_mut().for_each(|c| *c = 0);
+        for kernel in kernels {
+            if stop_flag.load(Ordering::SeqCst) {
+                break;
+            }
+            self.run_kernel(kernel, secs_each, stop_flag.clone(), &mut counts);
+            for (total, &c) in op_counts.iter_mut().zip(&counts) {
+                *total += c;
+            }
+        }
+        op_counts.iter().sum()
+    }
+    fn run_kernel(&self, kernel: MicroKernel, duration: u32, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) {
+        let mut handles = Vec::new();
+        let results = Arc::new(std::sync::Mutex::new(vec![0u64; self.config.threads]));
+        let run_seed = seed::current();
+        let pinned_cpus = Arc::new(self.config.pinned_cpus.clone());
+        let gate = Arc::new(LoadGate::new(self.config.load_profile));
+        let chase = Arc::new(if kernel == MicroKernel::Chase {
+            chase_cycle((self.config.chase_bytes / 4).clamp(2, u32::MAX as usize))
+        } else {
+            Vec::new()
+        });
+        {
+            let mut totals = self.totals.lock().unwrap();
+            if !totals.iter().any(|t| t.kernel == kernel) {
+                totals.push(MicroTotals::new(kernel));
+            }
+            if let Some(t) = totals.iter_mut().find(|t| t.kernel == kernel) {
+                t.thread_ops.resize(self.config.threads, 0.0);
+                t.thread_secs.resize(self.config.threads, 0.0);
+            }
+        }
+        for tid in 0..self.config.threads {
+            let stop_flag = stop_flag.clone();
+            let results = results.clone();
+            let pinned_cpus = pinned_cpus.clone();
+            let gate = gate.clone();
+            let totals = self.totals.clone();
+            let chase = chase.clone();
+            handles.push(thread::spawn(move || {
+                topology::pin_worker(&pinned_cpus, tid);
+                let mut rng = seed::stream_rng(run_seed, "micro", tid as u64);
+                let table: Vec<u64> = (0..TABLE_LEN).map(|_| rng.r#gen()).collect();
+                let mut count = 0u64;
+                let mut mismatches = 0u64;
+                let mut reference = None;
+                // Threads start spread around the cycle so they don't share cache lines
+                let chase_start = if chase.is_empty() { 0 } else { (tid * chase.len() / 64).min(chase.len() - 1) as u32 };
+                let mut position = chase_start;
+                let mut steps = 0usize;
+                // Paused time isn't running the kernel, so it's left out of the ops/sec
+                let mut paced = Duration::ZERO;
+                let start = Instant::now();
+                let keep_going = || !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64);
+                while keep_going() {
+                    paced += gate.pace(keep_going);
+                    if kernel == MicroKernel::Chase {
+                        for _ in 0..CHASE_BATCH {
+                            position = chase[position as usize];
+                            steps += 1;
+                            if steps == chase.len() {
+                                if position != chase_start {
+                                    mismatches += 1;
+                                    position = chase_start;
+                                }
+                                steps = 0;
+                            }
+                        }
+                        std::hint::black_box(position);
+                        count += CHASE_BATCH as u64;
+                    } else {
+                        let checksum = table_pass(kernel, std::hint::black_box(&table));
+                        match reference {
+                            None => reference = Some(checksum),
+                            Some(r) if r != checksum => mismatches += 1,
+                            Some(_) => {}
+                        }
+                        count += TABLE_LEN as u64;
+                    }
+                }
+                let secs = start.elapsed().saturating_sub(paced).as_secs_f64();
+                results.lock().unwrap()[tid] = count;
+                let mut totals = totals.lock().unwrap();
+                if let Some(t) = totals.iter_mut().find(|t| t.kernel == kernel) {
+                    t.thread_ops[tid] += count as f64;
+                    t.thread_secs[tid] += secs;
+                    t.mismatches += mismatches;
+                }
+            }));
+        }
+        for h in handles { let _ = h.join(); }
+        let results = results.lock().unwrap();
+        for (i, &v) in results.iter().enumerate() {
+            if i < op_counts.len() {
+                op_counts[i] = v;
+            }
+        }
+    }
+}
+
+pub fn ui_totals(ui: &mut egui::Ui, id: &str, totals: &[MicroTotals]) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Kernel"); ui.label("Mops/s"); ui.label("Per thread"); ui.label("Mismatches"); ui.end_row();
+        for t in totals {
+            ui.label(t.kernel.label()).on_hover_text(format!("One op = {}", t.kernel.op()));
+            ui.label(format!("{:.2}", t.ops_per_sec() / 1e6));
+            ui.label(t.thread_rates().iter().map(|r| format!("{:.1}", r / 1e6)).collect::<Vec<_>>().join(" / "));
+            if t.mismatches > 0 {
+                ui.colored_label(egui::Color32::RED, format!("✖ {}", t.mismatches));
+            } else {
+                ui.colored_label(egui::Color32::GREEN, "✔ 0");
+            }
+            ui.end_row();
+        }
+    });
+}
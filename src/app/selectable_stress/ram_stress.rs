@@ -1,6 +1,9 @@
-use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use std::sync::{Arc, atomic::{AtomicBool, AtomicU64, Ordering}};
 use std::thread;
 use std::time::{Duration, Instant};
+use crate::app::topology;
+use crate::app::load_profile::{LoadGate, LoadProfile};
+use crate::app::seed;
 use rand::Rng;
 
 #[derive(Clone)]
@@ -8,6 +11,8 @@ pub struct RamStressConfig {
     pub buffer_size: usize, // in bytes
     pub duration_secs: u32,
     pub threads: usize,
+    pub pinned_cpus: Vec<usize>,
+    pub load_profile: LoadProfile,
 }
 
 impl RamStressConfig {
@@ -16,34 +21,52 @@ impl RamStressConfig {
             buffer_size: config.ram_buffer_size,
             duration_secs: config.ram_duration_secs,
             threads: config.ram_threads,
+            pinned_cpus: Vec::new(),
+            load_profile: LoadProfile::Constant,
         }
     }
 }
 
 pub struct RamStress {
     pub config: RamStressConfig,
+    // Running op total across threads, updated every 1024 ops so callers can chart a live rate
+    pub live_ops: Arc<AtomicU64>,
 }
 
 impl RamStress {
     pub fn from_config(config: &crate::app::config::Config) -> Self {
         Self {
             config: RamStressConfig::from_config(config),
+            live_ops: Arc::new(AtomicU64::new(0)),
         }
     }
     pub fn run_with_counts(&self, stop_flag: Arc<AtomicBool>, op_counts: &mut [u64]) -> u64 {
         let mut handles = Vec::new();
         let results = Arc::new(std::sync::Mutex::new(vec![0u64; self.config.threads]));
         let buffer_size = self.config.buffer_size;
+        let run_seed = seed::current();
         let duration = self.config.duration_secs;
+        let pinned_cpus = Arc::new(self.config.pinned_cpus.clone());
+        let gate = Arc::new(LoadGate::new(self.config.load_profile));
         for tid in 0..self.config.threads {
             let stop_flag = stop_flag.clone();
             let results = results.clone();
+            let pinned_cpus = pinned_cpus.clone();
+            let gate = gate.clone();
+            let live_ops = self.live_ops.clone();
             handles.push(thread::spawn(move || {
-                let mut rng = rand::thread_rng();
+                topology::pin_worker(&pinned_cpus, tid);
+                let mut rng = seed::stream_rng(run_seed, "ram", tid as u64);
                 let mut count = 0u64;
                 let mut buffer = vec![0u8; buffer_size];
                 let start = Instant::now();
                 while !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64) {
+                    if count.is_multiple_of(1024) {
+                        if count > 0 {
+                            live_ops.fetch_add(1024, Ordering::Relaxed);
+                        }
+                        gate.pace(|| !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64));
+                    }
                     // Random write
                     let idx = rng.gen_range(0..buffer_size);
                     buffer[idx] = rng.r#gen();
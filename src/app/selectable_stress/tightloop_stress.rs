@@ -1,11 +1,15 @@
 use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 use std::thread;
 use std::time::{Duration, Instant};
+use crate::app::topology;
+use crate::app::load_profile::{LoadGate, LoadProfile};
 
 #[derive(Clone)]
 pub struct TightLoopStressConfig {
     pub duration_secs: u32,
     pub threads: usize,
+    pub pinned_cpus: Vec<usize>,
+    pub load_profile: LoadProfile,
 }
 
 impl TightLoopStressConfig {
@@ -13,6 +17,8 @@ impl TightLoopStressConfig {
         Self {
             duration_secs: config.tightloop_duration_secs,
             threads: config.tightloop_threads,
+            pinned_cpus: Vec::new(),
+            load_profile: LoadProfile::Constant,
         }
     }
 }
@@ -31,13 +37,21 @@ impl TightLoopStress {
         let mut handles = Vec::new();
         let results = Arc::new(std::sync::Mutex::new(vec![0u64; self.config.threads]));
         let duration = self.config.duration_secs;
+        let pinned_cpus = Arc::new(self.config.pinned_cpus.clone());
+        let gate = Arc::new(LoadGate::new(self.config.load_profile));
         for tid in 0..self.config.threads {
             let stop_flag = stop_flag.clone();
             let results = results.clone();
+            let pinned_cpus = pinned_cpus.clone();
+            let gate = gate.clone();
             handles.push(thread::spawn(move || {
+                topology::pin_worker(&pinned_cpus, tid);
                 let mut count = 0u64;
                 let start = Instant::now();
                 while !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64) {
+                    if count.is_multiple_of(1024) {
+                        gate.pace(|| !stop_flag.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(duration as u64));
+                    }
                     count += 1;
                 }
                 results.lock().unwrap()[tid] = count;
@@ -8,8 +8,20 @@ use std::{
     time::{Duration, Instant},
 };
 use atomic_float::AtomicF64;
-use rand::{thread_rng, Rng};
 use chrono::Local;
+use rand::Rng;
+use crate::app::storage_sync::{self, SyncSummary};
+use crate::app::telemetry;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::seed;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StorageMode {
+    Throughput,
+    Durability,
+}
 
 pub struct StorageStress {
     running: Arc<AtomicBool>,
@@ -22,6 +34,10 @@ pub struct StorageStress {
     avg_write: Arc<AtomicF64>,
     avg_read: Arc<AtomicF64>,
     buffer_mb: Arc<Mutex<u32>>,
+    mode: Arc<Mutex<StorageMode>>,
+    sync_write_bytes: Arc<Mutex<u32>>,
+    sync_live: Arc<Mutex<Option<SyncSummary>>>,
+    sync_results: Arc<Mutex<Vec<SyncSummary>>>,
 }
 
 impl StorageStress {
@@ -37,181 +53,333 @@ impl StorageStress {
             avg_write: Arc::new(AtomicF64::new(0.0)),
             avg_read: Arc::new(AtomicF64::new(0.0)),
             buffer_mb: Arc::new(Mutex::new(config.storage_buffer_mb)),
+            mode: Arc::new(Mutex::new(StorageMode::Throughput)),
+            sync_write_bytes: Arc::new(Mutex::new(config.storage_sync_write_bytes)),
+            sync_live: Arc::new(Mutex::new(None)),
+            sync_results: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
 
 impl StorageStress {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn start(&self, ctx: &egui::Context, dev_mode: bool) {
+        let running = self.running.clone();
+        let write_speeds = self.write_speeds.clone();
+        let read_speeds = self.read_speeds.clone();
+        let current_write_speed = self.current_write_speed.clone();
+        let current_read_speed = self.current_read_speed.clone();
+        let avg_write = self.avg_write.clone();
+        let avg_read = self.avg_read.clone();
+        let log_path = self.log_path.clone();
+        let duration_secs = *self.duration_secs.lock().unwrap();
+        let buffer_mb = *self.buffer_mb.lock().unwrap();
+        let ctx = ctx.clone();
+        running.store(true, Ordering::SeqCst);
+        write_speeds.lock().unwrap().clear();
+        read_speeds.lock().unwrap().clear();
+        current_write_speed.store(0.0, Ordering::SeqCst);
+        current_read_speed.store(0.0, Ordering::SeqCst);
+        avg_write.store(0.0, Ordering::SeqCst);
+        avg_read.store(0.0, Ordering::SeqCst);
+        if dev_mode {
+            println!("[DEV] Starting storage stress test: buffer_mb={}, duration_secs={}", buffer_mb, duration_secs);
+        }
+        thread::spawn(move || {
+            let mut rng = seed::stream_rng(seed::current(), "storage_buffer", 0);
+            let hash = seed::file_hash();
+            let date = Local::now().format("%Y%m%d_%H%M%S");
+            let log_dir = crate::app::log_dir(dev_mode);
+            let _ = std::fs::create_dir_all(&log_dir);
+            let log_file_name = log_dir.join(format!("storage_stress_{}_{}_buf{}_dur{}.csv", hash, date, buffer_mb, duration_secs));
+            if dev_mode {
+                println!("[DEV] Created log file: {}", log_file_name.display());
+            }
+            // Ensure log directory exists
+            if let Some(parent) = std::path::Path::new(&log_file_name).parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).unwrap();
+            {
+                let mut log_path_guard = log_path.lock().unwrap();
+                *log_path_guard = Some(PathBuf::from(&log_file_name));
+            }
+            writeln!(log_file, "timestamp,operation,mbps").unwrap();
+            inventory::get().write_csv_comments(&mut log_file);
+            let journal = RunJournal::begin(dev_mode, "storage_stress", Some(&log_file_name));
+            let watch = watchdog::watch("storage_stress", duration_secs as u64, {
+                let running = running.clone();
+                move || running.store(false, Ordering::SeqCst)
+            });
+            let test_file_path = "storage_stress_testfile.tmp";
+            let file_size_mb = 512; // 512 MB
+            let buffer_size = buffer_mb as usize * 1024 * 1024;
+            let mut buffer = vec![0u8; buffer_size];
+            rng.fill(&mut buffer[..]);
+            // Write test
+            let write_end = Instant::now() + Duration::from_secs((duration_secs / 2).max(1) as u64);
+            let mut file = File::create(test_file_path).unwrap();
+            let mut written = 0;
+            let mut total_written = 0;
+            let mut last_report = Instant::now();
+            let mut last_written = 0;
+            let write_start = Instant::now();
+            while Instant::now() < write_end && running.load(Ordering::SeqCst) {
+                let to_write = std::cmp::min(buffer_size, file_size_mb * 1024 * 1024 - written);
+                file.write_all(&buffer[..to_write]).unwrap();
+                file.flush().unwrap();
+                written += to_write;
+                total_written += to_write;
+                if written >= file_size_mb * 1024 * 1024 {
+                    // Overwrite from start
+                    file.seek(SeekFrom::Start(0)).unwrap();
+                    written = 0;
+                    last_written = 0;
+                }
+                if last_report.elapsed() >= Duration::from_millis(200) {
+                    watch.heartbeat();
+                    let elapsed = last_report.elapsed().as_secs_f64();
+                    let mbps = (written - last_written) as f64 / 1024.0 / 1024.0 / elapsed;
+                    current_write_speed.store(mbps, Ordering::SeqCst);
+                    telemetry::emit("storage_stress", "write_mbps", mbps, None);
+                    write_speeds.lock().unwrap().push(mbps);
+                    writeln!(log_file, "{},{},{}", Local::now().to_rfc3339(), "write", mbps).unwrap();
+                    last_report = Instant::now();
+                    last_written = written;
+                    ctx.request_repaint();
+                }
+            }
+            let write_total_time = write_start.elapsed().as_secs_f64();
+            let avg_write_val = total_written as f64 / 1024.0 / 1024.0 / write_total_time;
+            avg_write.store(avg_write_val, Ordering::SeqCst);
+            current_write_speed.store(0.0, Ordering::SeqCst);
+            file.sync_all().unwrap();
+            // Read test
+            let read_end = Instant::now() + Duration::from_secs((duration_secs / 2).max(1) as u64);
+            let mut file = File::open(test_file_path).unwrap();
+            file.seek(SeekFrom::Start(0)).unwrap();
+            let mut read = 0;
+            let mut total_read = 0;
+            let mut last_report = Instant::now();
+            let mut last_read = 0;
+            let read_start = Instant::now();
+            while Instant::now() < read_end && running.load(Ordering::SeqCst) {
+                let to_read = std::cmp::min(buffer_size, file_size_mb * 1024 * 1024 - read);
+                file.read_exact(&mut buffer[..to_read]).unwrap();
+                read += to_read;
+                total_read += to_read;
+                if read >= file_size_mb * 1024 * 1024 {
+                    // Re-read from start
+                    file.seek(SeekFrom::Start(0)).unwrap();
+                    read = 0;
+                    last_read = 0;
+                }
+                if last_report.elapsed() >= Duration::from_millis(200) {
+                    watch.heartbeat();
+                    let elapsed = last_report.elapsed().as_secs_f64();
+                    let mbps = (read - last_read) as f64 / 1024.0 / 1024.0 / elapsed;
+                    current_read_speed.store(mbps, Ordering::SeqCst);
+                    telemetry::emit("storage_stress", "read_mbps", mbps, None);
+                    read_speeds.lock().unwrap().push(mbps);
+                    writeln!(log_file, "{},{},{}", Local::now().to_rfc3339(), "read", mbps).unwrap();
+                    last_report = Instant::now();
+                    last_read = read;
+                    ctx.request_repaint();
+                }
+            }
+            let read_total_time = read_start.elapsed().as_secs_f64();
+            let avg_read_val = total_read as f64 / 1024.0 / 1024.0 / read_total_time;
+            avg_read.store(avg_read_val, Ordering::SeqCst);
+            current_read_speed.store(0.0, Ordering::SeqCst);
+            std::fs::remove_file(test_file_path).ok();
+            if let Some(journal) = journal {
+                journal.finish();
+            }
+            running.store(false, Ordering::SeqCst);
+            ctx.request_repaint();
+            if dev_mode {
+                println!("[DEV] Storage stress test thread finished");
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    // Used by presets that run this test with their own duration
+    pub fn set_duration_secs(&self, secs: u32) {
+        *self.duration_secs.lock().unwrap() = secs;
+    }
+
+    pub fn duration_secs(&self) -> u32 {
+        *self.duration_secs.lock().unwrap()
+    }
+
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.log_path.lock().unwrap().clone()
+    }
+
+    // Live (write, read) MB/s, for presets that chart this test alongside others
+    pub fn speed_handles(&self) -> (Arc<AtomicF64>, Arc<AtomicF64>) {
+        (self.current_write_speed.clone(), self.current_read_speed.clone())
+    }
+
+    // Clearing it stops the test, for presets whose watchdog has to stop it from another thread
+    pub fn running_handle(&self) -> Arc<AtomicBool> {
+        self.running.clone()
+    }
+
     pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
         ui.heading("Storage Stress Test");
         ui.add_space(10.0);
-        ui.label("This test will write and read a large file to measure disk throughput (MB/s).");
+        let mut mode = *self.mode.lock().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Mode:");
+            let idle = !self.running.load(Ordering::SeqCst);
+            ui.add_enabled_ui(idle, |ui| {
+                ui.selectable_value(&mut mode, StorageMode::Throughput, "Throughput");
+                ui.selectable_value(&mut mode, StorageMode::Durability, "Durable Write Latency");
+            });
+            *self.mode.lock().unwrap() = mode;
+        });
+        match mode {
+            StorageMode::Throughput => ui.label("This test will write and read a large file to measure disk throughput (MB/s)."),
+            StorageMode::Durability => ui.label("This test does small appends that must reach stable storage, comparing buffered + fsync, buffered + fdatasync, O_DSYNC and O_DIRECT paths by sync latency."),
+        };
         ui.add_space(10.0);
         let mut duration = *self.duration_secs.lock().unwrap();
         let mut buffer_mb = *self.buffer_mb.lock().unwrap();
+        let mut sync_write_bytes = *self.sync_write_bytes.lock().unwrap();
         ui.horizontal(|ui| {
             ui.label("Duration (seconds):");
             if ui.add(egui::DragValue::new(&mut duration).range(5..=300)).changed() {
                 *self.duration_secs.lock().unwrap() = duration;
             }
         });
-        ui.horizontal(|ui| {
-            ui.label("Chunk Size (MB):");
-            if ui.add(egui::DragValue::new(&mut buffer_mb).range(1..=128)).changed() {
-                *self.buffer_mb.lock().unwrap() = buffer_mb;
+        match mode {
+            StorageMode::Throughput => {
+                ui.horizontal(|ui| {
+                    ui.label("Chunk Size (MB):");
+                    if ui.add(egui::DragValue::new(&mut buffer_mb).range(1..=128)).changed() {
+                        *self.buffer_mb.lock().unwrap() = buffer_mb;
+                    }
+                });
             }
-        });
+            StorageMode::Durability => {
+                ui.horizontal(|ui| {
+                    ui.label("Append Size (bytes):");
+                    if ui.add(egui::DragValue::new(&mut sync_write_bytes).range(512..=1048576).speed(512)).changed() {
+                        *self.sync_write_bytes.lock().unwrap() = sync_write_bytes;
+                    }
+                    ui.label("(rounded up to 4 KiB for O_DIRECT alignment)");
+                });
+            }
+        }
         ui.add_space(10.0);
+        if mode == StorageMode::Durability {
+            self.ui_durability(ctx, ui, dev_mode, duration, sync_write_bytes);
+            return;
+        }
         if ui.button(if self.running.load(Ordering::SeqCst) { "Stop Storage Stress" } else { "Start Storage Stress" }).clicked() {
+            if self.running.load(Ordering::SeqCst) {
+                self.stop();
+            } else {
+                self.start(ctx, dev_mode);
+            }
+        }
+        // Visualizer and stats
+        let live_write = self.current_write_speed.load(Ordering::SeqCst);
+        let live_read = self.current_read_speed.load(Ordering::SeqCst);
+        let avg_write = self.avg_write.load(Ordering::SeqCst);
+        let avg_read = self.avg_read.load(Ordering::SeqCst);
+        ui.add_space(10.0);
+        ui.label(format!("Write: Avg {:.2} MB/s | Live {:.2} MB/s", avg_write, live_write));
+        draw_speed_bar(ui, live_write, "Write Speed");
+        ui.label(format!("Read:  Avg {:.2} MB/s | Live {:.2} MB/s", avg_read, live_read));
+        draw_speed_bar(ui, live_read, "Read Speed");
+        if self.running.load(Ordering::SeqCst) {
+            ui.colored_label(egui::Color32::YELLOW, "Test Running...");
+        } else if let Some(log_path) = &*self.log_path.lock().unwrap() {
+            ui.label(format!("Log saved to: {}", log_path.display()));
+        }
+    }
+}
+
+impl StorageStress {
+    fn ui_durability(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool, duration_secs: u32, write_bytes: u32) {
+        if ui.button(if self.running.load(Ordering::SeqCst) { "Stop Durability Test" } else { "Start Durability Test" }).clicked() {
             let running = self.running.clone();
-            let write_speeds = self.write_speeds.clone();
-            let read_speeds = self.read_speeds.clone();
-            let current_write_speed = self.current_write_speed.clone();
-            let current_read_speed = self.current_read_speed.clone();
-            let avg_write = self.avg_write.clone();
-            let avg_read = self.avg_read.clone();
-            let log_path = self.log_path.clone();
-            let duration_secs = *self.duration_secs.lock().unwrap();
-            let buffer_mb = *self.buffer_mb.lock().unwrap();
-            let ctx = ctx.clone();
             if running.load(Ordering::SeqCst) {
                 running.store(false, Ordering::SeqCst);
             } else {
                 running.store(true, Ordering::SeqCst);
-                write_speeds.lock().unwrap().clear();
-                read_speeds.lock().unwrap().clear();
-                current_write_speed.store(0.0, Ordering::SeqCst);
-                current_read_speed.store(0.0, Ordering::SeqCst);
-                avg_write.store(0.0, Ordering::SeqCst);
-                avg_read.store(0.0, Ordering::SeqCst);
-                let dev_mode = dev_mode;
+                self.sync_results.lock().unwrap().clear();
+                let live = self.sync_live.clone();
+                let results = self.sync_results.clone();
+                let log_path = self.log_path.clone();
+                let ctx = ctx.clone();
                 if dev_mode {
-                    println!("[DEV] Starting storage stress test: buffer_mb={}, duration_secs={}", buffer_mb, duration_secs);
+                    println!("[DEV] Starting storage durability test: write_bytes={}, duration_secs={}", write_bytes, duration_secs);
                 }
                 thread::spawn(move || {
-                    let mut rng = thread_rng();
-                    let hash: u16 = rng.gen_range(1000..9999);
+                    let hash = seed::file_hash();
                     let date = Local::now().format("%Y%m%d_%H%M%S");
-                    let _exe_dir = std::env::current_exe().ok().and_then(|p| p.parent().map(|d| d.to_path_buf()));
-                    let log_dir = if dev_mode {
-                        std::path::PathBuf::from("log")
-                    } else {
-                        std::env::current_exe().ok().and_then(|p| p.parent().map(|d| d.to_path_buf())).unwrap_or_else(|| std::path::PathBuf::from("."))
-                            .join("log")
-                    };
+                    let log_dir = crate::app::log_dir(dev_mode);
                     let _ = std::fs::create_dir_all(&log_dir);
-                    let log_file_name = log_dir.join(format!("storage_stress_{}_{}_buf{}_dur{}.csv", hash, date, buffer_mb, duration_secs));
-                    if dev_mode {
-                        println!("[DEV] Created log file: {}", log_file_name.display());
-                    }
-                    // Ensure log directory exists
-                    if let Some(parent) = std::path::Path::new(&log_file_name).parent() {
-                        let _ = std::fs::create_dir_all(parent);
-                    }
-                    let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).unwrap();
-                    {
-                        let mut log_path_guard = log_path.lock().unwrap();
-                        *log_path_guard = Some(PathBuf::from(&log_file_name));
-                    }
-                    writeln!(log_file, "timestamp,operation,mbps").unwrap();
-                    let test_file_path = "storage_stress_testfile.tmp";
-                    let file_size_mb = 512; // 512 MB
-                    let buffer_size = buffer_mb as usize * 1024 * 1024;
-                    let mut buffer = vec![0u8; buffer_size];
-                    rng.fill(&mut buffer[..]);
-                    // Write test
-                    let write_end = Instant::now() + Duration::from_secs((duration_secs / 2).max(1) as u64);
-                    let mut file = File::create(test_file_path).unwrap();
-                    let mut written = 0;
-                    let mut total_written = 0;
-                    let mut last_report = Instant::now();
-                    let mut last_written = 0;
-                    let write_start = Instant::now();
-                    while Instant::now() < write_end && running.load(Ordering::SeqCst) {
-                        let to_write = std::cmp::min(buffer_size, file_size_mb * 1024 * 1024 - written);
-                        file.write_all(&buffer[..to_write]).unwrap();
-                        file.flush().unwrap();
-                        written += to_write;
-                        total_written += to_write;
-                        if written >= file_size_mb * 1024 * 1024 {
-                            // Overwrite from start
-                            file.seek(SeekFrom::Start(0)).unwrap();
-                            written = 0;
-                            last_written = 0;
-                        }
-                        if last_report.elapsed() >= Duration::from_millis(200) {
-                            let elapsed = last_report.elapsed().as_secs_f64();
-                            let mbps = (written - last_written) as f64 / 1024.0 / 1024.0 / elapsed;
-                            current_write_speed.store(mbps, Ordering::SeqCst);
-                            write_speeds.lock().unwrap().push(mbps);
-                            writeln!(log_file, "{},{},{}", Local::now().to_rfc3339(), "write", mbps).unwrap();
-                            last_report = Instant::now();
-                            last_written = written;
-                            ctx.request_repaint();
-                        }
-                    }
-                    let write_total_time = write_start.elapsed().as_secs_f64();
-                    let avg_write_val = total_written as f64 / 1024.0 / 1024.0 / write_total_time;
-                    avg_write.store(avg_write_val, Ordering::SeqCst);
-                    file.sync_all().unwrap();
-                    // Read test
-                    let read_end = Instant::now() + Duration::from_secs((duration_secs / 2).max(1) as u64);
-                    let mut file = File::open(test_file_path).unwrap();
-                    file.seek(SeekFrom::Start(0)).unwrap();
-                    let mut read = 0;
-                    let mut total_read = 0;
-                    let mut last_report = Instant::now();
-                    let mut last_read = 0;
-                    let read_start = Instant::now();
-                    while Instant::now() < read_end && running.load(Ordering::SeqCst) {
-                        let to_read = std::cmp::min(buffer_size, file_size_mb * 1024 * 1024 - read);
-                        file.read_exact(&mut buffer[..to_read]).unwrap();
-                        read += to_read;
-                        total_read += to_read;
-                        if read >= file_size_mb * 1024 * 1024 {
-                            // Re-read from start
-                            file.seek(SeekFrom::Start(0)).unwrap();
-                            read = 0;
-                            last_read = 0;
+                    let log_file_name = log_dir.join(format!("storage_stress_{}_{}_sync{}_dur{}.csv", hash, date, write_bytes, duration_secs));
+                    if let Ok(mut log_file) = OpenOptions::new().create(true).append(true).open(&log_file_name) {
+                        if dev_mode {
+                            println!("[DEV] Created log file: {}", log_file_name.display());
                         }
-                        if last_report.elapsed() >= Duration::from_millis(200) {
-                            let elapsed = last_report.elapsed().as_secs_f64();
-                            let mbps = (read - last_read) as f64 / 1024.0 / 1024.0 / elapsed;
-                            current_read_speed.store(mbps, Ordering::SeqCst);
-                            read_speeds.lock().unwrap().push(mbps);
-                            writeln!(log_file, "{},{},{}", Local::now().to_rfc3339(), "read", mbps).unwrap();
-                            last_report = Instant::now();
-                            last_read = read;
-                            ctx.request_repaint();
+                        *log_path.lock().unwrap() = Some(log_file_name.clone());
+                        writeln!(log_file, "{}", storage_sync::DURABILITY_CSV_HEADER).ok();
+                        inventory::get().write_csv_comments(&mut log_file);
+                        let journal = RunJournal::begin(dev_mode, "storage_durability", Some(&log_file_name));
+                        let _watch = watchdog::watch("storage_durability", duration_secs as u64, {
+                            let running = running.clone();
+                            move || running.store(false, Ordering::SeqCst)
+                        });
+                        // Next to the log rather than the working directory, named per process like metadata scratch trees
+                        let test_file = log_dir.join(format!("storage_sync_{}_{}.tmp", std::process::id(), hash));
+                        let plan = storage_sync::DurabilityPlan { test_file: &test_file, write_bytes: write_bytes as usize, duration_secs };
+                        storage_sync::run_durability_test(&plan, &running, &live, &results, &mut log_file, &ctx);
+                        if let Some(journal) = journal {
+                            journal.finish();
                         }
                     }
-                    let read_total_time = read_start.elapsed().as_secs_f64();
-                    let avg_read_val = total_read as f64 / 1024.0 / 1024.0 / read_total_time;
-                    avg_read.store(avg_read_val, Ordering::SeqCst);
-                    std::fs::remove_file(test_file_path).ok();
                     running.store(false, Ordering::SeqCst);
                     ctx.request_repaint();
                     if dev_mode {
-                        println!("[DEV] Storage stress test thread finished");
+                        println!("[DEV] Storage durability test thread finished");
                     }
                 });
             }
         }
-        // Visualizer and stats
-        let live_write = self.current_write_speed.load(Ordering::SeqCst);
-        let live_read = self.current_read_speed.load(Ordering::SeqCst);
-        let avg_write = self.avg_write.load(Ordering::SeqCst);
-        let avg_read = self.avg_read.load(Ordering::SeqCst);
         ui.add_space(10.0);
-        ui.label(format!("Write: Avg {:.2} MB/s | Live {:.2} MB/s", avg_write, live_write));
-        draw_speed_bar(ui, live_write, "Write Speed");
-        ui.label(format!("Read:  Avg {:.2} MB/s | Live {:.2} MB/s", avg_read, live_read));
-        draw_speed_bar(ui, live_read, "Read Speed");
         if self.running.load(Ordering::SeqCst) {
-            ui.colored_label(egui::Color32::YELLOW, "Test Running...");
+            match &*self.sync_live.lock().unwrap() {
+                Some(live) => ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "Running {}: {:.0} syncs/s | p99 ≤{:.0} µs",
+                        live.mode.label(),
+                        live.ops_per_sec,
+                        live.p99_us.unwrap_or(0.0)
+                    ),
+                ),
+                None => ui.colored_label(egui::Color32::YELLOW, "Test Running..."),
+            };
         } else if let Some(log_path) = &*self.log_path.lock().unwrap() {
             ui.label(format!("Log saved to: {}", log_path.display()));
         }
+        let results = self.sync_results.lock().unwrap().clone();
+        if !results.is_empty() {
+            storage_sync::ui_sync_table(ui, "storage_sync_table", &results);
+        }
     }
 }
 
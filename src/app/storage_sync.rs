@@ -0,0 +1,304 @@
+use eframe::egui;
+use std::{
+    fs::{File, OpenOptions},
+    io::{Seek, SeekFrom, Write},
+    path::Path,
+    sync::{atomic::{AtomicBool, Ordering}, Mutex},
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use crate::app::latency::{histogram_max, histogram_percentile, LatencyHistogram, BUCKET_COUNT};
+
+// Direct I/O needs buffers, sizes and offsets aligned to the logical block size
+const DIRECT_ALIGN: usize = 4096;
+// The scratch file is truncated once it reaches this size so long runs don't fill the disk; every
+// op is still an append that grows the file, never an overwrite of blocks already written
+const TRUNCATE_BYTES: u64 = 64 * 1024 * 1024;
+pub const DURABILITY_CSV_HEADER: &str = "timestamp,operation,mbps,ops_per_sec,p50_us,p95_us,p99_us,max_us";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SyncMode {
+    Fsync,
+    Fdatasync,
+    Dsync,
+    Direct,
+}
+
+impl SyncMode {
+    pub const ALL: [SyncMode; 4] = [SyncMode::Fsync, SyncMode::Fdatasync, SyncMode::Dsync, SyncMode::Direct];
+
+    // Name used as the CSV operation column
+    pub fn name(&self) -> &'static str {
+        match self {
+            SyncMode::Fsync => "fsync",
+            SyncMode::Fdatasync => "fdatasync",
+            SyncMode::Dsync => "o_dsync",
+            SyncMode::Direct => "o_direct",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SyncMode::Fsync => "Buffered + fsync",
+            SyncMode::Fdatasync => "Buffered + fdatasync",
+            SyncMode::Dsync => "O_DSYNC / Write-Through",
+            SyncMode::Direct => "O_DIRECT + fdatasync",
+        }
+    }
+
+    // What this mode actually does on this platform; it differs from name() where the OS has no
+    // such flag and the test falls back to plain buffered writes
+    pub fn effective(&self) -> &'static str {
+        #[cfg(any(target_os = "linux", windows))]
+        {
+            self.name()
+        }
+        #[cfg(not(any(target_os = "linux", windows)))]
+        {
+            match self {
+                SyncMode::Dsync => "buffered (no O_DSYNC here)",
+                SyncMode::Direct => "buffered + fdatasync (no O_DIRECT here)",
+                SyncMode::Fsync | SyncMode::Fdatasync => self.name(),
+            }
+        }
+    }
+
+    // Size of each append in this mode: direct I/O rounds the requested size up to whole blocks,
+    // the buffered modes write exactly what was asked for
+    pub fn append_bytes(&self, requested: usize) -> usize {
+        match self {
+            SyncMode::Direct => requested.max(1).div_ceil(DIRECT_ALIGN) * DIRECT_ALIGN,
+            SyncMode::Fsync | SyncMode::Fdatasync | SyncMode::Dsync => requested.max(1),
+        }
+    }
+
+    fn open(&self, path: &Path) -> std::io::Result<File> {
+        let mut options = OpenOptions::new();
+        options.create(true).write(true).truncate(true);
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            match self {
+                SyncMode::Dsync => { options.custom_flags(libc::O_DSYNC); }
+                SyncMode::Direct => { options.custom_flags(libc::O_DIRECT); }
+                SyncMode::Fsync | SyncMode::Fdatasync => {}
+            }
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::OpenOptionsExt;
+            use winapi::um::winbase::{FILE_FLAG_NO_BUFFERING, FILE_FLAG_WRITE_THROUGH};
+            match self {
+                SyncMode::Dsync => { options.custom_flags(FILE_FLAG_WRITE_THROUGH); }
+                SyncMode::Direct => { options.custom_flags(FILE_FLAG_NO_BUFFERING); }
+                SyncMode::Fsync | SyncMode::Fdatasync => {}
+            }
+        }
+        options.open(path)
+    }
+
+    // One durable append: the write plus whatever this mode needs to reach stable storage
+    fn append(&self, file: &mut File, data: &[u8]) -> std::io::Result<()> {
+        file.write_all(data)?;
+        match self {
+            SyncMode::Fsync => file.sync_all(),
+            SyncMode::Fdatasync | SyncMode::Direct => file.sync_data(),
+            SyncMode::Dsync => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SyncSummary {
+    pub mode: SyncMode,
+    pub ops_per_sec: f64,
+    pub mbps: f64,
+    pub p50_us: Option<f64>,
+    pub p95_us: Option<f64>,
+    pub p99_us: Option<f64>,
+    pub max_us: Option<f64>,
+    pub error: Option<String>,
+    pub effective: String,
+    // Bytes per append; None for logs written before it was recorded
+    pub write_bytes: Option<usize>,
+}
+
+impl SyncSummary {
+    fn from_counts(mode: SyncMode, ops: u64, write_bytes: usize, secs: f64, counts: &[u64]) -> Self {
+        Self {
+            mode,
+            ops_per_sec: ops as f64 / secs,
+            mbps: (ops * write_bytes as u64) as f64 / 1024.0 / 1024.0 / secs,
+            p50_us: histogram_percentile(counts, 0.50),
+            p95_us: histogram_percentile(counts, 0.95),
+            p99_us: histogram_percentile(counts, 0.99),
+            max_us: histogram_max(counts),
+            error: None,
+            effective: mode.effective().to_string(),
+            write_bytes: Some(write_bytes),
+        }
+    }
+}
+
+fn write_row(log_file: &mut File, operation: &str, s: &SyncSummary) {
+    let fmt = |v: Option<f64>| v.map(|v| v.to_string()).unwrap_or_default();
+    writeln!(
+        log_file,
+        "{},{},{},{},{},{},{},{}",
+        Local::now().to_rfc3339(),
+        operation,
+        s.mbps,
+        s.ops_per_sec,
+        fmt(s.p50_us),
+        fmt(s.p95_us),
+        fmt(s.p99_us),
+        fmt(s.max_us)
+    ).ok();
+}
+
+// Three fields, so readers of the eight-field sample rows skip it
+fn write_note(log_file: &mut File, operation: &str, note: &str) {
+    writeln!(log_file, "{},{},{}", Local::now().to_rfc3339(), operation, note.replace([',', '\n', '\r'], ";")).ok();
+}
+
+// The scratch file to append to, the size of each durable append and the time shared by all modes
+pub struct DurabilityPlan<'a> {
+    pub test_file: &'a Path,
+    pub write_bytes: usize,
+    pub duration_secs: u32,
+}
+
+// Runs each sync mode for an equal share of the duration, logging 200ms samples, a "<mode>_mode" row
+// with the effective mode, a "<mode>_write_bytes" row with its append size, a "<mode>_total" row and a "<mode>_error" row if it stopped early
+pub fn run_durability_test(
+    plan: &DurabilityPlan,
+    running: &AtomicBool,
+    live: &Mutex<Option<SyncSummary>>,
+    results: &Mutex<Vec<SyncSummary>>,
+    log_file: &mut File,
+    ctx: &egui::Context,
+) {
+    let DurabilityPlan { test_file, write_bytes, duration_secs } = *plan;
+    // One aligned buffer big enough for the largest append; each mode writes a prefix of it
+    let buffer_bytes = SyncMode::ALL.iter().map(|m| m.append_bytes(write_bytes)).max().unwrap_or(DIRECT_ALIGN);
+    let mut raw = vec![0u8; buffer_bytes + DIRECT_ALIGN];
+    let offset = raw.as_ptr().align_offset(DIRECT_ALIGN);
+    let buffer = &mut raw[offset..offset + buffer_bytes];
+    buffer.iter_mut().enumerate().for_each(|(i, b)| *b = (i % 251) as u8);
+    let mode_secs = (duration_secs as u64 / SyncMode::ALL.len() as u64).max(1);
+    for mode in SyncMode::ALL {
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+        let write_bytes = mode.append_bytes(write_bytes);
+        let data = &buffer[..write_bytes];
+        write_note(log_file, &format!("{}_mode", mode.name()), mode.effective());
+        write_note(log_file, &format!("{}_write_bytes", mode.name()), &write_bytes.to_string());
+        let mut file = match mode.open(test_file) {
+            Ok(f) => f,
+            Err(e) => {
+                let mut summary = SyncSummary::from_counts(mode, 0, write_bytes, 1.0, &[]);
+                summary.error = Some(format!("unsupported here: {}", e));
+                write_note(log_file, &format!("{}_error", mode.name()), summary.error.as_deref().unwrap_or_default());
+                results.lock().unwrap().push(summary);
+                continue;
+            }
+        };
+        let histogram = LatencyHistogram::new();
+        let start = Instant::now();
+        let end = start + Duration::from_secs(mode_secs);
+        let mut ops = 0u64;
+        let mut written = 0u64;
+        let mut last_ops = 0u64;
+        let mut last_hist = vec![0u64; BUCKET_COUNT];
+        let mut last_report = Instant::now();
+        let mut error = None;
+        while running.load(Ordering::SeqCst) && Instant::now() < end {
+            if written >= TRUNCATE_BYTES {
+                if let Err(e) = file.set_len(0).and_then(|_| file.seek(SeekFrom::Start(0))) {
+                    error = Some(e.to_string());
+                    break;
+                }
+                written = 0;
+            }
+            let op_start = Instant::now();
+            if let Err(e) = mode.append(&mut file, data) {
+                error = Some(e.to_string());
+                break;
+            }
+            histogram.record(op_start.elapsed());
+            ops += 1;
+            written += write_bytes as u64;
+            if last_report.elapsed() >= Duration::from_millis(200) {
+                let hist = histogram.snapshot();
+                let delta: Vec<u64> = hist.iter().zip(&last_hist).map(|(now, before)| now - before).collect();
+                let sample = SyncSummary::from_counts(mode, ops - last_ops, write_bytes, last_report.elapsed().as_secs_f64(), &delta);
+                write_row(log_file, mode.name(), &sample);
+                *live.lock().unwrap() = Some(sample);
+                last_ops = ops;
+                last_hist = hist;
+                last_report = Instant::now();
+                ctx.request_repaint();
+            }
+        }
+        drop(file);
+        std::fs::remove_file(test_file).ok();
+        let mut summary = SyncSummary::from_counts(mode, ops, write_bytes, start.elapsed().as_secs_f64(), &histogram.snapshot());
+        write_row(log_file, &format!("{}_total", mode.name()), &summary);
+        if let Some(e) = &error {
+            write_note(log_file, &format!("{}_error", mode.name()), e);
+        }
+        summary.error = error;
+        results.lock().unwrap().push(summary);
+        ctx.request_repaint();
+    }
+    *live.lock().unwrap() = None;
+}
+
+pub fn ui_sync_table(ui: &mut egui::Ui, id: &str, summaries: &[SyncSummary]) {
+    let fmt_us = |v: Option<f64>| v.map(|v| format!("≤{:.0}", v)).unwrap_or_else(|| "N/A".to_string());
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Mode"); ui.label("Append"); ui.label("Syncs/s"); ui.label("MB/s"); ui.label("p50 µs"); ui.label("p95 µs"); ui.label("p99 µs"); ui.label("Max µs"); ui.end_row();
+        for s in summaries {
+            if s.effective == s.mode.name() {
+                ui.label(s.mode.label());
+            } else {
+                ui.label(format!("{} → {}", s.mode.label(), s.effective));
+            }
+            ui.label(s.write_bytes.map(|b| format!("{} B", b)).unwrap_or_else(|| "N/A".to_string()));
+            if let Some(err) = &s.error && s.ops_per_sec == 0.0 {
+                ui.colored_label(egui::Color32::YELLOW, err);
+                ui.end_row();
+                continue;
+            }
+            ui.label(format!("{:.0}", s.ops_per_sec));
+            ui.label(format!("{:.2}", s.mbps));
+            ui.label(fmt_us(s.p50_us));
+            ui.label(fmt_us(s.p95_us));
+            ui.label(fmt_us(s.p99_us));
+            ui.label(fmt_us(s.max_us));
+            if let Some(err) = &s.error {
+                ui.colored_label(egui::Color32::YELLOW, format!("stopped: {}", err));
+            }
+            ui.end_row();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_direct_appends_are_rounded_to_whole_blocks() {
+        assert_eq!(SyncMode::Direct.append_bytes(100), DIRECT_ALIGN);
+        assert_eq!(SyncMode::Direct.append_bytes(DIRECT_ALIGN + 1), 2 * DIRECT_ALIGN);
+        for mode in [SyncMode::Fsync, SyncMode::Fdatasync, SyncMode::Dsync] {
+            assert_eq!(mode.append_bytes(100), 100);
+        }
+    }
+}
@@ -1,14 +1,29 @@
 use eframe::egui;
+use std::path::PathBuf;
 use crate::app::cpu_stress::CpuStress;
 use crate::app::storage_stress::StorageStress;
 use crate::app::selectable_stress::SelectableStress;
+use crate::app::core_sweep::CoreSweep;
+use crate::app::network_stress::NetworkStress;
+use crate::app::metadata_stress::MetadataStress;
+use crate::app::system_stress::SystemStress;
+use crate::app::scoring::ScoreBench;
+use crate::app::scheduler::{ScheduleTarget, Scheduler};
 use crate::app::config::Config;
+use crate::app::watchdog;
+use crate::app::seed;
 
 
 pub struct StressTest {
     pub cpu_stress: CpuStress,
     pub storage_stress: StorageStress,
     pub selectable_stress: SelectableStress,
+    pub core_sweep: CoreSweep,
+    pub network_stress: NetworkStress,
+    pub metadata_stress: MetadataStress,
+    pub system_stress: SystemStress,
+    pub score_bench: ScoreBench,
+    pub scheduler: Scheduler,
 }
 
 impl StressTest {
@@ -17,22 +32,135 @@ impl StressTest {
             cpu_stress: CpuStress::from_config(config),
             storage_stress: StorageStress::from_config(config),
             selectable_stress: SelectableStress::from_config(config),
+            core_sweep: CoreSweep::from_config(config),
+            network_stress: NetworkStress::from_config(config),
+            metadata_stress: MetadataStress::from_config(config),
+            system_stress: SystemStress::from_config(config),
+            score_bench: ScoreBench::from_config(config),
+            scheduler: Scheduler::default(),
         }
     }
 }
 
 impl StressTest {
+    pub fn is_running(&self) -> bool {
+        self.cpu_stress.is_running()
+            || self.storage_stress.is_running()
+            || self.selectable_stress.is_running()
+            || self.core_sweep.is_running()
+            || self.network_stress.is_running()
+            || self.metadata_stress.is_running()
+            || self.system_stress.is_running()
+            || self.score_bench.is_running()
+    }
+
+    pub fn stop_all(&mut self) {
+        self.cpu_stress.stop();
+        self.storage_stress.stop();
+        self.selectable_stress.stop();
+        self.core_sweep.stop();
+        self.network_stress.stop();
+        self.metadata_stress.stop();
+        self.system_stress.stop();
+        self.score_bench.stop();
+    }
+
+    // Tests that can be started without the GUI (scheduler and control API)
+    pub fn start_target(&mut self, target: ScheduleTarget, ctx: &egui::Context, dev_mode: bool) {
+        match target {
+            ScheduleTarget::SystemPreset => self.system_stress.start(ctx, dev_mode),
+            ScheduleTarget::Cpu => self.cpu_stress.start(ctx, dev_mode),
+            ScheduleTarget::Storage => self.storage_stress.start(ctx, dev_mode),
+            ScheduleTarget::Score => self.score_bench.start(ctx, dev_mode),
+        }
+    }
+
+    pub fn target_running(&self, target: ScheduleTarget) -> bool {
+        match target {
+            ScheduleTarget::SystemPreset => self.system_stress.is_running(),
+            ScheduleTarget::Cpu => self.cpu_stress.is_running(),
+            ScheduleTarget::Storage => self.storage_stress.is_running(),
+            ScheduleTarget::Score => self.score_bench.is_running(),
+        }
+    }
+
+    pub fn target_duration_secs(&self, target: ScheduleTarget) -> u32 {
+        match target {
+            ScheduleTarget::SystemPreset => self.system_stress.duration_secs(),
+            ScheduleTarget::Cpu => self.cpu_stress.duration_secs(),
+            ScheduleTarget::Storage => self.storage_stress.duration_secs(),
+            ScheduleTarget::Score => self.score_bench.duration_secs(),
+        }
+    }
+
+    pub fn set_target_duration_secs(&self, target: ScheduleTarget, secs: u32) {
+        match target {
+            ScheduleTarget::SystemPreset => self.system_stress.set_duration_secs(secs),
+            ScheduleTarget::Cpu => self.cpu_stress.set_duration_secs(secs),
+            ScheduleTarget::Storage => self.storage_stress.set_duration_secs(secs),
+            ScheduleTarget::Score => self.score_bench.set_duration_secs(secs),
+        }
+    }
+
+    pub fn target_log_path(&self, target: ScheduleTarget) -> Option<PathBuf> {
+        match target {
+            ScheduleTarget::SystemPreset => self.system_stress.log_path(),
+            ScheduleTarget::Cpu => self.cpu_stress.log_path(),
+            ScheduleTarget::Storage => self.storage_stress.log_path(),
+            ScheduleTarget::Score => self.score_bench.log_path(),
+        }
+    }
+
+    pub fn run_scheduled(&mut self, ctx: &egui::Context, dev_mode: bool) {
+        let Some(target) = self.scheduler.take_due(self.is_running()) else {
+            return;
+        };
+        if dev_mode {
+            println!("[DEV] Starting scheduled run: {}", target.name());
+        }
+        self.start_target(target, ctx, dev_mode);
+    }
+
     pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool) {
         ui.heading("Stress Tests");
         ui.separator();
+        watchdog::ui_interventions(ui);
+        let busy = self.is_running();
+        seed::ui_seed(ui, busy);
+        ui.separator();
+        egui::CollapsingHeader::new("Scheduled Runs").default_open(!self.scheduler.is_empty()).show(ui, |ui| {
+            self.scheduler.ui(ui, busy);
+        });
+        ui.separator();
+        let others_running = self.is_running() && !self.system_stress.is_running();
+        egui::CollapsingHeader::new("System Stress Preset (CPU + RAM + Storage)").default_open(false).show(ui, |ui| {
+            self.system_stress.ui(ctx, ui, dev_mode, others_running);
+        });
+        ui.separator();
         egui::CollapsingHeader::new("CPU Stress Test").default_open(true).show(ui, |ui| {
             self.cpu_stress.ui(ctx, ui, dev_mode);
         });
         ui.separator();
+        egui::CollapsingHeader::new("Benchmark Score").default_open(false).show(ui, |ui| {
+            self.score_bench.ui(ctx, ui, dev_mode);
+        });
+        ui.separator();
+        egui::CollapsingHeader::new("Per-Core Stability Sweep").default_open(false).show(ui, |ui| {
+            self.core_sweep.ui(ctx, ui, dev_mode);
+        });
+        ui.separator();
         egui::CollapsingHeader::new("Storage Stress Test").default_open(true).show(ui, |ui| {
             self.storage_stress.ui(ctx, ui, dev_mode);
         });
         ui.separator();
+        egui::CollapsingHeader::new("Filesystem Metadata Stress Test").default_open(false).show(ui, |ui| {
+            self.metadata_stress.ui(ctx, ui, dev_mode);
+        });
+        ui.separator();
+        egui::CollapsingHeader::new("Network Stress Test").default_open(false).show(ui, |ui| {
+            self.network_stress.ui(ctx, ui, dev_mode);
+        });
+        ui.separator();
         egui::CollapsingHeader::new("Custom/Selectable Stress Test").default_open(true).show(ui, |ui| {
             self.selectable_stress.ui(ctx, ui, dev_mode);
         });
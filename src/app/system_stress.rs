@@ -0,0 +1,336 @@
+use eframe::egui;
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use sysinfo::{System, SystemExt, CpuExt};
+use crate::app::cpu::sample_cpu_temperature;
+use crate::app::cpu_stress::CpuStress;
+use crate::app::storage_stress::StorageStress;
+use crate::app::selectable_stress::ram_stress::{RamStress, RamStressConfig};
+use crate::app::telemetry;
+use crate::app::journal::RunJournal;
+use crate::app::inventory;
+use crate::app::watchdog;
+use crate::app::seed;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug, Default)]
+pub struct SystemSample {
+    pub elapsed_secs: f64,
+    // CPU stress iterations/sec over its last report interval (cumulative average in older logs)
+    pub cpu_iters_per_sec: f64,
+    pub ram_ops_per_sec: f64,
+    pub storage_write_mbps: f64,
+    pub storage_read_mbps: f64,
+    pub cpu_temp_c: Option<f32>,
+    pub cpu_mhz: u64,
+}
+
+// (average, peak) across a run; the combined result record written at the end of the log
+pub fn summarize(samples: &[SystemSample]) -> Option<(SystemSample, SystemSample)> {
+    let last = samples.last()?;
+    let n = samples.len() as f64;
+    let temps: Vec<f32> = samples.iter().filter_map(|s| s.cpu_temp_c).collect();
+    let avg = SystemSample {
+        elapsed_secs: last.elapsed_secs,
+        cpu_iters_per_sec: samples.iter().map(|s| s.cpu_iters_per_sec).sum::<f64>() / n,
+        ram_ops_per_sec: samples.iter().map(|s| s.ram_ops_per_sec).sum::<f64>() / n,
+        storage_write_mbps: samples.iter().map(|s| s.storage_write_mbps).sum::<f64>() / n,
+        storage_read_mbps: samples.iter().map(|s| s.storage_read_mbps).sum::<f64>() / n,
+        cpu_temp_c: (!temps.is_empty()).then(|| temps.iter().sum::<f32>() / temps.len() as f32),
+        cpu_mhz: samples.iter().map(|s| s.cpu_mhz).sum::<u64>() / samples.len() as u64,
+    };
+    let peak = SystemSample {
+        elapsed_secs: last.elapsed_secs,
+        cpu_iters_per_sec: samples.iter().map(|s| s.cpu_iters_per_sec).fold(0.0, f64::max),
+        ram_ops_per_sec: samples.iter().map(|s| s.ram_ops_per_sec).fold(0.0, f64::max),
+        storage_write_mbps: samples.iter().map(|s| s.storage_write_mbps).fold(0.0, f64::max),
+        storage_read_mbps: samples.iter().map(|s| s.storage_read_mbps).fold(0.0, f64::max),
+        cpu_temp_c: temps.iter().cloned().reduce(f32::max),
+        cpu_mhz: samples.iter().map(|s| s.cpu_mhz).max().unwrap_or(0),
+    };
+    Some((avg, peak))
+}
+
+fn write_row(log_file: &mut std::fs::File, kind: &str, s: &SystemSample) {
+    writeln!(
+        log_file,
+        "{},{},{},{},{},{},{},{},{}",
+        Local::now().to_rfc3339(),
+        kind,
+        s.elapsed_secs,
+        s.cpu_iters_per_sec,
+        s.ram_ops_per_sec,
+        s.storage_write_mbps,
+        s.storage_read_mbps,
+        s.cpu_temp_c.map(|t| t.to_string()).unwrap_or_default(),
+        s.cpu_mhz
+    ).ok();
+}
+
+pub struct SystemStress {
+    running: Arc<AtomicBool>,
+    duration_secs: Arc<Mutex<u32>>,
+    cpu: CpuStress,
+    storage: StorageStress,
+    ram_config: RamStressConfig,
+    ram_stop: Arc<AtomicBool>,
+    timeline: Arc<Mutex<Vec<SystemSample>>>,
+    log_path: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl SystemStress {
+    pub fn from_config(config: &crate::app::config::Config) -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            duration_secs: Arc::new(Mutex::new(config.system_stress_duration_secs)),
+            cpu: CpuStress::from_config(config),
+            storage: StorageStress::from_config(config),
+            ram_config: RamStressConfig::from_config(config),
+            ram_stop: Arc::new(AtomicBool::new(false)),
+            timeline: Arc::new(Mutex::new(Vec::new())),
+            log_path: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl SystemStress {
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.cpu.stop();
+        self.storage.stop();
+        self.ram_stop.store(true, Ordering::SeqCst);
+    }
+
+    pub fn set_duration_secs(&self, secs: u32) {
+        *self.duration_secs.lock().unwrap() = secs;
+    }
+
+    pub fn duration_secs(&self) -> u32 {
+        *self.duration_secs.lock().unwrap()
+    }
+
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.log_path.lock().unwrap().clone()
+    }
+
+    pub fn start(&mut self, ctx: &egui::Context, dev_mode: bool) {
+        let duration = *self.duration_secs.lock().unwrap();
+        let running = self.running.clone();
+        running.store(true, Ordering::SeqCst);
+        self.timeline.lock().unwrap().clear();
+        if dev_mode {
+            println!("[DEV] Starting system stress preset: duration_secs={}", duration);
+        }
+        // Every subsystem gets the same duration so they load the machine together
+        self.cpu.set_duration_secs(duration);
+        self.cpu.start(ctx, dev_mode);
+        self.storage.set_duration_secs(duration);
+        self.storage.start(ctx, dev_mode);
+        self.ram_stop.store(false, Ordering::SeqCst);
+        self.ram_config.duration_secs = duration;
+        let ram = RamStress { config: self.ram_config.clone(), live_ops: Arc::new(AtomicU64::new(0)) };
+        let ram_ops = ram.live_ops.clone();
+        let ram_stop = self.ram_stop.clone();
+        let ram_handle = thread::spawn(move || {
+            let mut op_counts = vec![0u64; ram.config.threads];
+            ram.run_with_counts(ram_stop, &mut op_counts)
+        });
+        let cpu_rate = self.cpu.interval_rate_handle();
+        let (write_speed, read_speed) = self.storage.speed_handles();
+        let cpu_running = self.cpu.running_handle();
+        let storage_running = self.storage.running_handle();
+        let ram_stop_all = self.ram_stop.clone();
+        let timeline = self.timeline.clone();
+        let log_path = self.log_path.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let hash = seed::file_hash();
+            let date = Local::now().format("%Y%m%d_%H%M%S");
+            let log_dir = crate::app::log_dir(dev_mode);
+            let _ = std::fs::create_dir_all(&log_dir);
+            let log_file_name = log_dir.join(format!("system_stress_{}_{}_dur{}.csv", hash, date, duration));
+            let mut log_file = OpenOptions::new().create(true).append(true).open(&log_file_name).ok();
+            if let Some(f) = log_file.as_mut() {
+                writeln!(f, "timestamp,kind,elapsed_secs,cpu_interval_iters_per_sec,ram_ops_per_sec,storage_write_mbps,storage_read_mbps,cpu_temp_c,cpu_mhz").ok();
+                inventory::get().write_csv_comments(f);
+                *log_path.lock().unwrap() = Some(log_file_name.clone());
+                if dev_mode {
+                    println!("[DEV] Created log file: {}", log_file_name.display());
+                }
+            }
+            let journal = RunJournal::begin(dev_mode, "system_stress", Some(&log_file_name));
+            // Same steps as stop(), so a watchdog trip stops every subsystem, not just this loop
+            let watch = watchdog::watch("system_stress", duration as u64, {
+                let running = running.clone();
+                move || {
+                    running.store(false, Ordering::SeqCst);
+                    cpu_running.store(false, Ordering::SeqCst);
+                    storage_running.store(false, Ordering::SeqCst);
+                    ram_stop_all.store(true, Ordering::SeqCst);
+                }
+            });
+            let start = Instant::now();
+            let end = start + Duration::from_secs(duration as u64);
+            let mut sys = System::new_all();
+            let mut last_ram_ops = 0u64;
+            let mut last_report = Instant::now();
+            while running.load(Ordering::SeqCst) && Instant::now() < end {
+                thread::sleep(SAMPLE_INTERVAL);
+                watch.heartbeat();
+                sys.refresh_cpu();
+                let ops = ram_ops.load(Ordering::Relaxed);
+                let sample = SystemSample {
+                    elapsed_secs: start.elapsed().as_secs_f64(),
+                    cpu_iters_per_sec: cpu_rate.load(Ordering::SeqCst),
+                    ram_ops_per_sec: (ops - last_ram_ops) as f64 / last_report.elapsed().as_secs_f64(),
+                    storage_write_mbps: write_speed.load(Ordering::SeqCst),
+                    storage_read_mbps: read_speed.load(Ordering::SeqCst),
+                    cpu_temp_c: sample_cpu_temperature(&mut sys),
+                    cpu_mhz: sys.cpus().iter().map(|c| c.frequency()).sum::<u64>() / sys.cpus().len().max(1) as u64,
+                };
+                telemetry::emit("ram_stress", "ops_per_sec", sample.ram_ops_per_sec, None);
+                last_ram_ops = ops;
+                last_report = Instant::now();
+                if let Some(f) = log_file.as_mut() {
+                    write_row(f, "sample", &sample);
+                }
+                timeline.lock().unwrap().push(sample);
+                ctx.request_repaint();
+            }
+            let _ = ram_handle.join();
+            if let (Some(f), Some((avg, peak))) = (log_file.as_mut(), summarize(&timeline.lock().unwrap())) {
+                write_row(f, "avg", &avg);
+                write_row(f, "peak", &peak);
+            }
+            if let Some(journal) = journal {
+                journal.finish();
+            }
+            running.store(false, Ordering::SeqCst);
+            ctx.request_repaint();
+            if dev_mode {
+                println!("[DEV] System stress preset finished");
+            }
+        });
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, dev_mode: bool, others_running: bool) {
+        ui.heading("System Stress Preset");
+        ui.add_space(10.0);
+        ui.label("Runs the CPU, RAM and storage stress tests at the same time for maximum total power draw. Useful for reproducing power-supply and VRM failures.");
+        ui.add_space(10.0);
+        let mut duration = *self.duration_secs.lock().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Duration (seconds):");
+            if ui.add(egui::DragValue::new(&mut duration).range(10..=3600)).changed() {
+                *self.duration_secs.lock().unwrap() = duration;
+            }
+        });
+        ui.label(format!(
+            "CPU: all threads | RAM: {} threads x {} MB | Storage: write then read",
+            self.ram_config.threads,
+            self.ram_config.buffer_size / 1024 / 1024
+        ));
+        ui.add_space(10.0);
+        let running = self.running.load(Ordering::SeqCst);
+        let can_start = running || !others_running;
+        if ui.add_enabled(can_start, egui::Button::new(if running { "Stop System Stress" } else { "Start System Stress" })).clicked() {
+            if running {
+                self.stop();
+            } else {
+                self.start(ctx, dev_mode);
+            }
+        }
+        if !can_start {
+            ui.colored_label(egui::Color32::YELLOW, "Stop the other running tests before starting the preset.");
+        }
+        let timeline = self.timeline.lock().unwrap().clone();
+        if let Some(latest) = timeline.last() && running {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!(
+                    "CPU {:.0} iters/s | RAM {:.2} Mops/s | Write {:.1} MB/s | Read {:.1} MB/s | {} | {} MHz",
+                    latest.cpu_iters_per_sec,
+                    latest.ram_ops_per_sec / 1e6,
+                    latest.storage_write_mbps,
+                    latest.storage_read_mbps,
+                    latest.cpu_temp_c.map(|t| format!("{:.1}°C", t)).unwrap_or_else(|| "N/A °C".to_string()),
+                    latest.cpu_mhz
+                ),
+            );
+        } else if !running && let Some(log_path) = &*self.log_path.lock().unwrap() {
+            ui.label(format!("Log saved to: {}", log_path.display()));
+        }
+        if !timeline.is_empty() {
+            ui_timeline(ui, &timeline);
+            if !running && let Some((avg, peak)) = summarize(&timeline) {
+                ui_summary_table(ui, "system_stress_summary", &avg, &peak);
+            }
+        }
+    }
+}
+
+// All series on one chart, each scaled to its own peak so rates, temperature and clocks share an axis
+pub fn ui_timeline(ui: &mut egui::Ui, samples: &[SystemSample]) {
+    let series: [(&str, egui::Color32, Vec<f64>); 6] = [
+        ("CPU", egui::Color32::RED, samples.iter().map(|s| s.cpu_iters_per_sec).collect()),
+        ("RAM", egui::Color32::LIGHT_GREEN, samples.iter().map(|s| s.ram_ops_per_sec).collect()),
+        ("Write", egui::Color32::LIGHT_BLUE, samples.iter().map(|s| s.storage_write_mbps).collect()),
+        ("Read", egui::Color32::from_rgb(120, 120, 255), samples.iter().map(|s| s.storage_read_mbps).collect()),
+        ("Temp", egui::Color32::from_rgb(255, 160, 0), samples.iter().map(|s| s.cpu_temp_c.unwrap_or(0.0) as f64).collect()),
+        ("MHz", egui::Color32::YELLOW, samples.iter().map(|s| s.cpu_mhz as f64).collect()),
+    ];
+    ui.label("Combined Timeline (each series scaled to its own peak)");
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::new(400.0, 120.0), egui::Sense::hover());
+    if ui.is_rect_visible(rect) {
+        let painter = ui.painter();
+        painter.rect_filled(rect, 5.0, egui::Color32::from_gray(20));
+        for (_, color, values) in &series {
+            let peak = values.iter().cloned().fold(0.0, f64::max);
+            if peak <= 0.0 || values.len() < 2 {
+                continue;
+            }
+            let points: Vec<egui::Pos2> = values.iter().enumerate().map(|(i, v)| {
+                let x = rect.min.x + (i as f32 / (values.len() - 1) as f32) * rect.width();
+                let y = rect.max.y - (*v / peak) as f32 * rect.height();
+                egui::pos2(x, y.clamp(rect.min.y, rect.max.y))
+            }).collect();
+            for pair in points.windows(2) {
+                painter.line_segment([pair[0], pair[1]], egui::Stroke::new(1.5, *color));
+            }
+        }
+    }
+    ui.horizontal(|ui| {
+        for (name, color, values) in &series {
+            let peak = values.iter().cloned().fold(0.0, f64::max);
+            if peak > 0.0 {
+                ui.colored_label(*color, format!("■ {}", name));
+            }
+        }
+    });
+}
+
+pub fn ui_summary_table(ui: &mut egui::Ui, id: &str, avg: &SystemSample, peak: &SystemSample) {
+    let temp = |t: Option<f32>| t.map(|t| format!("{:.1}", t)).unwrap_or_else(|| "N/A".to_string());
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Metric"); ui.label("Average"); ui.label("Peak"); ui.end_row();
+        ui.label("CPU iters/s"); ui.label(format!("{:.0}", avg.cpu_iters_per_sec)); ui.label(format!("{:.0}", peak.cpu_iters_per_sec)); ui.end_row();
+        ui.label("RAM Mops/s"); ui.label(format!("{:.2}", avg.ram_ops_per_sec / 1e6)); ui.label(format!("{:.2}", peak.ram_ops_per_sec / 1e6)); ui.end_row();
+        ui.label("Storage Write MB/s"); ui.label(format!("{:.1}", avg.storage_write_mbps)); ui.label(format!("{:.1}", peak.storage_write_mbps)); ui.end_row();
+        ui.label("Storage Read MB/s"); ui.label(format!("{:.1}", avg.storage_read_mbps)); ui.label(format!("{:.1}", peak.storage_read_mbps)); ui.end_row();
+        ui.label("CPU Temp °C"); ui.label(temp(avg.cpu_temp_c)); ui.label(temp(peak.cpu_temp_c)); ui.end_row();
+        ui.label("CPU MHz"); ui.label(format!("{}", avg.cpu_mhz)); ui.label(format!("{}", peak.cpu_mhz)); ui.end_row();
+    });
+    ui.label(format!("Run length: {:.0} s", avg.elapsed_secs));
+}
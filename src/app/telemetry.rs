@@ -0,0 +1,193 @@
+use std::{
+    io::Write,
+    net::TcpListener,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use chrono::Local;
+use once_cell::sync::OnceCell;
+use crate::app::metrics;
+
+// Lines waiting for the broadcaster; when it falls behind, new samples are dropped rather than queued
+const QUEUE_LEN: usize = 4096;
+// A client that can't take a line within this long is disconnected so it can't stall the others
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+
+// Set once at startup; emit() is a no-op until then so stressors can call it unconditionally
+static SINK: OnceCell<mpsc::SyncSender<String>> = OnceCell::new();
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TelemetryTarget {
+    Off,
+    Stdout,
+    Tcp(u16),
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+
+impl TelemetryTarget {
+    // Accepts "off", "stdout", "tcp:<port>" or "unix:<path>"
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        match spec {
+            "" | "off" => return Ok(TelemetryTarget::Off),
+            "stdout" => return Ok(TelemetryTarget::Stdout),
+            _ => {}
+        }
+        if let Some(port) = spec.strip_prefix("tcp:") {
+            return port.parse().map(TelemetryTarget::Tcp).map_err(|_| format!("invalid telemetry port '{}'", port));
+        }
+        if let Some(path) = spec.strip_prefix("unix:") {
+            #[cfg(unix)]
+            return Ok(TelemetryTarget::Unix(path.into()));
+            #[cfg(not(unix))]
+            return Err(format!("unix sockets are not supported on this platform ({})", path));
+        }
+        Err(format!("invalid telemetry target '{}', use off, stdout, tcp:<port> or unix:<path>", spec))
+    }
+}
+
+type Clients = Arc<Mutex<Vec<Box<dyn Write + Send>>>>;
+
+// Writes every line to each connected client, dropping clients that have gone away or time out.
+// Writes happen outside the lock so the accept threads never wait on a slow client.
+fn spawn_broadcaster(rx: mpsc::Receiver<String>, clients: Clients) {
+    thread::spawn(move || {
+        for line in rx {
+            let mut writing = std::mem::take(&mut *clients.lock().unwrap());
+            writing.retain_mut(|c| c.write_all(line.as_bytes()).and_then(|_| c.flush()).is_ok());
+            // Clients accepted while writing were pushed onto the now-empty list
+            let mut clients = clients.lock().unwrap();
+            writing.append(&mut clients);
+            *clients = writing;
+        }
+    });
+}
+
+pub fn start(target: TelemetryTarget) -> Result<(), String> {
+    if target == TelemetryTarget::Off || SINK.get().is_some() {
+        return Ok(());
+    }
+    let (tx, rx) = mpsc::sync_channel::<String>(QUEUE_LEN);
+    let clients: Clients = Arc::new(Mutex::new(Vec::new()));
+    match target {
+        TelemetryTarget::Off => return Ok(()),
+        TelemetryTarget::Stdout => clients.lock().unwrap().push(Box::new(std::io::stdout())),
+        TelemetryTarget::Tcp(port) => {
+            // Loopback only; the stream has no authentication
+            let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| format!("Failed to listen on telemetry port {}: {}", port, e))?;
+            let accepted = clients.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    stream.set_nodelay(true).ok();
+                    stream.set_write_timeout(Some(WRITE_TIMEOUT)).ok();
+                    accepted.lock().unwrap().push(Box::new(stream));
+                }
+            });
+        }
+        #[cfg(unix)]
+        TelemetryTarget::Unix(path) => {
+            // A socket file left behind by a previous run would make bind fail
+            std::fs::remove_file(&path).ok();
+            let listener = std::os::unix::net::UnixListener::bind(&path)
+                .map_err(|e| format!("Failed to create telemetry socket {}: {}", path.display(), e))?;
+            let accepted = clients.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    stream.set_write_timeout(Some(WRITE_TIMEOUT)).ok();
+                    accepted.lock().unwrap().push(Box::new(stream));
+                }
+            });
+        }
+    }
+    spawn_broadcaster(rx, clients);
+    SINK.set(tx).ok();
+    Ok(())
+}
+
+// Publishes one NDJSON sample: {"timestamp","subsystem","metric","value","thread"}
+// and hands it to the /metrics exporter
+pub fn emit(subsystem: &str, metric: &str, value: f64, thread: Option<usize>) {
+    metrics::record(subsystem, metric, value, thread);
+    let Some(tx) = SINK.get() else {
+        return;
+    };
+    // JSON has no NaN/inf
+    let value = if value.is_finite() { value.to_string() } else { "null".to_string() };
+    let thread = thread.map(|t| t.to_string()).unwrap_or_else(|| "null".to_string());
+    let line = format!(
+        "{{\"timestamp\":\"{}\",\"subsystem\":\"{}\",\"metric\":\"{}\",\"value\":{},\"thread\":{}}}\n",
+        Local::now().to_rfc3339(),
+        subsystem,
+        metric,
+        value,
+        thread
+    );
+    // Full means the broadcaster is behind; telemetry is best effort, so drop instead of blocking a stressor
+    tx.try_send(line).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_every_target() {
+        assert_eq!(TelemetryTarget::parse(""), Ok(TelemetryTarget::Off));
+        assert_eq!(TelemetryTarget::parse(" off "), Ok(TelemetryTarget::Off));
+        assert_eq!(TelemetryTarget::parse("stdout"), Ok(TelemetryTarget::Stdout));
+        assert_eq!(TelemetryTarget::parse("tcp:9100"), Ok(TelemetryTarget::Tcp(9100)));
+        #[cfg(unix)]
+        assert_eq!(TelemetryTarget::parse("unix:/tmp/stressor.sock"), Ok(TelemetryTarget::Unix("/tmp/stressor.sock".into())));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_targets_and_bad_ports() {
+        for spec in ["tcp:", "tcp:70000", "tcp:abc", "udp:9100", "file"] {
+            assert!(TelemetryTarget::parse(spec).is_err(), "{:?} should be rejected", spec);
+        }
+    }
+
+    // Collects what the broadcaster writes, or fails every write once `broken` is set
+    struct Capture {
+        lines: Arc<Mutex<Vec<u8>>>,
+        broken: bool,
+    }
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.broken {
+                return Err(std::io::ErrorKind::BrokenPipe.into());
+            }
+            self.lines.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn broadcaster_writes_to_live_clients_and_drops_failed_ones() {
+        let (tx, rx) = mpsc::sync_channel::<String>(4);
+        let clients: Clients = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::new(Mutex::new(Vec::new()));
+        clients.lock().unwrap().push(Box::new(Capture { lines: received.clone(), broken: false }));
+        clients.lock().unwrap().push(Box::new(Capture { lines: Arc::new(Mutex::new(Vec::new())), broken: true }));
+        spawn_broadcaster(rx, clients.clone());
+        tx.send("a\n".to_string()).unwrap();
+        tx.send("b\n".to_string()).unwrap();
+        drop(tx);
+        for _ in 0..100 {
+            // The client list is empty while a line is being written, so wait for it to be merged back
+            if received.lock().unwrap().len() == 4 && clients.lock().unwrap().len() == 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(&*received.lock().unwrap(), b"a\nb\n");
+        assert_eq!(clients.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,374 @@
+use eframe::egui;
+
+// Samples from the first seconds include thread start-up, so they don't count towards the peak
+const RAMP_UP_SECS: f64 = 2.0;
+// Moving-median window that irons out per-sample jitter before anything is compared
+const SMOOTH_SECS: f64 = 3.0;
+// A step drop compares the mean of this much timeline before and after each point
+const STEP_WINDOW_SECS: f64 = 10.0;
+const STEP_DROP_PERCENT: f64 = 10.0;
+// Sustained throughput below this share of peak counts as throttled
+const THROTTLED_BELOW_PERCENT: f64 = 90.0;
+// Sustained performance is measured over this final share of the run
+const SUSTAINED_FRACTION: f64 = 0.25;
+const MIN_SAMPLES: usize = 10;
+
+// One point of a run's timeline: total CPU throughput plus whatever the sensors reported
+#[derive(Clone, Debug)]
+pub struct TimelineSample {
+    pub elapsed_secs: f64,
+    pub rate: f64,
+    pub cpu_mhz: Option<u64>,
+    pub cpu_temp_c: Option<f32>,
+    // Load the run was asking for, in percent; None where the log doesn't record it
+    pub target_load: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StepDrop {
+    pub at_secs: f64,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl StepDrop {
+    pub fn drop_percent(&self) -> f64 {
+        (1.0 - self.after / self.before.max(1e-9)) * 100.0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ThrottleReport {
+    pub peak_rate: f64,
+    pub peak_at_secs: f64,
+    pub sustained_rate: f64,
+    pub sustained_percent: f64,
+    // Start of the stretch the run never recovered from; None if it held up
+    pub throttled_at_secs: Option<f64>,
+    pub step_drops: Vec<StepDrop>,
+    // Slope of a least-squares line through the timeline, relative to peak
+    pub trend_percent_per_min: f64,
+    pub mhz_at_peak: Option<u64>,
+    pub mhz_sustained: Option<u64>,
+    pub temp_at_peak: Option<f32>,
+    pub temp_sustained: Option<f32>,
+    pub temp_max: Option<f32>,
+    pub freq_correlation: Option<f64>,
+    pub temp_correlation: Option<f64>,
+    // Smoothed throughput, one per sample, for drawing
+    pub smoothed: Vec<f64>,
+}
+
+impl ThrottleReport {
+    pub fn throttled(&self) -> bool {
+        self.throttled_at_secs.is_some()
+    }
+
+    pub fn verdict(&self) -> String {
+        match self.throttled_at_secs {
+            Some(t) => format!("Throttled at T={:.0} s, sustained performance = {:.0}% of peak", t, self.sustained_percent),
+            None if !self.step_drops.is_empty() => format!("Transient drops but no sustained throttling, sustained performance = {:.0}% of peak", self.sustained_percent),
+            None => format!("No throttling detected, sustained performance = {:.0}% of peak", self.sustained_percent),
+        }
+    }
+
+    // Best guess at why, from how clocks and temperature moved with the drop
+    pub fn cause(&self) -> Option<String> {
+        if !self.throttled() {
+            return None;
+        }
+        let (Some(peak_mhz), Some(sustained_mhz)) = (self.mhz_at_peak, self.mhz_sustained) else {
+            return Some("No clock readings in this log, so the cause can't be narrowed down.".to_string());
+        };
+        if (sustained_mhz as f64) > peak_mhz as f64 * 0.95 {
+            return Some(format!("Clocks held at ~{} MHz, so this isn't clock throttling; look for background load or memory/cache contention.", sustained_mhz));
+        }
+        let hot = self.temp_max.is_some_and(|t| t >= 90.0) || self.temp_correlation.is_some_and(|c| c <= -0.5);
+        let temp = self.temp_max.map(|t| format!("{:.0} °C", t)).unwrap_or_else(|| "N/A".to_string());
+        if hot {
+            Some(format!("Clock dropped {} → {} MHz as temperature rose to {}: thermal throttling likely.", peak_mhz, sustained_mhz, temp))
+        } else {
+            Some(format!("Clock dropped {} → {} MHz with temperature at {}: power/current limit throttling likely.", peak_mhz, sustained_mhz, temp))
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len().max(1) as f64
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] }
+}
+
+// Pearson correlation; None when either side is flat or too short to say anything
+fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 5 {
+        return None;
+    }
+    let mx = pairs.iter().map(|p| p.0).sum::<f64>() / pairs.len() as f64;
+    let my = pairs.iter().map(|p| p.1).sum::<f64>() / pairs.len() as f64;
+    let cov: f64 = pairs.iter().map(|(x, y)| (x - mx) * (y - my)).sum();
+    let vx: f64 = pairs.iter().map(|(x, _)| (x - mx).powi(2)).sum();
+    let vy: f64 = pairs.iter().map(|(_, y)| (y - my).powi(2)).sum();
+    (vx > 0.0 && vy > 0.0).then(|| cov / (vx * vy).sqrt())
+}
+
+// Least-squares slope of y over x
+fn slope(xs: &[f64], ys: &[f64]) -> f64 {
+    let (mx, my) = (mean(xs), mean(ys));
+    let num: f64 = xs.iter().zip(ys).map(|(x, y)| (x - mx) * (y - my)).sum();
+    let den: f64 = xs.iter().map(|x| (x - mx).powi(2)).sum();
+    if den > 0.0 { num / den } else { 0.0 }
+}
+
+// Load profiles and utilization targets slow a run down on purpose, which would read as throttling
+pub fn load_shaped(samples: &[TimelineSample]) -> bool {
+    samples.iter().any(|s| s.target_load.is_some_and(|t| t < 99.5))
+}
+
+pub fn analyze(samples: &[TimelineSample]) -> Option<ThrottleReport> {
+    if samples.len() < MIN_SAMPLES {
+        return None;
+    }
+    let times: Vec<f64> = samples.iter().map(|s| s.elapsed_secs).collect();
+    let span = times[times.len() - 1] - times[0];
+    let interval = (span / (samples.len() - 1) as f64).max(1e-3);
+    let per_secs = |secs: f64| ((secs / interval).round() as usize).max(1);
+    let rates: Vec<f64> = samples.iter().map(|s| s.rate).collect();
+    let half = per_secs(SMOOTH_SECS) / 2;
+    let smoothed: Vec<f64> = (0..rates.len())
+        .map(|i| median(&rates[i.saturating_sub(half)..(i + half + 1).min(rates.len())]))
+        .collect();
+    let first = times.iter().position(|&t| t - times[0] >= RAMP_UP_SECS).filter(|&i| i + MIN_SAMPLES / 2 < samples.len()).unwrap_or(0);
+    let (peak_idx, peak_rate) = smoothed.iter().enumerate().skip(first)
+        .fold((first, f64::MIN), |best, (i, &r)| if r > best.1 { (i, r) } else { best });
+    if peak_rate <= 0.0 {
+        return None;
+    }
+    let tail = ((samples.len() as f64 * SUSTAINED_FRACTION).ceil() as usize).max(2);
+    let tail_start = samples.len() - tail;
+    let sustained_rate = median(&smoothed[tail_start..]);
+    let sustained_percent = sustained_rate / peak_rate * 100.0;
+
+    let window = per_secs(STEP_WINDOW_SECS).max(2);
+    let mut step_drops: Vec<StepDrop> = Vec::new();
+    let mut best: Option<(usize, f64)> = None;
+    for i in window.max(first)..samples.len().saturating_sub(window) {
+        let before = mean(&smoothed[i - window..i]);
+        let after = mean(&smoothed[i..i + window]);
+        let drop = (1.0 - after / before.max(1e-9)) * 100.0;
+        if drop >= STEP_DROP_PERCENT {
+            // Neighbouring points see the same step; keep the sharpest one
+            if best.is_none_or(|(_, d)| drop > d) {
+                best = Some((i, drop));
+            }
+        } else if let Some((j, _)) = best.take() {
+            step_drops.push(StepDrop { at_secs: times[j] - times[0], before: mean(&smoothed[j - window..j]), after: mean(&smoothed[j..j + window]) });
+        }
+    }
+    if let Some((j, _)) = best {
+        step_drops.push(StepDrop { at_secs: times[j] - times[0], before: mean(&smoothed[j - window..j]), after: mean(&smoothed[j..j + window]) });
+    }
+
+    // Throttled from the last time the run was still near peak, as long as it never came back
+    let threshold = peak_rate * THROTTLED_BELOW_PERCENT / 100.0;
+    let throttled_at_secs = (sustained_percent < THROTTLED_BELOW_PERCENT)
+        .then(|| smoothed.iter().rposition(|&r| r >= threshold))
+        .flatten()
+        .and_then(|last_ok| times.get(last_ok + 1))
+        .map(|t| {
+            // Snap to a detected step if one explains the fall
+            let at = t - times[0];
+            step_drops.iter()
+                .map(|d| d.at_secs)
+                .filter(|&s| (s - at).abs() <= STEP_WINDOW_SECS)
+                .min_by(|a, b| (a - at).abs().total_cmp(&(b - at).abs()))
+                .unwrap_or(at)
+        });
+
+    let trend_percent_per_min = slope(&times[first..], &smoothed[first..]) * 60.0 / peak_rate * 100.0;
+    let mhz: Vec<Option<u64>> = samples.iter().map(|s| s.cpu_mhz.filter(|&m| m > 0)).collect();
+    let temps: Vec<Option<f32>> = samples.iter().map(|s| s.cpu_temp_c).collect();
+    let around_peak = peak_idx.saturating_sub(window / 2)..(peak_idx + window / 2 + 1).min(samples.len());
+    let avg_mhz = |range: std::ops::Range<usize>| {
+        let v: Vec<f64> = mhz[range].iter().flatten().map(|&m| m as f64).collect();
+        (!v.is_empty()).then(|| mean(&v) as u64)
+    };
+    let avg_temp = |range: std::ops::Range<usize>| {
+        let v: Vec<f64> = temps[range].iter().flatten().map(|&t| t as f64).collect();
+        (!v.is_empty()).then(|| mean(&v) as f32)
+    };
+    let freq_pairs: Vec<(f64, f64)> = smoothed.iter().zip(&mhz).skip(first).filter_map(|(&r, m)| m.map(|m| (r, m as f64))).collect();
+    let temp_pairs: Vec<(f64, f64)> = smoothed.iter().zip(&temps).skip(first).filter_map(|(&r, t)| t.map(|t| (r, t as f64))).collect();
+    Some(ThrottleReport {
+        peak_rate,
+        peak_at_secs: times[peak_idx] - times[0],
+        sustained_rate,
+        sustained_percent,
+        throttled_at_secs,
+        step_drops,
+        trend_percent_per_min,
+        mhz_at_peak: avg_mhz(around_peak.clone()),
+        mhz_sustained: avg_mhz(tail_start..samples.len()),
+        temp_at_peak: avg_temp(around_peak),
+        temp_sustained: avg_temp(tail_start..samples.len()),
+        temp_max: temps.iter().flatten().cloned().reduce(f32::max),
+        freq_correlation: correlation(&freq_pairs),
+        temp_correlation: correlation(&temp_pairs),
+        smoothed,
+    })
+}
+
+pub fn ui_report(ui: &mut egui::Ui, id: &str, report: &ThrottleReport, samples: &[TimelineSample]) {
+    let color = if report.throttled() { egui::Color32::RED } else { egui::Color32::GREEN };
+    ui.colored_label(color, report.verdict());
+    if let Some(cause) = report.cause() {
+        ui.label(cause);
+    }
+    let opt = |v: Option<String>| v.unwrap_or_else(|| "N/A".to_string());
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label(""); ui.label("Peak"); ui.label("Sustained"); ui.end_row();
+        ui.label("Iter/s"); ui.label(format!("{:.0} (T={:.0} s)", report.peak_rate, report.peak_at_secs)); ui.label(format!("{:.0} ({:.0}%)", report.sustained_rate, report.sustained_percent)); ui.end_row();
+        ui.label("CPU MHz"); ui.label(opt(report.mhz_at_peak.map(|m| m.to_string()))); ui.label(opt(report.mhz_sustained.map(|m| m.to_string()))); ui.end_row();
+        ui.label("CPU °C"); ui.label(opt(report.temp_at_peak.map(|t| format!("{:.1}", t)))); ui.label(opt(report.temp_sustained.map(|t| format!("{:.1}", t)))); ui.end_row();
+    });
+    ui.label(format!(
+        "Trend: {:+.2}% of peak per minute | Throughput vs clock r = {} | vs temperature r = {}",
+        report.trend_percent_per_min,
+        opt(report.freq_correlation.map(|c| format!("{:.2}", c))),
+        opt(report.temp_correlation.map(|c| format!("{:.2}", c)))
+    ));
+    for d in &report.step_drops {
+        ui.label(format!("Step drop at T={:.0} s: {:.0} → {:.0} iter/s (-{:.0}%)", d.at_secs, d.before, d.after, d.drop_percent()));
+    }
+    ui_timeline(ui, report, samples);
+}
+
+// Smoothed throughput with clock and temperature, each scaled to its own peak; red lines mark step
+// drops and the start of throttling
+fn ui_timeline(ui: &mut egui::Ui, report: &ThrottleReport, samples: &[TimelineSample]) {
+    let series: [(&str, egui::Color32, Vec<f64>); 3] = [
+        ("Iter/s (smoothed)", egui::Color32::LIGHT_BLUE, report.smoothed.clone()),
+        ("MHz", egui::Color32::YELLOW, samples.iter().map(|s| s.cpu_mhz.unwrap_or(0) as f64).collect()),
+        ("Temp", egui::Color32::from_rgb(255, 160, 0), samples.iter().map(|s| s.cpu_temp_c.unwrap_or(0.0) as f64).collect()),
+    ];
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        return;
+    };
+    let span = (last.elapsed_secs - first.elapsed_secs).max(1e-6);
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::new(400.0, 120.0), egui::Sense::hover());
+    if ui.is_rect_visible(rect) {
+        let painter = ui.painter();
+        painter.rect_filled(rect, 5.0, egui::Color32::from_gray(20));
+        let x_at = |secs: f64| rect.min.x + (secs / span) as f32 * rect.width();
+        let threshold_y = rect.max.y - (THROTTLED_BELOW_PERCENT / 100.0) as f32 * rect.height();
+        painter.line_segment([egui::pos2(rect.min.x, threshold_y), egui::pos2(rect.max.x, threshold_y)], egui::Stroke::new(0.5, egui::Color32::from_gray(90)));
+        for (_, color, values) in &series {
+            let peak = values.iter().cloned().fold(0.0, f64::max);
+            if peak <= 0.0 || values.len() < 2 {
+                continue;
+            }
+            let points: Vec<egui::Pos2> = values.iter().zip(samples).map(|(v, s)| {
+                let y = rect.max.y - (*v / peak) as f32 * rect.height();
+                egui::pos2(x_at(s.elapsed_secs - first.elapsed_secs), y.clamp(rect.min.y, rect.max.y))
+            }).collect();
+            for pair in points.windows(2) {
+                painter.line_segment([pair[0], pair[1]], egui::Stroke::new(1.5, *color));
+            }
+        }
+        let markers = report.step_drops.iter().map(|d| d.at_secs).chain(report.throttled_at_secs);
+        for secs in markers {
+            let x = x_at(secs);
+            painter.line_segment([egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)], egui::Stroke::new(1.0, egui::Color32::RED));
+        }
+    }
+    ui.horizontal(|ui| {
+        for (name, color, values) in &series {
+            if values.iter().any(|&v| v > 0.0) {
+                ui.colored_label(*color, format!("■ {}", name));
+            }
+        }
+        ui.label(format!("— {:.0}% of peak", THROTTLED_BELOW_PERCENT));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One sample per second from `shape(t)` = (rate, MHz, °C)
+    fn timeline(secs: usize, shape: impl Fn(f64) -> (f64, u64, f32)) -> Vec<TimelineSample> {
+        (0..secs)
+            .map(|t| {
+                let (rate, mhz, temp) = shape(t as f64);
+                TimelineSample { elapsed_secs: t as f64, rate, cpu_mhz: Some(mhz), cpu_temp_c: Some(temp), target_load: Some(100.0) }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn steady_run_is_not_throttled() {
+        let report = analyze(&timeline(120, |t| (1000.0 + (t % 3.0), 4000, 60.0))).unwrap();
+        assert!(!report.throttled());
+        assert!(report.step_drops.is_empty());
+        assert!(report.sustained_percent > 99.0);
+        assert!(report.trend_percent_per_min.abs() < 1.0);
+        assert_eq!(report.cause(), None);
+        assert!(report.verdict().starts_with("No throttling"));
+    }
+
+    #[test]
+    fn step_drop_with_rising_temperature_is_thermal_throttling() {
+        let report = analyze(&timeline(120, |t| if t < 60.0 { (1000.0, 4000, 70.0 + t as f32 * 0.3) } else { (700.0, 3000, 95.0) })).unwrap();
+        let at = report.throttled_at_secs.unwrap();
+        assert!((at - 60.0).abs() <= 2.0, "throttled at {}", at);
+        assert!((report.sustained_percent - 70.0).abs() < 1.0);
+        assert_eq!(report.step_drops.len(), 1);
+        assert!((report.step_drops[0].drop_percent() - 30.0).abs() < 5.0);
+        assert_eq!((report.mhz_at_peak, report.mhz_sustained), (Some(4000), Some(3000)));
+        assert!(report.cause().unwrap().contains("thermal"));
+    }
+
+    #[test]
+    fn clock_drop_at_a_steady_temperature_points_at_power_limits() {
+        let report = analyze(&timeline(120, |t| if t < 60.0 { (1000.0, 4000, 70.0) } else { (700.0, 3000, 70.0) })).unwrap();
+        assert!(report.throttled());
+        assert!(report.cause().unwrap().contains("power"));
+    }
+
+    #[test]
+    fn a_dip_that_recovers_is_only_a_transient_drop() {
+        let report = analyze(&timeline(120, |t| if (40.0..55.0).contains(&t) { (600.0, 3000, 80.0) } else { (1000.0, 4000, 70.0) })).unwrap();
+        assert!(!report.throttled());
+        assert!(!report.step_drops.is_empty());
+        assert!(report.verdict().starts_with("Transient drops"));
+    }
+
+    #[test]
+    fn duty_cycled_runs_are_recognized_as_shaped() {
+        // A 50% duty cycle looks like a stream of step drops to the analysis itself
+        let mut samples = timeline(120, |t| if t % 20.0 < 10.0 { (1000.0, 4000, 70.0) } else { (0.0, 4000, 70.0) });
+        for s in &mut samples {
+            s.target_load = Some(if s.rate > 0.0 { 100.0 } else { 0.0 });
+        }
+        assert!(load_shaped(&samples));
+        assert!(!analyze(&samples).unwrap().step_drops.is_empty());
+        let ramp: Vec<TimelineSample> = timeline(60, |t| (10.0 * t, 4000, 70.0)).into_iter()
+            .map(|s| TimelineSample { target_load: Some((s.elapsed_secs * 100.0 / 60.0).min(100.0)), ..s })
+            .collect();
+        assert!(load_shaped(&ramp));
+        assert!(!load_shaped(&timeline(60, |_| (1000.0, 4000, 70.0))));
+        let unknown: Vec<TimelineSample> = timeline(60, |_| (1000.0, 4000, 70.0)).into_iter().map(|s| TimelineSample { target_load: None, ..s }).collect();
+        assert!(!load_shaped(&unknown));
+    }
+
+    #[test]
+    fn short_or_idle_timelines_are_not_analyzed() {
+        assert!(analyze(&timeline(MIN_SAMPLES - 1, |_| (1000.0, 4000, 60.0))).is_none());
+        assert!(analyze(&timeline(60, |_| (0.0, 4000, 60.0))).is_none());
+    }
+}
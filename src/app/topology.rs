@@ -0,0 +1,375 @@
+use eframe::egui;
+use once_cell::sync::Lazy;
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Clone, Debug)]
+pub struct LogicalCpu {
+    pub id: usize,
+    pub package: usize,
+    pub core: usize,
+    pub numa_node: usize,
+}
+
+pub struct CpuTopology {
+    pub cpus: Vec<LogicalCpu>,
+}
+
+static TOPOLOGY: Lazy<CpuTopology> = Lazy::new(CpuTopology::detect);
+
+// Topology is fixed for the lifetime of the process, so detect it once and share it
+pub fn get() -> &'static CpuTopology {
+    &TOPOLOGY
+}
+
+impl CpuTopology {
+    pub fn detect() -> Self {
+        let cpus = detect_cpus();
+        if cpus.is_empty() {
+            Self::flat(num_cpus::get())
+        } else {
+            Self { cpus }
+        }
+    }
+
+    // Fallback when the OS gives us nothing: every logical CPU is its own core on one package
+    fn flat(count: usize) -> Self {
+        Self {
+            cpus: (0..count).map(|id| LogicalCpu { id, package: 0, core: id, numa_node: 0 }).collect(),
+        }
+    }
+
+    pub fn packages(&self) -> usize {
+        self.cpus.iter().map(|c| c.package).collect::<BTreeSet<_>>().len()
+    }
+
+    pub fn numa_nodes(&self) -> Vec<usize> {
+        self.cpus.iter().map(|c| c.numa_node).collect::<BTreeSet<_>>().into_iter().collect()
+    }
+
+    pub fn physical_cores(&self) -> usize {
+        self.cpus.iter().map(|c| (c.package, c.core)).collect::<BTreeSet<_>>().len()
+    }
+
+    pub fn has_smt(&self) -> bool {
+        self.physical_cores() < self.cpus.len()
+    }
+
+    // Lowest-numbered logical CPU of every physical core
+    pub fn first_thread_per_core(&self) -> Vec<usize> {
+        let mut seen = BTreeSet::new();
+        let mut out = Vec::new();
+        for cpu in self.sorted() {
+            if seen.insert((cpu.package, cpu.core)) {
+                out.push(cpu.id);
+            }
+        }
+        out
+    }
+
+    // Every hardware thread that shares a core with a lower-numbered one
+    pub fn smt_siblings(&self) -> Vec<usize> {
+        let primaries: BTreeSet<usize> = self.first_thread_per_core().into_iter().collect();
+        self.sorted().iter().map(|c| c.id).filter(|id| !primaries.contains(id)).collect()
+    }
+
+    // One past the highest logical CPU id, the bound for user-supplied CPU lists
+    pub fn id_limit(&self) -> usize {
+        self.cpus.iter().map(|c| c.id + 1).max().unwrap_or(0)
+    }
+
+    pub fn cpus_on_node(&self, node: usize) -> Vec<usize> {
+        self.sorted().iter().filter(|c| c.numa_node == node).map(|c| c.id).collect()
+    }
+
+    fn sorted(&self) -> Vec<&LogicalCpu> {
+        let mut cpus: Vec<&LogicalCpu> = self.cpus.iter().collect();
+        cpus.sort_by_key(|c| c.id);
+        cpus
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn detect_cpus() -> Vec<LogicalCpu> {
+    let read_usize = |path: String| -> Option<usize> {
+        std::fs::read_to_string(path).ok()?.trim().parse::<usize>().ok()
+    };
+    let mut cpus = Vec::new();
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu") else {
+        return cpus;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(id) = name.strip_prefix("cpu").and_then(|n| n.parse::<usize>().ok()) else {
+            continue;
+        };
+        let base = format!("/sys/devices/system/cpu/{}/topology", name);
+        let package = read_usize(format!("{}/physical_package_id", base)).unwrap_or(0);
+        let core = read_usize(format!("{}/core_id", base)).unwrap_or(id);
+        cpus.push(LogicalCpu { id, package, core, numa_node: 0 });
+    }
+    if let Ok(nodes) = std::fs::read_dir("/sys/devices/system/node") {
+        for entry in nodes.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(node) = name.strip_prefix("node").and_then(|n| n.parse::<usize>().ok()) else {
+                continue;
+            };
+            if let Ok(list) = std::fs::read_to_string(entry.path().join("cpulist")) {
+                let limit = cpus.iter().map(|c| c.id + 1).max().unwrap_or(0);
+                for id in parse_cpu_list(&list, limit).unwrap_or_default() {
+                    if let Some(cpu) = cpus.iter_mut().find(|c| c.id == id) {
+                        cpu.numa_node = node;
+                    }
+                }
+            }
+        }
+    }
+    cpus
+}
+
+#[cfg(windows)]
+fn detect_cpus() -> Vec<LogicalCpu> {
+    use winapi::um::sysinfoapi::GetLogicalProcessorInformation;
+    use winapi::um::winnt::{
+        RelationNumaNode, RelationProcessorCore, RelationProcessorPackage,
+        SYSTEM_LOGICAL_PROCESSOR_INFORMATION,
+    };
+    let mut len: u32 = 0;
+    unsafe {
+        GetLogicalProcessorInformation(std::ptr::null_mut(), &mut len);
+        let count = len as usize / std::mem::size_of::<SYSTEM_LOGICAL_PROCESSOR_INFORMATION>();
+        if count == 0 {
+            return Vec::new();
+        }
+        let mut buf: Vec<SYSTEM_LOGICAL_PROCESSOR_INFORMATION> = Vec::with_capacity(count);
+        if GetLogicalProcessorInformation(buf.as_mut_ptr(), &mut len) == 0 {
+            return Vec::new();
+        }
+        buf.set_len(count);
+        let mask_bits = |mask: usize| (0..usize::BITS as usize).filter(move |b| mask & (1 << b) != 0);
+        let mut cpus: Vec<LogicalCpu> = Vec::new();
+        let mut core_idx = 0;
+        let mut package_idx = 0;
+        for info in &buf {
+            if info.Relationship == RelationProcessorCore {
+                for id in mask_bits(info.ProcessorMask) {
+                    cpus.push(LogicalCpu { id, package: 0, core: core_idx, numa_node: 0 });
+                }
+                core_idx += 1;
+            }
+        }
+        for info in &buf {
+            if info.Relationship == RelationProcessorPackage {
+                for id in mask_bits(info.ProcessorMask) {
+                    if let Some(cpu) = cpus.iter_mut().find(|c| c.id == id) {
+                        cpu.package = package_idx;
+                    }
+                }
+                package_idx += 1;
+            } else if info.Relationship == RelationNumaNode {
+                let node = info.u.NumaNode().NodeNumber as usize;
+                for id in mask_bits(info.ProcessorMask) {
+                    if let Some(cpu) = cpus.iter_mut().find(|c| c.id == id) {
+                        cpu.numa_node = node;
+                    }
+                }
+            }
+        }
+        cpus
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn detect_cpus() -> Vec<LogicalCpu> {
+    Vec::new()
+}
+
+// Parses the kernel's cpulist format, e.g. "0-3,8,10-11". Ids at or above `limit` are dropped
+// before ranges are expanded, so a typo like "0-99999999999" can't allocate billions of entries.
+pub fn parse_cpu_list(s: &str, limit: usize) -> Option<Vec<usize>> {
+    let mut out = BTreeSet::new();
+    for part in s.trim().split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if let Some((lo, hi)) = part.split_once('-') {
+            let lo = lo.trim().parse::<usize>().ok()?;
+            let hi = hi.trim().parse::<usize>().ok()?;
+            if hi < lo {
+                return None;
+            }
+            if lo < limit {
+                out.extend(lo..=hi.min(limit - 1));
+            }
+        } else {
+            let id = part.parse::<usize>().ok()?;
+            if id < limit {
+                out.insert(id);
+            }
+        }
+    }
+    Some(out.into_iter().collect())
+}
+
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpu: usize) -> bool {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(windows)]
+pub fn pin_current_thread(cpu: usize) -> bool {
+    use winapi::um::processthreadsapi::GetCurrentThread;
+    use winapi::um::winbase::SetThreadAffinityMask;
+    if cpu >= usize::BITS as usize {
+        return false;
+    }
+    unsafe { SetThreadAffinityMask(GetCurrentThread(), 1usize << cpu) != 0 }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn pin_current_thread(_cpu: usize) -> bool {
+    false
+}
+
+// Workers that were given a CPU and couldn't be pinned to it, since the process started. Runners
+// read it before and after a run to report unpinned workers in their logs.
+static PIN_FAILURES: AtomicUsize = AtomicUsize::new(0);
+
+pub fn pin_failures() -> usize {
+    PIN_FAILURES.load(Ordering::SeqCst)
+}
+
+// Pins worker `tid` to its slot in a resolved placement; an empty placement leaves it to the OS.
+// False means the placement asked for a CPU and the OS refused, so the worker runs wherever it's put.
+pub fn pin_worker(cpus: &[usize], tid: usize) -> bool {
+    if cpus.is_empty() {
+        return true;
+    }
+    let cpu = cpus[tid % cpus.len()];
+    let pinned = pin_current_thread(cpu);
+    // Counted rather than printed; runners report the total in dev mode and in their log
+    if !pinned {
+        PIN_FAILURES.fetch_add(1, Ordering::SeqCst);
+    }
+    pinned
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PlacementPolicy {
+    OsDefault,
+    PhysicalCores,
+    SmtSiblings,
+    CoreList(String),
+    NumaNode(usize),
+}
+
+impl PlacementPolicy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlacementPolicy::OsDefault => "OS Default",
+            PlacementPolicy::PhysicalCores => "One Thread per Physical Core",
+            PlacementPolicy::SmtSiblings => "SMT Siblings Only",
+            PlacementPolicy::CoreList(_) => "Chosen Core List",
+            PlacementPolicy::NumaNode(_) => "NUMA Node",
+        }
+    }
+
+    // Logical CPUs to pin workers to, one worker per entry. Empty means "let the OS decide".
+    pub fn resolve(&self, topology: &CpuTopology) -> Vec<usize> {
+        match self {
+            PlacementPolicy::OsDefault => Vec::new(),
+            PlacementPolicy::PhysicalCores => topology.first_thread_per_core(),
+            PlacementPolicy::SmtSiblings => topology.smt_siblings(),
+            PlacementPolicy::CoreList(list) => parse_cpu_list(list, topology.id_limit())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|id| topology.cpus.iter().any(|c| c.id == *id))
+                .collect(),
+            PlacementPolicy::NumaNode(node) => topology.cpus_on_node(*node),
+        }
+    }
+}
+
+pub fn placement_ui(ui: &mut egui::Ui, id_salt: &str, policy: &mut PlacementPolicy) {
+    let topology = get();
+    ui.horizontal(|ui| {
+        ui.label("Thread Placement:");
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(policy.label())
+            .show_ui(ui, |ui| {
+                let options = [
+                    PlacementPolicy::OsDefault,
+                    PlacementPolicy::PhysicalCores,
+                    PlacementPolicy::SmtSiblings,
+                    PlacementPolicy::CoreList(String::from("0")),
+                    PlacementPolicy::NumaNode(0),
+                ];
+                for option in options {
+                    let selected = std::mem::discriminant(policy) == std::mem::discriminant(&option);
+                    if ui.selectable_label(selected, option.label()).clicked() && !selected {
+                        *policy = option;
+                    }
+                }
+            });
+        match policy {
+            PlacementPolicy::CoreList(list) => {
+                ui.label("CPUs:");
+                ui.add(egui::TextEdit::singleline(list).desired_width(120.0).hint_text("e.g. 0,2,4-7"));
+            }
+            PlacementPolicy::NumaNode(node) => {
+                let max_node = topology.numa_nodes().last().copied().unwrap_or(0);
+                ui.label("Node:");
+                ui.add(egui::DragValue::new(node).range(0..=max_node));
+            }
+            _ => {}
+        }
+    });
+    if *policy != PlacementPolicy::OsDefault {
+        // Resolved again only when the policy or the typed list changes, not every frame
+        let id = ui.make_persistent_id(id_salt).with("resolved");
+        let cached = ui.data(|d| d.get_temp::<(PlacementPolicy, Vec<usize>)>(id)).filter(|(p, _)| p == policy);
+        let cpus = match cached {
+            Some((_, cpus)) => cpus,
+            None => {
+                let cpus = policy.resolve(topology);
+                ui.data_mut(|d| d.insert_temp(id, (policy.clone(), cpus.clone())));
+                cpus
+            }
+        };
+        if cpus.is_empty() {
+            ui.colored_label(egui::Color32::YELLOW, "Placement matches no CPUs on this machine; the OS will place threads.");
+        } else {
+            ui.label(format!("{} thread(s) pinned to CPUs {:?}", cpus.len(), cpus));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cpu_list_expands_ranges_and_singles() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11", 16), Some(vec![0, 1, 2, 3, 8, 10, 11]));
+        assert_eq!(parse_cpu_list(" 2 , 0 - 1 ,\n", 16), Some(vec![0, 1, 2]));
+        assert_eq!(parse_cpu_list("3,3,1-3", 16), Some(vec![1, 2, 3]));
+        assert_eq!(parse_cpu_list("", 16), Some(vec![]));
+    }
+
+    #[test]
+    fn parse_cpu_list_drops_ids_past_the_limit() {
+        assert_eq!(parse_cpu_list("6-99999999999,2,40", 8), Some(vec![2, 6, 7]));
+        assert_eq!(parse_cpu_list("9", 8), Some(vec![]));
+        assert_eq!(parse_cpu_list("0-3", 0), Some(vec![]));
+    }
+
+    #[test]
+    fn parse_cpu_list_rejects_malformed_input() {
+        assert_eq!(parse_cpu_list("3-1", 16), None);
+        assert_eq!(parse_cpu_list("a", 16), None);
+        assert_eq!(parse_cpu_list("1-", 16), None);
+        assert_eq!(parse_cpu_list("-1", 16), None);
+    }
+}
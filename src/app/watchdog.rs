@@ -0,0 +1,211 @@
+use eframe::egui;
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{atomic::{AtomicU64, Ordering}, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use chrono::Local;
+use once_cell::sync::{Lazy, OnceCell};
+use sysinfo::{System, SystemExt};
+use crate::app::config::Config;
+use crate::app::cpu::sample_cpu_temperature;
+use crate::app::journal;
+use crate::app::telemetry;
+
+// The supervisor runs on its own thread instead of the UI loop, so a max-stress run that starves
+// the egui thread can still be stopped. Tests register a stop closure when they start; the
+// watchdog calls it when the UI or the test's engine loop stops responding, when the run
+// overshoots its configured duration, or when the CPU passes the temperature limit.
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone)]
+struct WatchdogSettings {
+    ui_timeout_secs: u32,
+    engine_timeout_secs: u32,
+    grace_secs: u32,
+    max_cpu_temp_c: f64,
+    log_dir: PathBuf,
+    dev_mode: bool,
+}
+
+struct WatchedRun {
+    name: String,
+    deadline: Instant,
+    // None until the engine loop first checks in; runs without such a loop never do
+    last_beat: Option<Instant>,
+    stop: Box<dyn Fn() + Send>,
+    stopped_at: Option<Instant>,
+}
+
+#[derive(Clone)]
+pub struct Intervention {
+    pub time: String,
+    pub test: String,
+    pub reason: &'static str,
+    pub detail: String,
+}
+
+static SETTINGS: OnceCell<WatchdogSettings> = OnceCell::new();
+static RUNS: Lazy<Mutex<HashMap<u64, WatchedRun>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+// Last frame of the UI (or headless loop); None while hidden or unfocused, when eframe may skip frames
+static UI_BEAT: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+static INTERVENTIONS: Lazy<Mutex<Vec<Intervention>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[cfg(windows)]
+fn raise_thread_priority() {
+    use winapi::um::processthreadsapi::{GetCurrentThread, SetThreadPriority};
+    use winapi::um::winbase::THREAD_PRIORITY_TIME_CRITICAL;
+    // Above the THREAD_PRIORITY_HIGHEST used by max-stress workers
+    unsafe {
+        SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_TIME_CRITICAL as i32);
+    }
+}
+#[cfg(not(windows))]
+fn raise_thread_priority() {}
+
+// Handle held by a test thread for as long as its run is going; dropping it ends supervision
+pub struct Watch {
+    id: u64,
+}
+
+impl Watch {
+    // Called from the test's own monitor loop to show the engine is still alive
+    pub fn heartbeat(&self) {
+        if let Some(run) = RUNS.lock().unwrap().get_mut(&self.id) {
+            run.last_beat = Some(Instant::now());
+        }
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        RUNS.lock().unwrap().remove(&self.id);
+    }
+}
+
+pub fn watch(name: &str, duration_secs: u64, stop: impl Fn() + Send + 'static) -> Watch {
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let grace = SETTINGS.get().map(|s| s.grace_secs).unwrap_or(0) as u64;
+    RUNS.lock().unwrap().insert(id, WatchedRun {
+        name: name.to_string(),
+        deadline: Instant::now() + Duration::from_secs(duration_secs + grace),
+        last_beat: None,
+        stop: Box::new(stop),
+        stopped_at: None,
+    });
+    Watch { id }
+}
+
+pub fn ui_heartbeat(hidden: bool) {
+    *UI_BEAT.lock().unwrap() = (!hidden).then(Instant::now);
+}
+
+pub fn interventions() -> Vec<Intervention> {
+    INTERVENTIONS.lock().unwrap().clone()
+}
+
+fn record(settings: &WatchdogSettings, test: &str, reason: &'static str, detail: String) {
+    let intervention = Intervention { time: Local::now().to_rfc3339(), test: test.to_string(), reason, detail };
+    if settings.dev_mode {
+        println!("[DEV] Watchdog stopped {}: {} ({})", test, reason, intervention.detail);
+    }
+    let _ = std::fs::create_dir_all(&settings.log_dir);
+    let path = settings.log_dir.join("watchdog.csv");
+    let is_new = !path.exists();
+    if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(&path) {
+        if is_new {
+            writeln!(f, "timestamp,test,reason,detail").ok();
+        }
+        writeln!(f, "{},{},{},{}", intervention.time, test, reason, intervention.detail.replace(',', ";")).ok();
+        // The machine is in trouble if we got here, so don't leave this in the page cache
+        f.sync_all().ok();
+    }
+    telemetry::emit("watchdog", "interventions", (INTERVENTIONS.lock().unwrap().len() + 1) as f64, None);
+    INTERVENTIONS.lock().unwrap().push(intervention);
+}
+
+fn check(settings: &WatchdogSettings, sys: &mut System) {
+    let mut runs = RUNS.lock().unwrap();
+    if runs.is_empty() {
+        return;
+    }
+    let now = Instant::now();
+    let ui_stalled = UI_BEAT.lock().unwrap()
+        .map(|beat| now.duration_since(beat).as_secs_f64())
+        .filter(|&secs| settings.ui_timeout_secs > 0 && secs > settings.ui_timeout_secs as f64);
+    // Only sampled while something runs; reading sensors isn't free on every platform
+    let temp = (settings.max_cpu_temp_c > 0.0).then(|| sample_cpu_temperature(sys)).flatten();
+    for run in runs.values_mut() {
+        if let Some(stopped_at) = run.stopped_at {
+            // The stop flag was set but the run hasn't ended. With the UI also unresponsive there is
+            // nothing left that can recover, so end the process before it takes the machine down.
+            if let Some(secs) = ui_stalled
+                && now.duration_since(stopped_at).as_secs() > settings.ui_timeout_secs as u64 {
+                record(settings, &run.name, "process_terminated", format!("run ignored stop for {:.0} s; UI unresponsive for {:.0} s", now.duration_since(stopped_at).as_secs_f64(), secs));
+                journal::close_active("watchdog_terminated");
+                std::process::exit(3);
+            }
+            continue;
+        }
+        let engine_stalled = run.last_beat
+            .map(|beat| now.duration_since(beat).as_secs_f64())
+            .filter(|&secs| settings.engine_timeout_secs > 0 && secs > settings.engine_timeout_secs as f64);
+        let intervention = if let Some(secs) = ui_stalled {
+            Some(("ui_unresponsive", format!("no UI frame for {:.0} s", secs)))
+        } else if let Some(secs) = engine_stalled {
+            Some(("engine_unresponsive", format!("no engine heartbeat for {:.0} s", secs)))
+        } else if now > run.deadline {
+            Some(("duration_exceeded", format!("still running {} s past its configured duration", settings.grace_secs)))
+        } else {
+            temp.filter(|&t| t as f64 >= settings.max_cpu_temp_c)
+                .map(|t| ("temperature_limit", format!("CPU at {:.1} °C, limit {:.1} °C", t, settings.max_cpu_temp_c)))
+        };
+        if let Some((reason, detail)) = intervention {
+            (run.stop)();
+            run.stopped_at = Some(now);
+            record(settings, &run.name, reason, detail);
+        }
+    }
+}
+
+// Starts the supervisor thread once; settings come from the WATCHDOG_* keys in stressors.ini
+pub fn start(config: &Config, dev_mode: bool) {
+    let log_dir = crate::app::log_dir(dev_mode);
+    let settings = WatchdogSettings {
+        ui_timeout_secs: config.watchdog_ui_timeout_secs,
+        engine_timeout_secs: config.watchdog_engine_timeout_secs,
+        grace_secs: config.watchdog_grace_secs,
+        max_cpu_temp_c: config.watchdog_max_cpu_temp_c,
+        log_dir,
+        dev_mode,
+    };
+    if SETTINGS.set(settings.clone()).is_err() {
+        return;
+    }
+    thread::spawn(move || {
+        raise_thread_priority();
+        let mut sys = System::new();
+        loop {
+            thread::sleep(CHECK_INTERVAL);
+            check(&settings, &mut sys);
+        }
+    });
+}
+
+// Interventions from this session, shown at the top of the Stress Tests tab
+pub fn ui_interventions(ui: &mut egui::Ui) {
+    let interventions = interventions();
+    if interventions.is_empty() {
+        return;
+    }
+    ui.colored_label(egui::Color32::RED, format!("⚠ Watchdog stopped {} run(s) this session:", interventions.len()));
+    for i in interventions.iter().rev() {
+        ui.label(format!("{}  {}: {} ({})", journal::format_time(&i.time), i.test, i.reason, i.detail));
+    }
+    ui.separator();
+}
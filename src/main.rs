@@ -1,6 +1,38 @@
 mod app;
 use crate::app::onload::OnLoadApp;
 use crate::app::SystemMonitorApp;
+use crate::app::scheduler::{self, ScheduledRun};
+use crate::app::telemetry::{self, TelemetryTarget};
+use crate::app::metrics;
+use crate::app::control;
+use crate::app::coordinator;
+use crate::app::journal;
+use crate::app::watchdog;
+use crate::app::config::Config;
+use crate::app::seed;
+
+const USAGE: &str = "\
+Usage: stressor [options] [--schedule <test> [--in <delay>] [--at <time>] [--every <hours>]]...
+
+Options (each overrides the matching key in stressors.ini):
+  --telemetry <target>    Stream live samples as NDJSON: off, stdout, tcp:<port> or unix:<path>
+  --metrics-port <port>   Serve Prometheus metrics at http://127.0.0.1:<port>/metrics
+  --control-port <port>   Serve the remote control API on this port
+  --control-bind <addr>   Address for the control API (default from CONTROL_BIND)
+  --control-token <token> Bearer token required by the control API
+  --seed <n>              Seed for workload input data and access patterns, to replay a run exactly
+  --headless              Run without a window; exits when nothing is running or scheduled
+                          unless the control API is enabled
+  --dev                   Headless only: use the source tree config and log folders
+
+Coordinator (drives other Stressor instances through their control API):
+  --coordinate <file>     Agents file, one \"<host:port> [token]\" per line; --control-token is the default token
+  --plan <test>[:secs]    Test every agent runs, e.g. system:600
+
+Tests: system (CPU + RAM + storage preset), cpu, storage, score (calibrated benchmark score)
+  --in <delay>            Start after a delay, e.g. 90s, 15m, 2h (default: now)
+  --at <time>             Start at a wall-clock time: HH:MM (next occurrence) or YYYY-MM-DDTHH:MM
+  --every <hours>         Repeat every N hours after the first run";
 
 enum AppState {
     Splash(OnLoadApp),
@@ -9,6 +41,8 @@ enum AppState {
 
 struct RootApp {
     state: AppState,
+    // Runs requested on the command line, queued once the main app exists
+    scheduled: Vec<ScheduledRun>,
 }
 
 impl eframe::App for RootApp {
@@ -18,7 +52,11 @@ impl eframe::App for RootApp {
                 splash.update(ctx, frame);
                 if splash.done {
                     let dev_mode = splash.dev_mode;
-                    self.state = AppState::Main(SystemMonitorApp::with_dev_mode(dev_mode));
+                    let mut main_app = SystemMonitorApp::with_dev_mode(dev_mode);
+                    for run in self.scheduled.drain(..) {
+                        main_app.stress_test.scheduler.enqueue(run);
+                    }
+                    self.state = AppState::Main(main_app);
                 }
             }
             AppState::Main(main_app) => {
@@ -26,12 +64,115 @@ impl eframe::App for RootApp {
             }
         }
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let AppState::Main(main_app) = &mut self.state {
+            main_app.on_exit();
+        }
+    }
+}
+
+fn fail(e: String) -> ! {
+    eprintln!("error: {}\n\n{}", e, USAGE);
+    std::process::exit(2);
+}
+
+// Removes `flag <value>` from args and returns the value
+fn take_flag(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let i = args.iter().position(|a| a == flag)?;
+    if i + 1 >= args.len() {
+        fail(format!("{} needs a value", flag));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Some(value)
+}
+
+// Same app state as the GUI, driven by a plain loop instead of eframe
+fn run_headless(scheduled: Vec<ScheduledRun>, dev_mode: bool) {
+    let ctx = eframe::egui::Context::default();
+    let mut app = SystemMonitorApp::with_dev_mode(dev_mode);
+    if let Some(err) = &app.last_error {
+        fail(err.clone());
+    }
+    // No banner to show these in, so report them the way the GUI would on launch
+    for run in app.analyzer.crashed_runs.iter().filter(|r| !r.acknowledged) {
+        eprintln!("warning: {} ({}, started {}): {}", run.label(), run.kind, journal::format_time(&run.started), run.readings());
+    }
+    for run in scheduled {
+        app.stress_test.scheduler.enqueue(run);
+    }
+    if !control::is_enabled() && app.stress_test.scheduler.is_empty() {
+        fail("--headless needs --schedule or a control port".to_string());
+    }
+    loop {
+        // This loop owns the tests here, so the watchdog treats it like the GUI thread
+        watchdog::ui_heartbeat(false);
+        app.update_system_data();
+        app.stress_test.run_scheduled(&ctx, dev_mode);
+        if !control::is_enabled() && app.stress_test.scheduler.is_empty() && !app.stress_test.is_running() {
+            break;
+        }
+        // Doubles as the loop's sleep
+        control::process_pending(&mut app.stress_test, &ctx, dev_mode, std::time::Duration::from_millis(200));
+    }
+    app.on_exit();
 }
 
 fn main() -> eframe::Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let dev_mode = args.iter().any(|a| a == "--dev");
+    let headless = args.iter().any(|a| a == "--headless");
+    args.retain(|a| a != "--dev" && a != "--headless");
+    // Started before the app loads its config so the command line wins
+    if let Some(spec) = take_flag(&mut args, "--telemetry")
+        && let Err(e) = TelemetryTarget::parse(&spec).and_then(telemetry::start) {
+        fail(e);
+    }
+    if let Some(value) = take_flag(&mut args, "--seed") {
+        let value = value.parse::<u64>().ok().filter(|&s| s != 0).unwrap_or_else(|| fail(format!("invalid seed '{}', use a positive integer", value)));
+        seed::init(value);
+    }
+    if let Some(port) = take_flag(&mut args, "--metrics-port") {
+        let port = port.parse::<u16>().unwrap_or_else(|_| fail(format!("invalid metrics port '{}'", port)));
+        if let Err(e) = metrics::start_server(port) {
+            fail(e);
+        }
+    }
+    let control_bind = take_flag(&mut args, "--control-bind");
+    let control_token = take_flag(&mut args, "--control-token");
+    if let Some(agents_file) = take_flag(&mut args, "--coordinate") {
+        let plan = take_flag(&mut args, "--plan").unwrap_or_else(|| fail("--coordinate needs --plan".to_string()));
+        let (target, duration_secs) = coordinator::parse_plan(&plan).unwrap_or_else(|e| fail(e));
+        let text = std::fs::read_to_string(&agents_file).unwrap_or_else(|e| fail(format!("cannot read {}: {}", agents_file, e)));
+        let agents = coordinator::parse_agents(&text, control_token.as_deref()).unwrap_or_else(|e| fail(e));
+        if let Err(e) = coordinator::run_campaign(&agents, target, duration_secs, dev_mode) {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(port) = take_flag(&mut args, "--control-port") {
+        let port = port.parse::<u16>().unwrap_or_else(|_| fail(format!("invalid control port '{}'", port)));
+        let config = Config::load(dev_mode);
+        let bind = control_bind.unwrap_or(config.control_bind);
+        let token = control_token.unwrap_or(config.control_token);
+        if let Err(e) = control::start_server(&bind, port, &token) {
+            fail(e);
+        }
+    }
+    let scheduled = scheduler::parse_args(&args).unwrap_or_else(|e| fail(e));
+    if headless {
+        run_headless(scheduled, dev_mode);
+        return Ok(());
+    }
     eframe::run_native(
         "Stressor",
         eframe::NativeOptions::default(),
-        Box::new(|_cc| Ok(Box::new(RootApp { state: AppState::Splash(OnLoadApp::default()) }))),
+        Box::new(|_cc| Ok(Box::new(RootApp { state: AppState::Splash(OnLoadApp::default()), scheduled }))),
     )
-}
\ No newline at end of file
+}